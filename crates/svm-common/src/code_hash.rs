@@ -0,0 +1,6 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents a contract code hash
+#[repr(transparent)]
+#[derive(Serialize, Deserialize, Hash, PartialEq, Eq, Copy, Clone, Debug)]
+pub struct CodeHash(pub [u8; 32]);
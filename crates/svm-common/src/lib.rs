@@ -5,6 +5,7 @@
 
 mod address;
 mod balance;
+mod code_hash;
 mod default_key_hasher;
 mod key_hasher;
 mod macros;
@@ -15,6 +16,7 @@ pub mod utils;
 
 pub use address::Address;
 pub use balance::Balance;
+pub use code_hash::CodeHash;
 pub use default_key_hasher::DefaultKeyHasher;
 pub use key_hasher::KeyHasher;
 pub use state::State;
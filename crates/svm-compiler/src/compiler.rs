@@ -1,12 +1,22 @@
-use wasmer_runtime_core::error::CompileResult;
+use std::collections::HashMap;
+
+use wasmer_runtime_core::cache::{Artifact, Error as CacheError};
+use wasmer_runtime_core::error::{CompileError, CompileResult};
 use wasmer_runtime_core::Module;
 
+use crate::gas::{estimate_program_gas, GasEstimate};
+
+/// The version of the code generated by `compile_program` (the middlewares and the opcodes cost table).
+/// Must be bumped on each change affecting the compiled code (e.g an opcode gas cost has changed),
+/// so that modules serialized by a previous version won't be loaded (see `deserialize_program`).
+pub const COMPILER_VERSION: u32 = 1;
+
 /// The `svm_compiler` macro returns a `wasmer singlepass compiler` with middlewares required by the `svm`.
 /// Since we can't say explicitly all the wildcards (`_`) we can't define a function
 /// returning a `StreamingCompiler<SinglePassMCG, _, _, _, _>` so we use a rust macro instead.
 macro_rules! svm_compiler {
     () => {{
-        use crate::middleware::ValidationMiddleware;
+        use crate::middleware::{MeteringMiddleware, ValidationMiddleware};
         use wasmer_runtime_core::codegen::{MiddlewareChain, StreamingCompiler};
         use wasmer_singlepass_backend::ModuleCodeGenerator as SinglePassMCG;
 
@@ -14,6 +24,7 @@ macro_rules! svm_compiler {
             StreamingCompiler::new(move || {
                 let mut chain = MiddlewareChain::new();
                 chain.push(ValidationMiddleware::new());
+                chain.push(MeteringMiddleware::new());
                 chain
             });
 
@@ -23,9 +34,71 @@ macro_rules! svm_compiler {
 
 /// This function is responsible on compiling a wasm program using the `wasmer singlepass` compiler along
 /// with the the middlewares required by `svm`.
+///
+/// The compiled code is gas metered, so the remaining gas of each instance must be set
+/// (see `gas::set_gas_left`) before calling any of its exported functions.
 #[must_use]
 pub fn compile_program(wasm: &[u8]) -> CompileResult<Module> {
     let compiler = svm_compiler!();
 
     wasmer_runtime_core::compile_with(wasm, &compiler)
 }
+
+/// Serializes a module compiled by `compile_program` (e.g for persisting it to disk).
+pub fn serialize_program(module: &Module) -> Result<Vec<u8>, CacheError> {
+    module.cache()?.serialize()
+}
+
+/// Loads a module serialized by `serialize_program`, skipping its compilation.
+///
+/// # Safety
+///
+/// `bytes` must be the output of `serialize_program` (of the same `COMPILER_VERSION`),
+/// since the loaded module machine code isn't validated.
+pub unsafe fn deserialize_program(bytes: &[u8]) -> Result<Module, CacheError> {
+    let artifact = Artifact::deserialize(bytes)?;
+    let compiler = svm_compiler!();
+
+    wasmer_runtime_core::load_cache_with(artifact, &compiler)
+}
+
+/// A compiled wasm program along with the ahead-of-time gas estimation of its exported functions.
+#[derive(Clone)]
+pub struct CompiledProgram {
+    /// The compiled `wasmer` module
+    pub module: Module,
+
+    /// The gas estimation of each exported function (keyed by the export name)
+    pub exports_gas: HashMap<String, GasEstimate>,
+}
+
+impl CompiledProgram {
+    /// Returns the gas estimation of exported function `func_name`.
+    /// Returns `None` when there is no such exported function.
+    pub fn export_gas(&self, func_name: &str) -> Option<GasEstimate> {
+        self.exports_gas.get(func_name).copied()
+    }
+}
+
+/// Compiles the wasm program (see `compile_program`) and computes the gas estimation of its exported functions.
+/// Having the estimations, the node can reject transactions whose gas limit can't cover the
+/// executed function (when loop-free), without instantiating the program.
+///
+/// `import_gas` returns the fixed gas of each imported function (see `gas::estimate_program_gas`).
+#[must_use]
+pub fn compile_program_with_gas<F>(wasm: &[u8], import_gas: F) -> CompileResult<CompiledProgram>
+where
+    F: Fn(&str, &str) -> Option<u64>,
+{
+    let module = compile_program(wasm)?;
+
+    let exports_gas =
+        estimate_program_gas(wasm, import_gas).map_err(|e| CompileError::InternalError {
+            msg: format!("gas estimation failed: {:?}", e),
+        })?;
+
+    Ok(CompiledProgram {
+        module,
+        exports_gas,
+    })
+}
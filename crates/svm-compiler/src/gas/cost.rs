@@ -0,0 +1,66 @@
+use wasmer_runtime_core::wasmparser::Operator;
+
+/// The gas charged for each page (64KiB) requested by `memory.grow` (on top of the opcode cost).
+/// Charged at runtime by the `MeteringMiddleware`, right before growing the memory (even if the grow fails).
+pub const MEMORY_GROW_PAGE_GAS: u64 = 10_000;
+
+/// Returns the gas cost of a single wasm opcode.
+/// Opcodes not supported by `svm` (see `ValidationMiddleware`) will never reach the metering,
+/// so they fall into the default bucket.
+///
+/// The `memory.grow` cost excludes the per-page cost (see `MEMORY_GROW_PAGE_GAS`).
+pub fn op_gas_cost(op: &Operator) -> u64 {
+    match op {
+        Operator::Nop
+        | Operator::Block { .. }
+        | Operator::Loop { .. }
+        | Operator::Else
+        | Operator::End => 0,
+        Operator::Unreachable
+        | Operator::If { .. }
+        | Operator::Br { .. }
+        | Operator::BrIf { .. }
+        | Operator::Return
+        | Operator::Drop
+        | Operator::Select
+        | Operator::GetLocal { .. }
+        | Operator::SetLocal { .. }
+        | Operator::TeeLocal { .. }
+        | Operator::I32Const { .. }
+        | Operator::I64Const { .. } => 1,
+        Operator::BrTable { .. } | Operator::GetGlobal { .. } | Operator::SetGlobal { .. } => 2,
+        Operator::I32Load { .. }
+        | Operator::I64Load { .. }
+        | Operator::I32Load8S { .. }
+        | Operator::I32Load8U { .. }
+        | Operator::I32Load16S { .. }
+        | Operator::I32Load16U { .. }
+        | Operator::I64Load8S { .. }
+        | Operator::I64Load8U { .. }
+        | Operator::I64Load16S { .. }
+        | Operator::I64Load16U { .. }
+        | Operator::I64Load32S { .. }
+        | Operator::I64Load32U { .. }
+        | Operator::I32Store { .. }
+        | Operator::I64Store { .. }
+        | Operator::I32Store8 { .. }
+        | Operator::I32Store16 { .. }
+        | Operator::I64Store8 { .. }
+        | Operator::I64Store16 { .. }
+        | Operator::I64Store32 { .. } => 3,
+        Operator::I32Mul | Operator::I64Mul => 3,
+        Operator::I32DivS
+        | Operator::I32DivU
+        | Operator::I32RemS
+        | Operator::I32RemU
+        | Operator::I64DivS
+        | Operator::I64DivU
+        | Operator::I64RemS
+        | Operator::I64RemU => 8,
+        Operator::Call { .. } => 10,
+        Operator::CallIndirect { .. } => 15,
+        Operator::MemorySize { .. } => 2,
+        Operator::MemoryGrow { .. } => 1000,
+        _ => 1,
+    }
+}
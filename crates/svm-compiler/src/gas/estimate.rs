@@ -0,0 +1,510 @@
+use std::collections::{HashMap, HashSet};
+
+use wasmer_runtime_core::wasmparser::{
+    BinaryReaderError, ExternalKind, ImportSectionEntryType, ModuleReader, Operator, SectionCode,
+};
+
+use super::op_gas_cost;
+
+/// The ahead-of-time gas estimation of a function
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GasEstimate {
+    /// The function is loop-free, calls only imported functions of a fixed gas cost and doesn't grow the memory.
+    /// Holds the maximum gas a single call to it may consume.
+    Fixed(u64),
+
+    /// The function (or one of its callees) has a back-edge branch (`br / br_if / br_table` to a `loop`)
+    HasLoops,
+
+    /// The function may call itself (directly or via other functions)
+    HasRecursion,
+
+    /// The function (or one of its callees) uses `call_indirect`, so its callees can't be known ahead-of-time
+    HasIndirectCalls,
+
+    /// The function (or one of its callees) calls an imported function (e.g `contract_call`),
+    /// whose gas consumption isn't metered by the program code and isn't fixed
+    HasImportedCalls,
+
+    /// The function (or one of its callees) uses `memory.grow`, whose cost depends on the requested pages count
+    /// (see `MEMORY_GROW_PAGE_GAS`)
+    HasMemoryGrow,
+}
+
+/// The reason `estimate_program_gas` failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GasEstimateError {
+    /// The program isn't a valid wasm module
+    InvalidWasm,
+
+    /// Function `func_idx` is neither imported nor defined by the program
+    FuncNotFound(u32),
+
+    /// The body of function `func_idx` has unbalanced blocks (a missing `end`, or a misplaced `else`)
+    MalformedBody(u32),
+}
+
+impl From<BinaryReaderError> for GasEstimateError {
+    fn from(_e: BinaryReaderError) -> Self {
+        GasEstimateError::InvalidWasm
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum FrameKind {
+    Block,
+    Loop,
+    If,
+}
+
+struct Frame {
+    kind: FrameKind,
+    cost: u64,
+    then_cost: Option<u64>,
+}
+
+impl Frame {
+    fn new(kind: FrameKind) -> Self {
+        Self {
+            kind,
+            cost: 0,
+            then_cost: None,
+        }
+    }
+
+    /// For an `if` block we take the most expensive branch
+    fn total_cost(&self) -> u64 {
+        match self.then_cost {
+            Some(then_cost) => std::cmp::max(then_cost, self.cost),
+            None => self.cost,
+        }
+    }
+}
+
+struct Estimator<'b, 'a: 'b> {
+    imports_gas: Vec<Option<u64>>,
+    bodies: &'b [Vec<Operator<'a>>],
+    memo: HashMap<u32, GasEstimate>,
+    visiting: HashSet<u32>,
+}
+
+impl<'b, 'a: 'b> Estimator<'b, 'a> {
+    fn estimate_func(&mut self, func_idx: u32) -> Result<GasEstimate, GasEstimateError> {
+        if let Some(import_gas) = self.imports_gas.get(func_idx as usize) {
+            return match import_gas {
+                Some(gas) => Ok(GasEstimate::Fixed(*gas)),
+                None => Ok(GasEstimate::HasImportedCalls),
+            };
+        }
+
+        if let Some(estimate) = self.memo.get(&func_idx) {
+            return Ok(*estimate);
+        }
+
+        if !self.visiting.insert(func_idx) {
+            return Ok(GasEstimate::HasRecursion);
+        }
+
+        let estimate = self.estimate_body(func_idx)?;
+
+        self.visiting.remove(&func_idx);
+        self.memo.insert(func_idx, estimate);
+
+        Ok(estimate)
+    }
+
+    fn estimate_body(&mut self, func_idx: u32) -> Result<GasEstimate, GasEstimateError> {
+        let bodies = self.bodies;
+        let body = bodies
+            .get(func_idx as usize - self.imports_gas.len())
+            .ok_or(GasEstimateError::FuncNotFound(func_idx))?;
+
+        // the function body acts as the outermost block
+        let mut frames = vec![Frame::new(FrameKind::Block)];
+
+        for op in body.iter() {
+            let op_cost = op_gas_cost(op);
+
+            match op {
+                Operator::Block { .. } | Operator::Loop { .. } | Operator::If { .. } => {
+                    let kind = match op {
+                        Operator::Block { .. } => FrameKind::Block,
+                        Operator::Loop { .. } => FrameKind::Loop,
+                        _ => FrameKind::If,
+                    };
+
+                    add_cost(&mut frames, op_cost);
+                    frames.push(Frame::new(kind));
+                    continue;
+                }
+                Operator::Else => {
+                    let frame = frames.last_mut().unwrap();
+
+                    if frame.kind != FrameKind::If || frame.then_cost.is_some() {
+                        return Err(GasEstimateError::MalformedBody(func_idx));
+                    }
+
+                    frame.then_cost = Some(frame.cost);
+                    frame.cost = 0;
+                    continue;
+                }
+                Operator::End => {
+                    // there is always a frame here (we return once the outermost block ends)
+                    let frame = frames.pop().unwrap();
+
+                    if frames.is_empty() {
+                        return Ok(GasEstimate::Fixed(frame.total_cost()));
+                    }
+
+                    add_cost(&mut frames, frame.total_cost());
+                    continue;
+                }
+                Operator::Br { relative_depth } | Operator::BrIf { relative_depth } => {
+                    if is_back_edge(&frames, *relative_depth) {
+                        return Ok(GasEstimate::HasLoops);
+                    }
+                }
+                Operator::BrTable { table } => {
+                    let (targets, default_target) = table.read_table()?;
+
+                    let has_back_edge = targets
+                        .iter()
+                        .chain(std::iter::once(&default_target))
+                        .any(|depth| is_back_edge(&frames, *depth));
+
+                    if has_back_edge {
+                        return Ok(GasEstimate::HasLoops);
+                    }
+                }
+                Operator::Call { function_index } => match self.estimate_func(*function_index)? {
+                    GasEstimate::Fixed(callee_cost) => add_cost(&mut frames, callee_cost),
+                    estimate => return Ok(estimate),
+                },
+                Operator::CallIndirect { .. } => return Ok(GasEstimate::HasIndirectCalls),
+                Operator::MemoryGrow { .. } => return Ok(GasEstimate::HasMemoryGrow),
+                _ => (),
+            }
+
+            add_cost(&mut frames, op_cost);
+        }
+
+        // a valid function body always terminates with an `end`
+        Err(GasEstimateError::MalformedBody(func_idx))
+    }
+}
+
+fn add_cost(frames: &mut Vec<Frame>, cost: u64) {
+    let frame = frames.last_mut().unwrap();
+    frame.cost = frame.cost.saturating_add(cost);
+}
+
+/// A branch to a `loop` block jumps backward (to the `loop` start)
+fn is_back_edge(frames: &[Frame], relative_depth: u32) -> bool {
+    match frames.len().checked_sub(1 + relative_depth as usize) {
+        Some(idx) => frames[idx].kind == FrameKind::Loop,
+        None => false,
+    }
+}
+
+/// Walks the control-flow of each function of the `wasm` program and returns the gas estimation
+/// of each exported function (keyed by the export name).
+///
+/// The estimation uses the same opcodes cost table as the `MeteringMiddleware`, so a `GasEstimate::Fixed`
+/// estimation is an upper-bound for the gas metered when calling the exported function.
+///
+/// * `wasm`       - the wasm program
+/// * `import_gas` - given an import `(module, field)` returns the fixed gas charged by the runtime
+///                  for calling such a function (on top of the `call` opcode), or `None` when its gas isn't fixed
+///
+/// Fails when the `wasm` program can't be parsed (the estimation doesn't validate the program,
+/// see `validate_program`).
+pub fn estimate_program_gas<F>(
+    wasm: &[u8],
+    import_gas: F,
+) -> Result<HashMap<String, GasEstimate>, GasEstimateError>
+where
+    F: Fn(&str, &str) -> Option<u64>,
+{
+    let mut reader = ModuleReader::new(wasm)?;
+
+    let mut imports_gas = Vec::new();
+    let mut exports = Vec::new();
+    let mut bodies = Vec::new();
+
+    while !reader.eof() {
+        let section = reader.read()?;
+
+        match section.code {
+            SectionCode::Import => {
+                for import in section.get_import_section_reader()? {
+                    let import = import?;
+
+                    if let ImportSectionEntryType::Function(..) = import.ty {
+                        imports_gas.push(import_gas(import.module, import.field));
+                    }
+                }
+            }
+            SectionCode::Export => {
+                for export in section.get_export_section_reader()? {
+                    let export = export?;
+
+                    if let ExternalKind::Function = export.kind {
+                        exports.push((export.field.to_string(), export.index));
+                    }
+                }
+            }
+            SectionCode::Code => {
+                for body in section.get_code_section_reader()? {
+                    let ops = body?
+                        .get_operators_reader()?
+                        .into_iter()
+                        .collect::<Result<Vec<Operator>, _>>()?;
+
+                    bodies.push(ops);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut estimator = Estimator {
+        imports_gas,
+        bodies: &bodies,
+        memo: HashMap::new(),
+        visiting: HashSet::new(),
+    };
+
+    let mut estimates = HashMap::new();
+
+    for (name, func_idx) in exports {
+        let estimate = estimator.estimate_func(func_idx)?;
+        estimates.insert(name, estimate);
+    }
+
+    Ok(estimates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! estimate {
+        ($input: expr, $func: expr) => {{
+            let wasm = wabt::wat2wasm($input).unwrap();
+            let estimates = estimate_program_gas(&wasm, |_module, _field| None).unwrap();
+
+            *estimates.get($func).unwrap()
+        }};
+    }
+
+    #[test]
+    fn estimate_straight_line_func() {
+        let input = r#"
+            (module
+                (func (export "sum") (param i32 i32) (result i32)
+                    get_local 0
+                    get_local 1
+                    i32.add
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::Fixed(3), estimate!(input, "sum"));
+    }
+
+    #[test]
+    fn estimate_if_else_takes_the_max_branch() {
+        let input = r#"
+            (module
+                (func (export "branch") (param i32) (result i32)
+                    get_local 0
+                    if (result i32)
+                        i32.const 1
+                    else
+                        get_local 0
+                        get_local 0
+                        i32.mul
+                    end
+                ))
+            "#;
+
+        // `get_local` + `if` + max(`i32.const`, `get_local` x 2 + `i32.mul`)
+        assert_eq!(GasEstimate::Fixed(1 + 1 + 5), estimate!(input, "branch"));
+    }
+
+    #[test]
+    fn estimate_adds_callee_cost() {
+        let input = r#"
+            (module
+                (func $inner (result i32)
+                    i32.const 1)
+
+                (func (export "outer") (result i32)
+                    call $inner
+                ))
+            "#;
+
+        // `call` + `i32.const`
+        assert_eq!(GasEstimate::Fixed(10 + 1), estimate!(input, "outer"));
+    }
+
+    #[test]
+    fn estimate_loop_without_back_edge_is_fixed() {
+        let input = r#"
+            (module
+                (func (export "once") (result i32)
+                    (loop (result i32)
+                        i32.const 1)
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::Fixed(1), estimate!(input, "once"));
+    }
+
+    #[test]
+    fn estimate_loop_with_back_edge() {
+        let input = r#"
+            (module
+                (func (export "forever")
+                    (loop
+                        br 0)
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::HasLoops, estimate!(input, "forever"));
+    }
+
+    #[test]
+    fn estimate_loop_in_callee() {
+        let input = r#"
+            (module
+                (func $forever
+                    (loop
+                        i32.const 0
+                        br_table 0 0))
+
+                (func (export "outer")
+                    call $forever
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::HasLoops, estimate!(input, "outer"));
+    }
+
+    #[test]
+    fn estimate_recursive_calls() {
+        let input = r#"
+            (module
+                (func $ping (export "ping")
+                    call $pong)
+
+                (func $pong
+                    call $ping
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::HasRecursion, estimate!(input, "ping"));
+    }
+
+    #[test]
+    fn estimate_indirect_calls() {
+        let input = r#"
+            (module
+                (type $t (func))
+                (table 1 anyfunc)
+
+                (func (export "indirect")
+                    i32.const 0
+                    call_indirect (type $t)
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::HasIndirectCalls, estimate!(input, "indirect"));
+    }
+
+    #[test]
+    fn estimate_imported_calls() {
+        let input = r#"
+            (module
+                (func $contract_call (import "svm" "contract_call") (param i32 i32) (result i64))
+
+                (func $inner (result i64)
+                    i32.const 0
+                    i32.const 0
+                    call $contract_call)
+
+                (func (export "outer") (result i64)
+                    call $inner
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::HasImportedCalls, estimate!(input, "outer"));
+    }
+
+    #[test]
+    fn estimate_fixed_gas_imported_calls() {
+        let input = r#"
+            (module
+                (func $get_layer_id (import "svm" "get_layer_id") (result i64))
+                (func $contract_call (import "svm" "contract_call") (param i32 i32) (result i64))
+
+                (func (export "layer") (result i64)
+                    call $get_layer_id)
+
+                (func (export "call") (result i64)
+                    i32.const 0
+                    i32.const 0
+                    call $contract_call
+                ))
+            "#;
+
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let import_gas = |module: &str, field: &str| match (module, field) {
+            ("svm", "get_layer_id") => Some(100),
+            _ => None,
+        };
+
+        let estimates = estimate_program_gas(&wasm, import_gas).unwrap();
+
+        // `call` + the imported function gas
+        assert_eq!(Some(&GasEstimate::Fixed(10 + 100)), estimates.get("layer"));
+        assert_eq!(Some(&GasEstimate::HasImportedCalls), estimates.get("call"));
+    }
+
+    #[test]
+    fn estimate_memory_grow() {
+        let input = r#"
+            (module
+                (memory 1)
+
+                (func (export "grow") (result i32)
+                    i32.const 1
+                    memory.grow
+                ))
+            "#;
+
+        assert_eq!(GasEstimate::HasMemoryGrow, estimate!(input, "grow"));
+    }
+
+    #[test]
+    fn estimate_invalid_wasm() {
+        let res = estimate_program_gas(&[0, 1, 2, 3], |_module, _field| None);
+
+        assert_eq!(Err(GasEstimateError::InvalidWasm), res);
+    }
+
+    #[test]
+    fn estimate_exported_func_without_body() {
+        // a module exporting function `1` while declaring a single function (with no code section)
+        #[rustfmt::skip]
+        let wasm = [
+            0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, // magic | version
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00,             // type section: `() -> ()`
+            0x03, 0x02, 0x01, 0x00,                         // function section: one function
+            0x07, 0x07, 0x01, 0x03, b'f', b'o', b'o', 0x00, 0x01, // export section: `foo` -> func `1`
+        ];
+
+        let res = estimate_program_gas(&wasm, |_module, _field| None);
+
+        assert_eq!(Err(GasEstimateError::FuncNotFound(1)), res);
+    }
+}
@@ -0,0 +1,56 @@
+mod cost;
+mod estimate;
+
+pub use cost::{op_gas_cost, MEMORY_GROW_PAGE_GAS};
+pub use estimate::{estimate_program_gas, GasEstimate, GasEstimateError};
+
+use wasmer_runtime_core::{
+    vm::{Ctx, InternalField},
+    Instance,
+};
+
+/// The `wasmer` instance internal field holding the instance's remaining gas.
+/// The field is decremented by the code injected by the `MeteringMiddleware` at the end of each basic block.
+pub(crate) static GAS_LEFT_FIELD: InternalField = InternalField::allocate();
+
+/// A scratch `wasmer` internal field holding the pages count requested by the executing `memory.grow`.
+/// Used by the code injected by the `MeteringMiddleware` for charging the grown pages.
+pub(crate) static GROW_PAGES_FIELD: InternalField = InternalField::allocate();
+
+/// The error raised (via a `wasmer` breakpoint) when an instance runs out of gas.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OutOfGasError;
+
+/// Returns the remaining gas of a `wasmer` instance
+pub fn get_gas_left(instance: &Instance) -> u64 {
+    instance.get_internal(&GAS_LEFT_FIELD)
+}
+
+/// Sets the remaining gas of a `wasmer` instance. Should be called right after instantiation
+/// (and before calling any exported function), otherwise the instance has no gas at all.
+pub fn set_gas_left(instance: &mut Instance, gas: u64) {
+    instance.set_internal(&GAS_LEFT_FIELD, gas);
+}
+
+/// Makes `lib` charge its gas from the remaining gas of `instance`, i.e both instances share a single gas counter.
+/// Used for instances whose exported functions are imported by `instance` (libraries).
+///
+/// # Safety
+///
+/// The `wasmer` internal fields of `lib` will point to the internal fields of `instance`,
+/// so `lib` must not outlive `instance` (e.g `lib` is owned by the `instance` data).
+pub unsafe fn share_gas_left(instance: &mut Instance, lib: &mut Instance) {
+    let internals = instance.context_mut().internal.internals;
+
+    lib.context_mut().internal.internals = internals;
+}
+
+/// Returns the remaining gas given a `wasmer` context (to be used within vmcalls)
+pub fn get_gas_left_ctx(ctx: &Ctx) -> u64 {
+    ctx.get_internal(&GAS_LEFT_FIELD)
+}
+
+/// Sets the remaining gas given a `wasmer` context (to be used within vmcalls)
+pub fn set_gas_left_ctx(ctx: &mut Ctx, gas: u64) {
+    ctx.set_internal(&GAS_LEFT_FIELD, gas);
+}
@@ -8,5 +8,13 @@
 mod compiler;
 
 mod middleware;
+mod validate;
 
-pub use compiler::compile_program;
+/// Gas related code (the opcodes cost table, ahead-of-time gas estimation and the instance's remaining gas accessors)
+pub mod gas;
+
+pub use compiler::{
+    compile_program, compile_program_with_gas, deserialize_program, serialize_program,
+    CompiledProgram, COMPILER_VERSION,
+};
+pub use validate::{validate_program, ValidationError, MAX_MEMORY_PAGES};
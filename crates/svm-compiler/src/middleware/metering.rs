@@ -0,0 +1,307 @@
+use wasmer_runtime_core::{
+    codegen::{Event, EventSink, FunctionMiddleware, InternalEvent},
+    module::ModuleInfo,
+    wasmparser::{Operator, Type as WpType, TypeOrFuncType as WpTypeOrFuncType},
+};
+
+use crate::gas::{
+    op_gas_cost, OutOfGasError, GAS_LEFT_FIELD, GROW_PAGES_FIELD, MEMORY_GROW_PAGE_GAS,
+};
+
+use super::error::ParseError;
+
+/// The `MeteringMiddleware` charges gas for executed code.
+///
+/// Each opcode has its cost (see `gas::op_gas_cost`). The costs are accumulated per basic block,
+/// and right before the opcode closing the block (a branch, a call, `end` etc.) we inject code that:
+/// * traps (with `OutOfGasError`) if the instance's remaining gas is less than the block cost.
+/// * otherwise, decrements the block cost from the instance's remaining gas.
+///
+/// Right before each `memory.grow` we also inject code charging `MEMORY_GROW_PAGE_GAS` per requested page
+/// (the pages count is known only at runtime).
+///
+/// The remaining gas is kept in a `wasmer` internal field, so the compiled module doesn't depend on
+/// the gas limit. The runtime sets the gas limit per instance (see `gas::set_gas_left`).
+pub struct MeteringMiddleware {
+    block_cost: u64,
+}
+
+impl MeteringMiddleware {
+    pub fn new() -> Self {
+        Self { block_cost: 0 }
+    }
+
+    fn inject_charge<'a, 'b: 'a>(&mut self, sink: &mut EventSink<'a, 'b>) {
+        if self.block_cost == 0 {
+            return;
+        }
+
+        let field_idx = GAS_LEFT_FIELD.index() as _;
+        let cost = self.block_cost as i64;
+
+        // if gas_left < block_cost { trap }
+        sink.push(Event::Internal(InternalEvent::GetInternal(field_idx)));
+        sink.push(Event::WasmOwned(Operator::I64Const { value: cost }));
+        sink.push(Event::WasmOwned(Operator::I64LtU));
+        sink.push(Event::WasmOwned(Operator::If {
+            ty: WpTypeOrFuncType::Type(WpType::EmptyBlockType),
+        }));
+        sink.push(Event::Internal(InternalEvent::Breakpoint(Box::new(|_| {
+            Err(Box::new(OutOfGasError))
+        }))));
+        sink.push(Event::WasmOwned(Operator::End));
+
+        // gas_left = gas_left - block_cost
+        sink.push(Event::Internal(InternalEvent::GetInternal(field_idx)));
+        sink.push(Event::WasmOwned(Operator::I64Const { value: cost }));
+        sink.push(Event::WasmOwned(Operator::I64Sub));
+        sink.push(Event::Internal(InternalEvent::SetInternal(field_idx)));
+
+        self.block_cost = 0;
+    }
+
+    /// Expects the requested pages count (an `i32`) at the top of the stack, and leaves it there
+    fn inject_memory_grow_charge<'a, 'b: 'a>(&mut self, sink: &mut EventSink<'a, 'b>) {
+        let gas_idx = GAS_LEFT_FIELD.index() as _;
+        let pages_idx = GROW_PAGES_FIELD.index() as _;
+        let page_cost = MEMORY_GROW_PAGE_GAS as i64;
+
+        // pages = (u64) pages_count
+        sink.push(Event::WasmOwned(Operator::I64ExtendUI32));
+        sink.push(Event::Internal(InternalEvent::SetInternal(pages_idx)));
+
+        // if gas_left < pages * page_cost { trap }
+        // (`pages` is at most `2^32 - 1`, so the multiplication can't overflow)
+        sink.push(Event::Internal(InternalEvent::GetInternal(gas_idx)));
+        sink.push(Event::Internal(InternalEvent::GetInternal(pages_idx)));
+        sink.push(Event::WasmOwned(Operator::I64Const { value: page_cost }));
+        sink.push(Event::WasmOwned(Operator::I64Mul));
+        sink.push(Event::WasmOwned(Operator::I64LtU));
+        sink.push(Event::WasmOwned(Operator::If {
+            ty: WpTypeOrFuncType::Type(WpType::EmptyBlockType),
+        }));
+        sink.push(Event::Internal(InternalEvent::Breakpoint(Box::new(|_| {
+            Err(Box::new(OutOfGasError))
+        }))));
+        sink.push(Event::WasmOwned(Operator::End));
+
+        // gas_left = gas_left - pages * page_cost
+        sink.push(Event::Internal(InternalEvent::GetInternal(gas_idx)));
+        sink.push(Event::Internal(InternalEvent::GetInternal(pages_idx)));
+        sink.push(Event::WasmOwned(Operator::I64Const { value: page_cost }));
+        sink.push(Event::WasmOwned(Operator::I64Mul));
+        sink.push(Event::WasmOwned(Operator::I64Sub));
+        sink.push(Event::Internal(InternalEvent::SetInternal(gas_idx)));
+
+        // restores the pages count for the `memory.grow`
+        sink.push(Event::Internal(InternalEvent::GetInternal(pages_idx)));
+        sink.push(Event::WasmOwned(Operator::I32WrapI64));
+    }
+}
+
+impl FunctionMiddleware for MeteringMiddleware {
+    type Error = ParseError;
+
+    fn feed_event<'a, 'b: 'a>(
+        &mut self,
+        event: Event<'a, 'b>,
+        _module_info: &ModuleInfo,
+        sink: &mut EventSink<'a, 'b>,
+    ) -> Result<(), Self::Error> {
+        match event {
+            Event::Internal(InternalEvent::FunctionBegin(_)) => self.block_cost = 0,
+            Event::Wasm(&ref op) | Event::WasmOwned(ref op) => {
+                self.block_cost += op_gas_cost(op);
+
+                if let Operator::MemoryGrow { .. } = op {
+                    self.inject_memory_grow_charge(sink);
+                }
+
+                if is_block_boundary(op) {
+                    self.inject_charge(sink);
+                }
+            }
+            _ => (),
+        }
+
+        sink.push(event);
+        Ok(())
+    }
+}
+
+/// Returns whether `op` ends the current basic block
+fn is_block_boundary(op: &Operator) -> bool {
+    match op {
+        Operator::Block { .. }
+        | Operator::Loop { .. }
+        | Operator::If { .. }
+        | Operator::Else
+        | Operator::End
+        | Operator::Unreachable
+        | Operator::Br { .. }
+        | Operator::BrIf { .. }
+        | Operator::BrTable { .. }
+        | Operator::Call { .. }
+        | Operator::CallIndirect { .. }
+        | Operator::Return => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gas::{
+        get_gas_left, op_gas_cost, set_gas_left, GasEstimate, OutOfGasError, MEMORY_GROW_PAGE_GAS,
+    };
+    use crate::{compile_program, compile_program_with_gas};
+    use wasmer_runtime::error::RuntimeError;
+    use wasmer_runtime::{imports, Func};
+    use wasmer_runtime_core::wasmparser::Operator;
+
+    #[test]
+    fn metering_charges_straight_line_code() {
+        let input = r#"
+            (module
+                (func (export "sum") (param i32 i32) (result i32)
+                    get_local 0
+                    get_local 1
+                    i32.add
+                ))
+            "#;
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let module = compile_program(&wasm).unwrap();
+        let mut instance = module.instantiate(&imports! {}).unwrap();
+        set_gas_left(&mut instance, 1000);
+
+        let func: Func<(i32, i32), i32> = instance.func("sum").unwrap();
+        assert_eq!(30, func.call(10, 20).unwrap());
+
+        // `get_local` x 2 + `i32.add`
+        assert_eq!(1000 - 3, get_gas_left(&instance));
+    }
+
+    #[test]
+    fn metering_traps_on_infinite_loop() {
+        let input = r#"
+            (module
+                (func (export "forever")
+                    (loop
+                        br 0)
+                ))
+            "#;
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let module = compile_program(&wasm).unwrap();
+        let mut instance = module.instantiate(&imports! {}).unwrap();
+        set_gas_left(&mut instance, 1000);
+
+        let func: Func<(), ()> = instance.func("forever").unwrap();
+        let res = func.call();
+
+        match res {
+            Err(RuntimeError::Error { data }) => {
+                assert!(data.downcast_ref::<OutOfGasError>().is_some());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn metering_no_gas_traps() {
+        let input = r#"
+            (module
+                (func (export "one") (result i32)
+                    i32.const 1
+                ))
+            "#;
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let module = compile_program(&wasm).unwrap();
+        let instance = module.instantiate(&imports! {}).unwrap();
+
+        let func: Func<(), i32> = instance.func("one").unwrap();
+        assert!(func.call().is_err());
+    }
+
+    #[test]
+    fn metering_charges_up_to_the_estimate() {
+        let input = r#"
+            (module
+                (func (export "branch") (param i32) (result i32)
+                    get_local 0
+                    if (result i32)
+                        i32.const 1
+                    else
+                        get_local 0
+                        get_local 0
+                        i32.mul
+                    end
+                ))
+            "#;
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let program = compile_program_with_gas(&wasm, |_module, _field| None).unwrap();
+        assert_eq!(Some(GasEstimate::Fixed(7)), program.export_gas("branch"));
+
+        let mut instance = program.module.instantiate(&imports! {}).unwrap();
+        set_gas_left(&mut instance, 7);
+
+        // `0` takes the `else` (most expensive) branch
+        let func: Func<i32, i32> = instance.func("branch").unwrap();
+        assert_eq!(0, func.call(0).unwrap());
+        assert_eq!(0, get_gas_left(&instance));
+    }
+
+    #[test]
+    fn metering_charges_memory_grow_per_page() {
+        let input = r#"
+            (module
+                (memory 1 10)
+
+                (func (export "grow") (param i32) (result i32)
+                    get_local 0
+                    memory.grow
+                ))
+            "#;
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let module = compile_program(&wasm).unwrap();
+        let mut instance = module.instantiate(&imports! {}).unwrap();
+        set_gas_left(&mut instance, 1_000_000);
+
+        let func: Func<i32, i32> = instance.func("grow").unwrap();
+
+        // returns the previous pages count
+        assert_eq!(1, func.call(3).unwrap());
+
+        let grow_cost = op_gas_cost(&Operator::MemoryGrow { reserved: 0 });
+        let expected = 1_000_000 - (1 + grow_cost + 3 * MEMORY_GROW_PAGE_GAS);
+        assert_eq!(expected, get_gas_left(&instance));
+    }
+
+    #[test]
+    fn metering_traps_on_large_memory_grow() {
+        let input = r#"
+            (module
+                (memory 1)
+
+                (func (export "grow") (result i32)
+                    i32.const 65535
+                    memory.grow
+                ))
+            "#;
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let module = compile_program(&wasm).unwrap();
+        let mut instance = module.instantiate(&imports! {}).unwrap();
+        set_gas_left(&mut instance, 1_000_000);
+
+        let func: Func<(), i32> = instance.func("grow").unwrap();
+        let res = func.call();
+
+        match res {
+            Err(RuntimeError::Error { data }) => {
+                assert!(data.downcast_ref::<OutOfGasError>().is_some());
+            }
+            _ => unreachable!(),
+        }
+
+        // the memory hasn't been grown
+        assert_eq!(1, instance.context().memory(0).size().0);
+    }
+}
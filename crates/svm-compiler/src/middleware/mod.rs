@@ -1,4 +1,7 @@
 mod error;
+mod metering;
 mod validation;
 
+pub use metering::MeteringMiddleware;
+pub(crate) use validation::parse_wasm_opcode;
 pub use validation::ValidationMiddleware;
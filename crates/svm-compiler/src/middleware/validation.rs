@@ -9,8 +9,8 @@ use super::error::ParseError;
 /// The `ValidationMiddleware` has two main objectives:
 /// * validation - make sure the wasm is valid and doesn't contain and opcodes not supported by `svm` (for example: floats)
 /// * preprocessing - we want to know whether the input contains loops or not.
-///   In case there no loop we can later compute ahead-of-time the gas for each function,
-///   otherwise we'll have a dynamic get metering used
+///   In case there no loop we can later compute ahead-of-time the gas for each function
+///   (see `gas::estimate_program_gas`), otherwise we'll have a dynamic gas metering used (see `MeteringMiddleware`)
 pub struct ValidationMiddleware;
 
 impl ValidationMiddleware {
@@ -40,7 +40,7 @@ impl FunctionMiddleware for ValidationMiddleware {
 }
 
 /// we explicitly whitelist the supported opcodes
-fn parse_wasm_opcode(opcode: &Operator) -> Result<(), ParseError> {
+pub(crate) fn parse_wasm_opcode(opcode: &Operator) -> Result<(), ParseError> {
     match opcode {
         Operator::Unreachable
         | Operator::Nop
@@ -158,6 +158,7 @@ fn parse_wasm_opcode(opcode: &Operator) -> Result<(), ParseError> {
 #[cfg(test)]
 mod tests {
     use crate::compile_program;
+    use crate::gas::set_gas_left;
     use wasmer_runtime::error::CompileError;
     use wasmer_runtime::{imports, Func};
 
@@ -173,7 +174,8 @@ mod tests {
             "#;
         let wasm = wabt::wat2wasm(input).unwrap();
         let module = compile_program(&wasm).unwrap();
-        let instance = module.instantiate(&imports! {}).unwrap();
+        let mut instance = module.instantiate(&imports! {}).unwrap();
+        set_gas_left(&mut instance, 1000);
 
         let func: Func<(i32, i32), i32> = instance.func("sum").unwrap();
         let res = func.call(10, 20);
@@ -0,0 +1,311 @@
+use wasmer_runtime_core::wasmparser::{
+    BinaryReaderError, ExternalKind, ImportSectionEntryType, ModuleReader, SectionCode, Type,
+};
+
+use crate::middleware::parse_wasm_opcode;
+
+/// The default maximum number of pages (64KiB each) a program memory may start with
+pub const MAX_MEMORY_PAGES: u32 = 256;
+
+/// The reason a wasm program has been rejected by `validate_program`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The program isn't a valid wasm module
+    InvalidWasm,
+
+    /// The program declares a float typed value (param, result, local or global)
+    FloatsNotAllowed,
+
+    /// The body of function `func_idx` uses an opcode not supported by `svm`
+    UnsupportedOpcode(u32),
+
+    /// The program imports something other than a known function
+    UnsupportedImport {
+        /// The import module name
+        module: String,
+
+        /// The import field name
+        field: String,
+    },
+
+    /// The program exports no functions (so it can't be executed)
+    NoExportedFunctions,
+
+    /// The program declares more than a single memory
+    MultipleMemories,
+
+    /// The program memory initial pages count exceeds the allowed maximum
+    MemoryLimitExceeded(u32),
+
+    /// The program memory declares no maximum pages count (`None`), or its maximum exceeds the allowed maximum
+    MemoryMaximumExceeded(Option<u32>),
+}
+
+impl From<BinaryReaderError> for ValidationError {
+    fn from(_e: BinaryReaderError) -> Self {
+        ValidationError::InvalidWasm
+    }
+}
+
+/// Validates the `wasm` program ahead of its deployment. The opcodes are checked using the same rules
+/// as the `ValidationMiddleware` (so a validated program won't fail compilation because of them).
+///
+/// * `is_known_import` - given an import `(module, field)` returns whether the runtime provides such a function
+/// * `max_memory_pages` - the maximum pages of the program memory (both initially and after growing it).
+///   The program memory must declare its maximum pages count.
+pub fn validate_program<F>(
+    wasm: &[u8],
+    is_known_import: F,
+    max_memory_pages: u32,
+) -> Result<(), ValidationError>
+where
+    F: Fn(&str, &str) -> bool,
+{
+    let mut reader = ModuleReader::new(wasm)?;
+
+    let mut imported_funcs = 0;
+    let mut memories = 0;
+    let mut has_exported_funcs = false;
+
+    while !reader.eof() {
+        let section = reader.read()?;
+
+        match section.code {
+            SectionCode::Type => {
+                for func_type in section.get_type_section_reader()? {
+                    let func_type = func_type?;
+
+                    let types = func_type.params.iter().chain(func_type.returns.iter());
+                    ensure_no_floats(types)?;
+                }
+            }
+            SectionCode::Import => {
+                for import in section.get_import_section_reader()? {
+                    let import = import?;
+
+                    let is_known = match import.ty {
+                        ImportSectionEntryType::Function(..) => {
+                            is_known_import(import.module, import.field)
+                        }
+                        _ => false,
+                    };
+
+                    if !is_known {
+                        return Err(ValidationError::UnsupportedImport {
+                            module: import.module.to_string(),
+                            field: import.field.to_string(),
+                        });
+                    }
+
+                    imported_funcs += 1;
+                }
+            }
+            SectionCode::Memory => {
+                for memory in section.get_memory_section_reader()? {
+                    let memory = memory?;
+
+                    memories += 1;
+
+                    if memories > 1 {
+                        return Err(ValidationError::MultipleMemories);
+                    }
+
+                    if memory.limits.initial > max_memory_pages {
+                        return Err(ValidationError::MemoryLimitExceeded(memory.limits.initial));
+                    }
+
+                    match memory.limits.maximum {
+                        Some(maximum) if maximum <= max_memory_pages => (),
+                        maximum => return Err(ValidationError::MemoryMaximumExceeded(maximum)),
+                    }
+                }
+            }
+            SectionCode::Global => {
+                for global in section.get_global_section_reader()? {
+                    let global = global?;
+
+                    ensure_no_floats(std::iter::once(&global.ty.content_type))?;
+                }
+            }
+            SectionCode::Export => {
+                for export in section.get_export_section_reader()? {
+                    if let ExternalKind::Function = export?.kind {
+                        has_exported_funcs = true;
+                    }
+                }
+            }
+            SectionCode::Code => {
+                for (i, body) in section.get_code_section_reader()?.into_iter().enumerate() {
+                    let body = body?;
+                    let func_idx = imported_funcs + i as u32;
+
+                    for local in body.get_locals_reader()? {
+                        let (_count, ty) = local?;
+
+                        ensure_no_floats(std::iter::once(&ty))?;
+                    }
+
+                    for op in body.get_operators_reader()? {
+                        if parse_wasm_opcode(&op?).is_err() {
+                            return Err(ValidationError::UnsupportedOpcode(func_idx));
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    if !has_exported_funcs {
+        return Err(ValidationError::NoExportedFunctions);
+    }
+
+    Ok(())
+}
+
+fn ensure_no_floats<'a, I>(types: I) -> Result<(), ValidationError>
+where
+    I: Iterator<Item = &'a Type>,
+{
+    for ty in types {
+        match ty {
+            Type::F32 | Type::F64 => return Err(ValidationError::FloatsNotAllowed),
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! validate {
+        ($input: expr) => {{
+            let wasm = wabt::wat2wasm($input).unwrap();
+
+            validate_program(
+                &wasm,
+                |module, field| module == "svm" && field == "known",
+                2,
+            )
+        }};
+    }
+
+    #[test]
+    fn validate_valid_program() {
+        let input = r#"
+            (module
+                (func $known (import "svm" "known") (param i32))
+                (memory 1 2)
+
+                (func (export "run") (param i32)
+                    get_local 0
+                    call $known
+                ))
+            "#;
+
+        assert_eq!(Ok(()), validate!(input));
+    }
+
+    #[test]
+    fn validate_float_params() {
+        let input = r#"
+            (module
+                (func (export "run") (param f32)
+                ))
+            "#;
+
+        assert_eq!(Err(ValidationError::FloatsNotAllowed), validate!(input));
+    }
+
+    #[test]
+    fn validate_float_opcodes() {
+        let input = r#"
+            (module
+                (func (export "run") (param i32) (result i32)
+                    get_local 0
+                    f32.convert_u/i32
+                    i32.trunc_u/f32
+                ))
+            "#;
+
+        assert_eq!(Err(ValidationError::UnsupportedOpcode(0)), validate!(input));
+    }
+
+    #[test]
+    fn validate_unknown_import() {
+        let input = r#"
+            (module
+                (func (import "env" "known"))
+                (func (export "run")))
+            "#;
+
+        let expected = ValidationError::UnsupportedImport {
+            module: "env".to_string(),
+            field: "known".to_string(),
+        };
+
+        assert_eq!(Err(expected), validate!(input));
+    }
+
+    #[test]
+    fn validate_no_exported_functions() {
+        let input = r#"
+            (module
+                (func $run))
+            "#;
+
+        assert_eq!(Err(ValidationError::NoExportedFunctions), validate!(input));
+    }
+
+    #[test]
+    fn validate_memory_limit() {
+        let input = r#"
+            (module
+                (memory 3)
+                (func (export "run")))
+            "#;
+
+        assert_eq!(
+            Err(ValidationError::MemoryLimitExceeded(3)),
+            validate!(input)
+        );
+    }
+
+    #[test]
+    fn validate_memory_without_maximum() {
+        let input = r#"
+            (module
+                (memory 1)
+                (func (export "run")))
+            "#;
+
+        assert_eq!(
+            Err(ValidationError::MemoryMaximumExceeded(None)),
+            validate!(input)
+        );
+    }
+
+    #[test]
+    fn validate_memory_maximum_limit() {
+        let input = r#"
+            (module
+                (memory 1 65536)
+                (func (export "run")))
+            "#;
+
+        assert_eq!(
+            Err(ValidationError::MemoryMaximumExceeded(Some(65536))),
+            validate!(input)
+        );
+    }
+
+    #[test]
+    fn validate_invalid_wasm() {
+        let res = validate_program(&[0xAA, 0xBB, 0xCC, 0xDD], |_, _| true, 2);
+
+        assert_eq!(Err(ValidationError::InvalidWasm), res);
+    }
+}
@@ -1,5 +1,7 @@
 mod code_hasher;
 mod contract_address_compute;
+mod salted_contract_address_compute;
 
 pub use code_hasher::DefaultCodeHasher;
 pub use contract_address_compute::DefaultContractAddressCompute;
+pub use salted_contract_address_compute::SaltedContractAddressCompute;
@@ -0,0 +1,36 @@
+use crate::traits::ContractAddressCompute;
+use crate::wasm::Contract;
+use svm_common::{Address, DefaultKeyHasher, KeyHasher};
+
+use byteorder::{BigEndian, WriteBytesExt};
+
+/// `ContractAddressCompute` implementation incorporating the author nonce and the (optional) contract salt.
+///
+/// Computing the contract's account address as follows:
+/// Taking `Address::len()` bytes of `HASH(contract.author || contract.nonce || salt || contract.wasm)`
+///
+/// Where `salt` is `0` (a single byte) when the contract has no salt, and `1 || contract.salt` otherwise.
+/// So the same author may deploy the same code more than once (under different addresses).
+pub struct SaltedContractAddressCompute;
+
+impl ContractAddressCompute for SaltedContractAddressCompute {
+    fn compute(contract: &Contract) -> Address {
+        let mut buf = Vec::with_capacity(Address::len() + 8 + 33 + contract.wasm.len());
+        buf.extend_from_slice(contract.author.as_slice());
+        buf.write_u64::<BigEndian>(contract.nonce).unwrap();
+
+        match contract.salt {
+            None => buf.write_u8(0).unwrap(),
+            Some(ref salt) => {
+                buf.write_u8(1).unwrap();
+                buf.extend_from_slice(salt);
+            }
+        }
+
+        buf.extend_from_slice(contract.wasm.as_slice());
+
+        let hash = DefaultKeyHasher::hash(&buf);
+
+        Address::from(&hash[0..Address::len()])
+    }
+}
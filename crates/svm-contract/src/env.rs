@@ -1,13 +1,19 @@
+use crate::load_error::ContractLoadError;
+use crate::store_error::ContractStoreError;
 use crate::traits::{
     ContractAddressCompute, ContractCodeHasher, ContractDeserializer, ContractSerializer,
     ContractStore,
 };
 use crate::transaction::Transaction;
-use crate::types::CodeHash;
+use crate::types::{CodeHash, Dependency};
+use crate::upgrade::Upgrade;
 use crate::wasm::Contract;
-use crate::wire::{deploy::ContractBuildError, exec::TransactionBuildError};
+use crate::wire::{
+    deploy::ContractBuildError, exec::TransactionBuildError, upgrade::ContractUpgradeError,
+};
 
 use svm_common::Address;
+use svm_kv::KVError;
 
 pub trait ContractEnvTypes {
     type Serializer: ContractSerializer;
@@ -61,12 +67,105 @@ pub trait ContractEnv {
         Ok(tx)
     }
 
-    /// Stores contract by its `CodeHash`
-    #[inline(always)]
-    fn store_contract(&mut self, contract: &Contract, addr: &Address) {
+    /// Parses a raw contract upgrade transaction
+    fn build_upgrade(bytes: &[u8]) -> Result<Upgrade, ContractUpgradeError> {
+        let upgrade = crate::wire::upgrade::parse_upgrade(bytes)?;
+
+        Ok(upgrade)
+    }
+
+    /// Validates an upgrade against the currently deployed contract:
+    /// * the upgraded contract must exist
+    /// * the upgrade sender must be one of the contract admins
+    /// * the new code must differ from the current code
+    ///
+    /// Returns the upgraded contract. It's the current contract with the new code
+    /// (the name, author, admins, dependencies and pages count are kept).
+    fn validate_upgrade(&self, upgrade: &Upgrade) -> Result<Contract, ContractUpgradeError> {
+        let store = self.get_store();
+
+        let contract = store
+            .load(&upgrade.contract)
+            .map_err(ContractUpgradeError::LoadFailed)?
+            .ok_or_else(|| ContractUpgradeError::ContractNotFound(upgrade.contract.clone()))?;
+
+        if !contract.admins.contains(&upgrade.sender) {
+            return Err(ContractUpgradeError::NotAdmin(upgrade.sender.clone()));
+        }
+
+        if contract.wasm == upgrade.wasm {
+            return Err(ContractUpgradeError::CodeUnchanged);
+        }
+
+        Ok(Contract {
+            wasm: upgrade.wasm.clone(),
+            ..contract
+        })
+    }
+
+    /// Loads the code of the contract (a.k.a library) a dependency refers to.
+    /// Returns `None` when no such contract has been deployed.
+    fn load_dependency(&self, dep: &Dependency) -> Result<Option<Vec<u8>>, ContractLoadError> {
+        let store = self.get_store();
+
+        let hash = match dep {
+            Dependency::Address(addr) => match store.load_code_hash(addr)? {
+                None => return Ok(None),
+                Some(hash) => hash,
+            },
+            Dependency::CodeHash(hash) => *hash,
+        };
+
+        store.load_code(&hash)
+    }
+
+    /// Validates that all the contract dependencies have already been deployed
+    fn validate_deps(&self, contract: &Contract) -> Result<(), ContractBuildError> {
+        for dep in contract.deps.iter() {
+            let lib = self
+                .load_dependency(dep)
+                .map_err(ContractBuildError::LoadFailed)?;
+
+            if lib.is_none() {
+                return Err(ContractBuildError::DependencyNotFound(dep.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stores contract by its `CodeHash`.
+    /// Fails when a contract has already been stored under `addr` (the stored contract is kept).
+    fn store_contract(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+    ) -> Result<(), ContractStoreError> {
         let hash = Self::compute_code_hash(contract);
         let store = self.get_store_mut();
 
-        store.store(contract, addr, hash);
+        let code_hash = store
+            .load_code_hash(addr)
+            .map_err(ContractStoreError::LoadFailed)?;
+
+        if code_hash.is_some() {
+            return Err(ContractStoreError::AddressTaken(addr.clone()));
+        }
+
+        store
+            .store(contract, addr, hash)
+            .map_err(ContractStoreError::StoreFailed)
+    }
+
+    /// Stores the upgraded contract by its `CodeHash`, and points `addr` at it
+    /// (the previous code-hash is kept in the code history of `addr`).
+    /// Returns the new `CodeHash`.
+    fn store_upgrade(&mut self, contract: &Contract, addr: &Address) -> Result<CodeHash, KVError> {
+        let hash = Self::compute_code_hash(contract);
+        let store = self.get_store_mut();
+
+        store.upgrade(contract, addr, hash)?;
+
+        Ok(hash)
     }
 }
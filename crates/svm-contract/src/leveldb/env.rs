@@ -0,0 +1,42 @@
+use crate::default::{DefaultCodeHasher, SaltedContractAddressCompute};
+use crate::env::{ContractEnv, ContractEnvTypes};
+use crate::leveldb::LDBContractStore;
+use crate::wasm::{WasmContractDeserializer, WasmContractSerializer};
+
+pub struct LDBEnvTypes {}
+
+impl ContractEnvTypes for LDBEnvTypes {
+    type Serializer = WasmContractSerializer;
+
+    type Deserializer = WasmContractDeserializer;
+
+    type Store = LDBContractStore<Self::Serializer, Self::Deserializer>;
+
+    type AddressCompute = SaltedContractAddressCompute;
+
+    type CodeHasher = DefaultCodeHasher;
+}
+
+/// Contract environment backed by `leveldb` for persistence.
+pub struct LDBEnv {
+    store: <LDBEnvTypes as ContractEnvTypes>::Store,
+}
+
+impl LDBEnv {
+    /// Creates a new `LDBEnv`. Injects externally the `ContractStore`
+    pub fn new(store: <LDBEnvTypes as ContractEnvTypes>::Store) -> Self {
+        Self { store }
+    }
+}
+
+impl ContractEnv for LDBEnv {
+    type Types = LDBEnvTypes;
+
+    fn get_store(&self) -> &<Self::Types as ContractEnvTypes>::Store {
+        &self.store
+    }
+
+    fn get_store_mut(&mut self) -> &mut <Self::Types as ContractEnvTypes>::Store {
+        &mut self.store
+    }
+}
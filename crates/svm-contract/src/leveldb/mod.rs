@@ -0,0 +1,5 @@
+mod contract_store;
+mod env;
+
+pub use contract_store::LDBContractStore;
+pub use env::LDBEnv;
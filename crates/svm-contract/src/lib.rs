@@ -11,21 +11,33 @@ pub mod memory;
 pub mod traits;
 pub mod transaction;
 pub mod types;
+pub mod upgrade;
 pub mod wasm;
 
 /// `rocksdb` backed implementation for `ContractStore` and `ContractEnv`
 #[cfg(feature = "default-rocksdb")]
 pub mod rocksdb;
 
+/// `leveldb` backed implementation for `ContractStore` and `ContractEnv`
+#[cfg(feature = "default-leveldb")]
+pub mod leveldb;
+
+mod load_error;
+mod store_error;
 mod wire;
 
 pub mod error {
+    pub use crate::load_error::ContractLoadError;
+    pub use crate::store_error::ContractStoreError;
+    pub use crate::wasm::ContractDeserializeError;
     pub use crate::wire::deploy::ContractBuildError;
     pub use crate::wire::exec::TransactionBuildError;
+    pub use crate::wire::upgrade::ContractUpgradeError;
 }
 
-/// Building in-memory representations for a new contract / smart-contract transaction
+/// Building in-memory representations for a new contract / smart-contract transaction / contract upgrade
 pub mod build {
     pub use crate::wire::deploy::WireContractBuilder;
     pub use crate::wire::exec::WireTxBuilder;
+    pub use crate::wire::upgrade::WireUpgradeBuilder;
 }
@@ -0,0 +1,52 @@
+use crate::types::CodeHash;
+use crate::wasm::ContractDeserializeError;
+
+use svm_kv::KVError;
+
+/// Loading a stored contract error (see `ContractStore::load`)
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum ContractLoadError {
+    ReadFailed(KVError),
+    CodeNotFound(CodeHash),
+    Corrupted(CodeHash, ContractDeserializeError),
+}
+
+impl From<KVError> for ContractLoadError {
+    fn from(e: KVError) -> Self {
+        ContractLoadError::ReadFailed(e)
+    }
+}
+
+impl std::error::Error for ContractLoadError {
+    fn description(&self) -> &'static str {
+        match self {
+            ContractLoadError::ReadFailed(_) => "Read failed",
+            ContractLoadError::CodeNotFound(_) => "Code not found",
+            ContractLoadError::Corrupted(..) => "Stored contract is corrupted",
+        }
+    }
+}
+
+impl std::fmt::Display for ContractLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            ContractLoadError::ReadFailed(e) => format!("Read failed: {:?}", e),
+            ContractLoadError::CodeNotFound(hash) => {
+                format!("Code associated with `CodeHash = {:?}` not found", hash)
+            }
+            ContractLoadError::Corrupted(hash, e) => format!(
+                "Code associated with `CodeHash = {:?}` is corrupted: {:?}",
+                hash, e
+            ),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for ContractLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::load_error::ContractLoadError;
 use crate::traits::{ContractDeserializer, ContractSerializer, ContractStore};
 use crate::types::CodeHash;
 use crate::wasm::Contract;
@@ -6,11 +7,13 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use svm_common::Address;
+use svm_kv::KVError;
 
 /// An in-memory implementation of `ContractStore`
 pub struct MemContractStore<S, D> {
-    contract_bytes: HashMap<CodeHash, Vec<u8>>,
-    addr_codehash: HashMap<Address, CodeHash>,
+    code: HashMap<CodeHash, Vec<u8>>,
+    addr_contract: HashMap<Address, (CodeHash, Vec<u8>)>,
+    code_history: HashMap<Address, Vec<CodeHash>>,
     marker: PhantomData<(S, D)>,
 }
 
@@ -23,8 +26,9 @@ where
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
-            contract_bytes: HashMap::new(),
-            addr_codehash: HashMap::new(),
+            code: HashMap::new(),
+            addr_contract: HashMap::new(),
+            code_history: HashMap::new(),
             marker: PhantomData,
         }
     }
@@ -35,26 +39,85 @@ where
     S: ContractSerializer,
     D: ContractDeserializer,
 {
-    fn store(&mut self, contract: &Contract, addr: &Address, hash: CodeHash) {
-        let serialized: Vec<u8> = S::serialize(contract);
-
-        self.contract_bytes.insert(hash, serialized);
-        self.addr_codehash.insert(addr.clone(), hash);
-    }
-
-    fn load(&self, addr: &Address) -> Option<Contract> {
-        match self.addr_codehash.get(addr) {
-            None => None,
-            Some(hash) => match self.contract_bytes.get(&hash) {
-                None => panic!(format!(
-                    "Code associated with `CodeHash = {:?}` not found",
-                    hash
-                )),
-                Some(bytes) => {
-                    let contract = D::deserialize(bytes.to_vec());
-                    Some(contract)
-                }
-            },
+    fn store(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+        hash: CodeHash,
+    ) -> Result<(), KVError> {
+        self.write_contract(contract, addr, hash);
+
+        Ok(())
+    }
+
+    fn load(&self, addr: &Address) -> Result<Option<Contract>, ContractLoadError> {
+        let (hash, meta) = match self.addr_contract.get(addr) {
+            None => return Ok(None),
+            Some((hash, meta)) => (*hash, meta),
+        };
+
+        let code = self
+            .code
+            .get(&hash)
+            .ok_or_else(|| ContractLoadError::CodeNotFound(hash))?;
+
+        let mut contract =
+            D::deserialize(meta.to_vec()).map_err(|e| ContractLoadError::Corrupted(hash, e))?;
+        contract.wasm = code.to_vec();
+
+        Ok(Some(contract))
+    }
+
+    fn load_code(&self, hash: &CodeHash) -> Result<Option<Vec<u8>>, ContractLoadError> {
+        Ok(self.code.get(hash).cloned())
+    }
+
+    fn upgrade(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+        hash: CodeHash,
+    ) -> Result<(), KVError> {
+        if let Some(prev) = self.write_contract(contract, addr, hash) {
+            self.code_history
+                .entry(addr.clone())
+                .or_default()
+                .push(prev);
         }
+
+        Ok(())
+    }
+
+    fn load_code_hash(&self, addr: &Address) -> Result<Option<CodeHash>, KVError> {
+        Ok(self.addr_contract.get(addr).map(|(hash, _meta)| *hash))
+    }
+
+    fn load_code_history(&self, addr: &Address) -> Result<Vec<CodeHash>, KVError> {
+        Ok(self.code_history.get(addr).cloned().unwrap_or_default())
+    }
+}
+
+impl<S, D> MemContractStore<S, D>
+where
+    S: ContractSerializer,
+    D: ContractDeserializer,
+{
+    /// Keeps the code by its `hash`, and the code-hash along with the contract metadata by its `addr`.
+    /// Returns the code-hash previously associated with `addr` (if any).
+    fn write_contract(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+        hash: CodeHash,
+    ) -> Option<CodeHash> {
+        let meta = S::serialize(&contract.metadata());
+
+        self.code
+            .entry(hash)
+            .or_insert_with(|| contract.wasm.clone());
+
+        self.addr_contract
+            .insert(addr.clone(), (hash, meta))
+            .map(|(prev, _meta)| prev)
     }
 }
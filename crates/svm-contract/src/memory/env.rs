@@ -1,18 +1,18 @@
-use crate::default::{DefaultCodeHasher, DefaultContractAddressCompute};
+use crate::default::{DefaultCodeHasher, SaltedContractAddressCompute};
 use crate::env::{ContractEnv, ContractEnvTypes};
 use crate::memory::MemContractStore;
-use crate::wasm::{WasmContractJsonDeserializer, WasmContractJsonSerializer};
+use crate::wasm::{WasmContractDeserializer, WasmContractSerializer};
 
 pub struct MemoryEnvTypes {}
 
 impl ContractEnvTypes for MemoryEnvTypes {
-    type Serializer = WasmContractJsonSerializer;
+    type Serializer = WasmContractSerializer;
 
-    type Deserializer = WasmContractJsonDeserializer;
+    type Deserializer = WasmContractDeserializer;
 
     type Store = MemContractStore<Self::Serializer, Self::Deserializer>;
 
-    type AddressCompute = DefaultContractAddressCompute;
+    type AddressCompute = SaltedContractAddressCompute;
 
     type CodeHasher = DefaultCodeHasher;
 }
@@ -1,3 +1,4 @@
+use crate::load_error::ContractLoadError;
 use crate::traits::{ContractDeserializer, ContractSerializer, ContractStore};
 use crate::types::CodeHash;
 use crate::wasm::Contract;
@@ -8,6 +9,7 @@ use std::path::Path;
 use svm_common::Address;
 use svm_kv::rocksdb::RocksStore;
 use svm_kv::traits::KVStore;
+use svm_kv::KVError;
 
 use log::info;
 
@@ -21,9 +23,21 @@ where
     S: ContractSerializer,
     D: ContractDeserializer,
 {
-    pub fn new(path: &Path) -> Self {
+    /// A new store over the database under `path`.
+    /// Returns an error when the database can't be opened.
+    pub fn new(path: &Path) -> Result<Self, KVError> {
+        let store = Self {
+            db: RocksStore::new(path)?,
+            marker: PhantomData,
+        };
+
+        Ok(store)
+    }
+
+    /// A new store over an opened `RocksStore` (e.g a column family of a shared database)
+    pub fn from_store(db: RocksStore) -> Self {
         Self {
-            db: RocksStore::new(path),
+            db,
             marker: PhantomData,
         }
     }
@@ -34,35 +48,164 @@ where
     S: ContractSerializer,
     D: ContractDeserializer,
 {
-    fn store(&mut self, contract: &Contract, addr: &Address, hash: CodeHash) {
+    fn store(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+        hash: CodeHash,
+    ) -> Result<(), KVError> {
         info!("storing contract: \n{:?}", contract);
         info!("     contract account address: {:?}", addr);
         info!("     contract code-hash: {:?}", hash);
 
-        let serialized: Vec<u8> = S::serialize(contract);
-
-        let addr_hash = (addr.as_slice(), &hash.0[..]);
-        let hash_wasm = (&hash.0[..], &serialized[..]);
-        self.db.store(&[addr_hash, hash_wasm]);
+        self.write_contract(contract, addr, hash, None)
     }
 
-    fn load(&self, addr: &Address) -> Option<Contract> {
+    fn load(&self, addr: &Address) -> Result<Option<Contract>, ContractLoadError> {
         info!("loading contract account {:?}", addr);
 
-        match self.db.get(addr.as_slice()) {
-            None => None,
-            Some(hash) => match self.db.get(&hash) {
-                None => panic!(format!(
-                    "code associated with `CodeHash = {:?}` not found",
-                    hash
-                )),
-                Some(bytes) => {
-                    let contract = D::deserialize(bytes.to_vec());
-                    info!("loaded contract: \n{:?}", contract);
-
-                    Some(contract)
-                }
-            },
+        let hash = match self.db.get(addr.as_slice())? {
+            None => return Ok(None),
+            Some(hash) => to_code_hash(&hash),
+        };
+
+        let code = self
+            .db
+            .get(&hash.0)?
+            .ok_or_else(|| ContractLoadError::CodeNotFound(hash))?;
+
+        let contract = match self.db.get(&meta_key(addr))? {
+            Some(meta) => {
+                let mut contract = deserialize::<D>(hash, &meta)?;
+                contract.wasm = code_from_bytes::<D>(hash, code)?;
+
+                contract
+            }
+            // a contract stored by a previous release (the whole contract is kept under its code-hash)
+            None => deserialize::<D>(hash, &code)?,
+        };
+
+        info!("loaded contract: \n{:?}", contract);
+
+        Ok(Some(contract))
+    }
+
+    fn load_code(&self, hash: &CodeHash) -> Result<Option<Vec<u8>>, ContractLoadError> {
+        info!("loading contract code by code-hash {:?}", hash);
+
+        match self.db.get(&hash.0)? {
+            None => Ok(None),
+            Some(bytes) => code_from_bytes::<D>(*hash, bytes).map(Some),
+        }
+    }
+
+    fn upgrade(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+        hash: CodeHash,
+    ) -> Result<(), KVError> {
+        info!("upgrading contract account {:?}", addr);
+        info!("     new contract code-hash: {:?}", hash);
+
+        let mut history = self.db.get(&history_key(addr))?.unwrap_or_default();
+        if let Some(prev) = self.db.get(addr.as_slice())? {
+            history.extend_from_slice(&prev);
+        }
+
+        self.write_contract(contract, addr, hash, Some(&history))
+    }
+
+    fn load_code_hash(&self, addr: &Address) -> Result<Option<CodeHash>, KVError> {
+        let hash = self.db.get(addr.as_slice())?;
+
+        Ok(hash.map(|hash| to_code_hash(&hash)))
+    }
+
+    fn load_code_history(&self, addr: &Address) -> Result<Vec<CodeHash>, KVError> {
+        let history = match self.db.get(&history_key(addr))? {
+            None => Vec::new(),
+            Some(history) => history.chunks_exact(32).map(to_code_hash).collect(),
+        };
+
+        Ok(history)
+    }
+}
+
+impl<S, D> RocksContractStore<S, D>
+where
+    S: ContractSerializer,
+    D: ContractDeserializer,
+{
+    /// Stores the `addr -> hash`, `hash -> wasm` and `"meta" || addr -> contract metadata` associations
+    /// (and the code `history` of `addr` if given).
+    ///
+    /// The code kept under `hash` is never overwritten, since it's shared by all the contracts having that code.
+    fn write_contract(
+        &mut self,
+        contract: &Contract,
+        addr: &Address,
+        hash: CodeHash,
+        history: Option<&[u8]>,
+    ) -> Result<(), KVError> {
+        let meta = S::serialize(&contract.metadata());
+        let meta_key = meta_key(addr);
+        let history_key = history_key(addr);
+
+        let mut changes = vec![(addr.as_slice(), &hash.0[..]), (&meta_key[..], &meta[..])];
+
+        if self.db.get(&hash.0)?.is_none() {
+            changes.push((&hash.0[..], &contract.wasm[..]));
+        }
+
+        if let Some(history) = history {
+            changes.push((&history_key[..], history));
         }
+
+        self.db.store(&changes)
+    }
+}
+
+fn deserialize<D: ContractDeserializer>(
+    hash: CodeHash,
+    bytes: &[u8],
+) -> Result<Contract, ContractLoadError> {
+    D::deserialize(bytes.to_vec()).map_err(|e| ContractLoadError::Corrupted(hash, e))
+}
+
+/// The code kept under a code-hash is the raw wasm (starting with the `\0asm` magic number).
+/// Previous releases kept the whole serialized contract instead (never starting with a zero byte).
+fn code_from_bytes<D: ContractDeserializer>(
+    hash: CodeHash,
+    bytes: Vec<u8>,
+) -> Result<Vec<u8>, ContractLoadError> {
+    match bytes.first() {
+        Some(0) => Ok(bytes),
+        _ => deserialize::<D>(hash, &bytes).map(|contract| contract.wasm),
     }
 }
+
+/// The key of the metadata of contract account `addr`: `"meta" || addr`.
+/// Its value is the serialized contract having an empty code (the code is kept under its code-hash).
+fn meta_key(addr: &Address) -> Vec<u8> {
+    let mut key = b"meta".to_vec();
+    key.extend_from_slice(addr.as_slice());
+
+    key
+}
+
+/// The key of the code history of contract account `addr`: `"history" || addr`.
+/// Its value is the concatenation of the prior code hashes (32 bytes each).
+fn history_key(addr: &Address) -> Vec<u8> {
+    let mut key = b"history".to_vec();
+    key.extend_from_slice(addr.as_slice());
+
+    key
+}
+
+fn to_code_hash(bytes: &[u8]) -> CodeHash {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(bytes);
+
+    CodeHash(hash)
+}
@@ -1,18 +1,18 @@
-use crate::default::{DefaultCodeHasher, DefaultContractAddressCompute};
+use crate::default::{DefaultCodeHasher, SaltedContractAddressCompute};
 use crate::env::{ContractEnv, ContractEnvTypes};
 use crate::rocksdb::RocksContractStore;
-use crate::wasm::{WasmContractJsonDeserializer, WasmContractJsonSerializer};
+use crate::wasm::{WasmContractDeserializer, WasmContractSerializer};
 
 pub struct RocksEnvTypes {}
 
 impl ContractEnvTypes for RocksEnvTypes {
-    type Serializer = WasmContractJsonSerializer;
+    type Serializer = WasmContractSerializer;
 
-    type Deserializer = WasmContractJsonDeserializer;
+    type Deserializer = WasmContractDeserializer;
 
     type Store = RocksContractStore<Self::Serializer, Self::Deserializer>;
 
-    type AddressCompute = DefaultContractAddressCompute;
+    type AddressCompute = SaltedContractAddressCompute;
 
     type CodeHasher = DefaultCodeHasher;
 }
@@ -0,0 +1,40 @@
+use svm_common::Address;
+use svm_kv::KVError;
+
+/// Storing a newly deployed contract error (see `ContractEnv::store_contract`)
+#[allow(missing_docs)]
+pub enum ContractStoreError {
+    AddressTaken(Address),
+    StoreFailed(KVError),
+    LoadFailed(KVError),
+}
+
+impl std::error::Error for ContractStoreError {
+    fn description(&self) -> &'static str {
+        match self {
+            ContractStoreError::AddressTaken(_) => "Address already taken",
+            ContractStoreError::StoreFailed(_) => "Store failed",
+            ContractStoreError::LoadFailed(_) => "Load failed",
+        }
+    }
+}
+
+impl std::fmt::Display for ContractStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            ContractStoreError::AddressTaken(addr) => {
+                format!("Address `{:?}` is already taken", addr)
+            }
+            ContractStoreError::StoreFailed(e) => format!("Store failed: {:?}", e),
+            ContractStoreError::LoadFailed(e) => format!("Load failed: {:?}", e),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for ContractStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
@@ -1,7 +1,9 @@
+use crate::load_error::ContractLoadError;
 use crate::types::CodeHash;
-use crate::wasm::Contract;
+use crate::wasm::{Contract, ContractDeserializeError};
 
 use svm_common::Address;
+use svm_kv::KVError;
 
 /// Serializing a contract into its raw representation trait.
 pub trait ContractSerializer {
@@ -12,22 +14,58 @@ pub trait ContractSerializer {
 /// Deserializing raw contract into its in-memory representation trait.
 pub trait ContractDeserializer {
     #[allow(missing_docs)]
-    fn deserialize(bytes: Vec<u8>) -> Contract;
+    fn deserialize(bytes: Vec<u8>) -> Result<Contract, ContractDeserializeError>;
 }
 
 /// Stores serialized contracts (a.k.a raw contracts)
 /// and deserializes raw contract into `Contract` upon fetching.
+///
+/// The code of a contract is kept by its code-hash (contracts having the same code share it),
+/// while the rest of the contract (name, author, admins, dependencies and pages count) is kept by the contract address.
 pub trait ContractStore<S, D>
 where
     S: ContractSerializer,
     D: ContractDeserializer,
 {
-    /// Stores the `hash` -> `raw contract` association
-    fn store(&mut self, contract: &Contract, address: &Address, hash: CodeHash);
+    /// Stores the `hash` -> `code` and `address` -> `raw contract` associations.
+    /// Returns an error when the underlying database failed writing.
+    fn store(
+        &mut self,
+        contract: &Contract,
+        address: &Address,
+        hash: CodeHash,
+    ) -> Result<(), KVError>;
 
     /// Given a contract account address, fetches its raw contract dada
-    /// and deserializes it. Return `None` it contract doesn't exist
-    fn load(&self, address: &Address) -> Option<Contract>;
+    /// and deserializes it. Return `None` it contract doesn't exist.
+    /// Returns an error when the underlying database failed reading, or when the stored contract is corrupted.
+    fn load(&self, address: &Address) -> Result<Option<Contract>, ContractLoadError>;
+
+    /// Given a contract code hash, fetches the code (wasm) having that hash.
+    /// Return `None` if no contract with that code has been stored.
+    /// Returns an error when the underlying database failed reading, or when the stored code is corrupted.
+    fn load_code(&self, hash: &CodeHash) -> Result<Option<Vec<u8>>, ContractLoadError>;
+
+    /// Upgrades contract account `address` to the upgraded `contract` code (having code-hash `hash`).
+    /// Stores the `hash` -> `code` association, re-associates `address` with `hash`
+    /// and appends the previous code-hash of `address` to its code history.
+    /// Returns an error when the underlying database failed writing.
+    fn upgrade(
+        &mut self,
+        contract: &Contract,
+        address: &Address,
+        hash: CodeHash,
+    ) -> Result<(), KVError>;
+
+    /// Returns the code hash currently associated with contract account `address`.
+    /// Returns `None` if no contract has been stored under `address`.
+    /// Returns an error when the underlying database failed reading.
+    fn load_code_hash(&self, address: &Address) -> Result<Option<CodeHash>, KVError>;
+
+    /// Returns the prior code hashes of contract account `address` (the oldest first).
+    /// Empty if the contract has never been upgraded.
+    /// Returns an error when the underlying database failed reading.
+    fn load_code_history(&self, address: &Address) -> Result<Vec<CodeHash>, KVError>;
 }
 
 /// Computes a contract account address.
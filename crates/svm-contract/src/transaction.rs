@@ -1,5 +1,5 @@
 use crate::wasm::WasmArgValue;
-use svm_common::Address;
+use svm_common::{Address, Balance};
 
 /// An in-memory representation of a smart-contract transaction.
 #[derive(Clone, PartialEq)]
@@ -10,6 +10,15 @@ pub struct Transaction {
     /// Transaction sender account address
     pub sender: Address,
 
+    /// The maximum gas the transaction execution may consume
+    pub gas_limit: u64,
+
+    /// The price (in coins) the sender pays per unit of consumed gas
+    pub gas_price: u64,
+
+    /// The amount (in coins) the sender transfers to the contract along with the call
+    pub value: Balance,
+
     /// Contract function to execute
     pub func_name: String,
 
@@ -21,10 +30,15 @@ impl std::fmt::Debug for Transaction {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let contract = self.fmt_contract();
         let sender = self.fmt_sender();
+        let gas_limit = self.fmt_gas_limit();
+        let gas_price = self.fmt_gas_price();
+        let value = self.fmt_value();
         let func_name = self.fmt_func_name();
         let func_args = self.fmt_func_args();
 
-        let msg = [contract, sender, func_name, func_args];
+        let msg = [
+            contract, sender, gas_limit, gas_price, value, func_name, func_args,
+        ];
 
         write!(f, "{}", msg.join("\n"))
     }
@@ -43,6 +57,18 @@ impl Transaction {
         format!("{:?}: {:?}", field, addr)
     }
 
+    fn fmt_gas_limit(&self) -> String {
+        format!("GasLimit: {}", self.gas_limit)
+    }
+
+    fn fmt_gas_price(&self) -> String {
+        format!("GasPrice: {}", self.gas_price)
+    }
+
+    fn fmt_value(&self) -> String {
+        format!("Value: {}", self.value.0)
+    }
+
     fn fmt_func_name(&self) -> String {
         format!("FuncName: {:?}", self.func_name)
     }
@@ -1,7 +1,21 @@
 pub use crate::traits::ContractAddressCompute;
 use serde::{Deserialize, Serialize};
 
-/// Represents a contract code hash
-#[repr(transparent)]
-#[derive(Serialize, Deserialize, Hash, PartialEq, Eq, Copy, Clone, Debug)]
-pub struct CodeHash(pub [u8; 32]);
+pub use svm_common::CodeHash;
+
+use svm_common::Address;
+
+/// The maximum number of dependencies of a contract.
+/// The dependencies are instantiated on each execution of the contract (see `svm_runtime`).
+pub const MAX_DEPS: usize = 8;
+
+/// A contract dependency (a.k.a library). Specified either by a deployed contract address,
+/// or by the code hash of a deployed contract.
+#[derive(Serialize, Deserialize, PartialEq, Clone, Debug)]
+pub enum Dependency {
+    /// The dependency is the code of the contract deployed under that address
+    Address(Address),
+
+    /// The dependency is the deployed code having that code hash
+    CodeHash(CodeHash),
+}
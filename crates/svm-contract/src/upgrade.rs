@@ -0,0 +1,45 @@
+use svm_common::Address;
+
+/// An in-memory representation of a contract upgrade transaction.
+///
+/// An upgrade points an existing contract account at a new code (a new `CodeHash`),
+/// while keeping its address and its storage `State`.
+#[derive(Clone, PartialEq)]
+pub struct Upgrade {
+    /// The upgraded contract account address
+    pub contract: Address,
+
+    /// Transaction sender account address (must be one of the contract admins)
+    pub sender: Address,
+
+    /// The new contract code
+    pub wasm: Vec<u8>,
+}
+
+impl std::fmt::Debug for Upgrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let contract = self.fmt_contract();
+        let sender = self.fmt_sender();
+        let wasm = self.fmt_wasm();
+
+        let msg = [contract, sender, wasm];
+
+        write!(f, "{}", msg.join("\n"))
+    }
+}
+
+impl Upgrade {
+    fn fmt_contract(&self) -> String {
+        format!("Contract: {:?}", self.contract)
+    }
+
+    fn fmt_sender(&self) -> String {
+        format!("Sender: {:?}", self.sender)
+    }
+
+    fn fmt_wasm(&self) -> String {
+        let preview = self.wasm.len().min(4);
+
+        format!("Code: {:?}", &self.wasm[0..preview])
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::types::Dependency;
+use crate::wasm::WasmArgValue;
+
 use svm_common::Address;
 
 /// We first parse the on-the-wire contract transaction into a `Contract` instance.
@@ -10,25 +13,117 @@ pub struct Contract {
     pub wasm: Vec<u8>,
     pub name: String,
     pub author: Address,
+
+    #[serde(default)]
+    pub admins: Vec<Address>,
+
+    #[serde(default)]
+    pub deps: Vec<Dependency>,
+
+    /// The number of storage pages allocated at deploy time (the storage may grow when running)
+    #[serde(default)]
+    pub pages_count: u16,
+
+    /// The constructor to run at deploy time (if any). It isn't persisted along with the contract
+    #[serde(skip)]
+    pub ctor: Option<ContractCtor>,
+
+    /// The author nonce. Used only for computing the contract address (it isn't persisted)
+    #[serde(skip)]
+    pub nonce: u64,
+
+    /// An optional salt. Used only for computing the contract address (it isn't persisted)
+    #[serde(skip)]
+    pub salt: Option<[u8; 32]>,
+
+    /// The maximum gas the constructor execution may consume (it isn't persisted)
+    #[serde(skip)]
+    pub gas_limit: u64,
+
+    /// The price (in coins) the author pays per unit of gas consumed by the constructor (it isn't persisted)
+    #[serde(skip)]
+    pub gas_price: u64,
+}
+
+/// A contract constructor: an exported function the runtime calls when the contract is deployed.
+/// The storage written by the constructor is the contract initial state.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ContractCtor {
+    /// The exported function to call
+    pub func_name: String,
+
+    /// The function args (encoded as the args of an exec transaction)
+    pub func_args: Vec<WasmArgValue>,
 }
 
 impl std::fmt::Debug for Contract {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let author = self.preview_author(&self.author);
+        let admins = self.preview_admins(&self.admins);
+        let deps = self.preview_deps(&self.deps);
+        let pages = format!("#Pages: {}", self.pages_count);
+        let ctor = self.preview_ctor(&self.ctor);
+        let nonce = format!("Nonce: {}", self.nonce);
+        let salt = self.preview_salt(&self.salt);
+        let gas_limit = format!("GasLimit: {}", self.gas_limit);
+        let gas_price = format!("GasPrice: {}", self.gas_price);
         let wasm = self.preview_wasm(&self.wasm);
 
-        let msg = [author, wasm].join("\n");
+        let msg = [
+            author, admins, deps, pages, ctor, nonce, salt, gas_limit, gas_price, wasm,
+        ]
+        .join("\n");
 
         write!(f, "{}", msg)
     }
 }
 
 impl Contract {
+    /// The persisted fields of the contract except for its code.
+    /// The code is kept by its code-hash (shared by all the contracts having that code), see `ContractStore`.
+    pub(crate) fn metadata(&self) -> Contract {
+        Contract {
+            wasm: Vec::new(),
+            name: self.name.clone(),
+            author: self.author.clone(),
+            admins: self.admins.clone(),
+            deps: self.deps.clone(),
+            pages_count: self.pages_count,
+            ctor: None,
+            nonce: 0,
+            salt: None,
+            gas_limit: 0,
+            gas_price: 0,
+        }
+    }
+
     fn preview_author(&self, author: &Address) -> String {
         format!("Author: {:?}...", &author.as_slice()[0..8])
     }
 
+    fn preview_admins(&self, admins: &[Address]) -> String {
+        format!("#Admins: {}", admins.len())
+    }
+
+    fn preview_deps(&self, deps: &[Dependency]) -> String {
+        format!("#Deps: {}", deps.len())
+    }
+
+    fn preview_ctor(&self, ctor: &Option<ContractCtor>) -> String {
+        match ctor {
+            None => "Ctor: None".to_string(),
+            Some(ctor) => format!("Ctor: {:?}", ctor.func_name),
+        }
+    }
+
+    fn preview_salt(&self, salt: &Option<[u8; 32]>) -> String {
+        match salt {
+            None => "Salt: None".to_string(),
+            Some(salt) => format!("Salt: {:?}...", &salt[0..4]),
+        }
+    }
+
     fn preview_wasm(&self, wasm: &[u8]) -> String {
-        format!("Code: {:?}", &wasm[0..4])
+        format!("Code: {:?}", &wasm[0..wasm.len().min(4)])
     }
 }
@@ -3,6 +3,9 @@ mod contract;
 mod serialize;
 
 pub use arg::{WasmArgType, WasmArgTypeError, WasmArgValue, WasmIntType};
-pub use contract::Contract;
-pub use serialize::WasmContractJsonDeserializer;
-pub use serialize::WasmContractJsonSerializer;
+pub use contract::{Contract, ContractCtor};
+pub use serialize::{
+    migrate_json_contract, ContractDeserializeError, WasmContractDeserializer,
+    WasmContractJsonDeserializer, WasmContractJsonSerializer, WasmContractSerializer,
+    CONTRACT_SERIALIZE_VERSION,
+};
@@ -0,0 +1,249 @@
+use super::error::ContractDeserializeError;
+use super::json::WasmContractJsonDeserializer;
+
+use crate::traits::{ContractDeserializer, ContractSerializer};
+use crate::types::{CodeHash, Dependency};
+use crate::wasm::Contract;
+
+use svm_common::Address;
+
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::convert::TryFrom;
+use std::io::{Cursor, Read};
+
+/// The current version of the binary contract format (the first byte of a serialized contract)
+pub const CONTRACT_SERIALIZE_VERSION: u8 = 2;
+
+/// The first byte of a JSON serialized contract (see `WasmContractJsonSerializer`)
+const JSON_CONTRACT_MARKER: u8 = b'{';
+
+/// Serializes a contract using the compact binary format:
+///
+///  +-----------+-------------+--------------+----------------+
+///  |  version  |  name len   |  name        |  author        |
+///  |  (1 byte) |  (2 bytes)  |  (UTF-8)     |  (20 bytes)    |
+///  +-----------+-------------+--------------+----------------+
+///  |  #admins  |  admins     |  #deps       |  deps          |
+///  | (2 bytes) |  (20 bytes  |  (2 bytes)   |  (kind byte +  |
+///  |           |  each)      |              |  20/32 bytes)  |
+///  +-----------+-------------+--------------+----------------+
+///  |  #pages   |  code len   |  code (wasm)                  |
+///  | (2 bytes) |  (4 bytes)  |                               |
+///  +-----------+-------------+-------------------------------+
+///
+/// Dependency kinds are the same as in the deploy transaction (`0` - address, `1` - code hash).
+/// Version `1` contracts (having no `#pages` field) are deserialized with zero pages.
+/// The contract constructor runs only at deploy time, so it isn't serialized (neither are the nonce and salt).
+///
+/// Panics when a length doesn't fit its field. Deployed contracts always fit, since the deploy and upgrade
+/// transactions are bounded the same way (a 1 byte name length, 2 bytes counts and a code length up to `u32::MAX`).
+pub struct WasmContractSerializer;
+
+/// Deserializes a contract serialized by `WasmContractSerializer`.
+/// Contracts serialized as JSON (by previous releases) are deserialized as well.
+pub struct WasmContractDeserializer;
+
+impl ContractSerializer for WasmContractSerializer {
+    fn serialize(contract: &Contract) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(contract.wasm.len() + 64);
+
+        buf.write_u8(CONTRACT_SERIALIZE_VERSION).unwrap();
+
+        let name = contract.name.as_bytes();
+        buf.write_u16::<BigEndian>(field_len(name.len(), "name"))
+            .unwrap();
+        buf.extend_from_slice(name);
+
+        buf.extend_from_slice(contract.author.as_slice());
+
+        buf.write_u16::<BigEndian>(field_len(contract.admins.len(), "admins"))
+            .unwrap();
+        for admin in contract.admins.iter() {
+            buf.extend_from_slice(admin.as_slice());
+        }
+
+        buf.write_u16::<BigEndian>(field_len(contract.deps.len(), "deps"))
+            .unwrap();
+        for dep in contract.deps.iter() {
+            match dep {
+                Dependency::Address(addr) => {
+                    buf.write_u8(0).unwrap();
+                    buf.extend_from_slice(addr.as_slice());
+                }
+                Dependency::CodeHash(hash) => {
+                    buf.write_u8(1).unwrap();
+                    buf.extend_from_slice(&hash.0);
+                }
+            }
+        }
+
+        buf.write_u16::<BigEndian>(contract.pages_count).unwrap();
+
+        buf.write_u32::<BigEndian>(field_len(contract.wasm.len(), "code"))
+            .unwrap();
+        buf.extend_from_slice(&contract.wasm);
+
+        buf
+    }
+}
+
+impl ContractDeserializer for WasmContractDeserializer {
+    fn deserialize(bytes: Vec<u8>) -> Result<Contract, ContractDeserializeError> {
+        match bytes.first() {
+            None => Err(ContractDeserializeError::EmptyInput),
+            Some(&JSON_CONTRACT_MARKER) => WasmContractJsonDeserializer::deserialize(bytes),
+            Some(&version @ 1..=CONTRACT_SERIALIZE_VERSION) => {
+                deserialize_binary(&bytes[1..], version)
+            }
+            Some(&version) => Err(ContractDeserializeError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+/// Converts a JSON serialized contract (see `WasmContractJsonSerializer`) into the binary format.
+/// Contracts already in the binary format are returned as is.
+pub fn migrate_json_contract(bytes: &[u8]) -> Result<Vec<u8>, ContractDeserializeError> {
+    match bytes.first() {
+        Some(&JSON_CONTRACT_MARKER) => {
+            let contract = WasmContractJsonDeserializer::deserialize(bytes.to_vec())?;
+
+            Ok(WasmContractSerializer::serialize(&contract))
+        }
+        _ => {
+            // validating the input is a well-formed binary contract
+            WasmContractDeserializer::deserialize(bytes.to_vec())?;
+
+            Ok(bytes.to_vec())
+        }
+    }
+}
+
+fn field_len<T: TryFrom<usize>>(len: usize, field: &'static str) -> T {
+    T::try_from(len)
+        .unwrap_or_else(|_| panic!("contract `{}` length `{}` overflows its field", field, len))
+}
+
+macro_rules! ensure_enough_bytes {
+    ($res: expr, $field: expr) => {{
+        if $res.is_err() {
+            return Err(ContractDeserializeError::NotEnoughBytes($field));
+        }
+    }};
+}
+
+fn deserialize_binary(bytes: &[u8], version: u8) -> Result<Contract, ContractDeserializeError> {
+    let mut cursor = Cursor::new(bytes);
+
+    let name = read_name(&mut cursor)?;
+    let author = read_address(&mut cursor, "author")?;
+    let admins = read_admins(&mut cursor)?;
+    let deps = read_deps(&mut cursor)?;
+
+    let pages_count = if version >= 2 {
+        read_pages_count(&mut cursor)?
+    } else {
+        0
+    };
+
+    let wasm = read_code(&mut cursor)?;
+
+    let remaining = bytes.len() - cursor.position() as usize;
+    if remaining > 0 {
+        return Err(ContractDeserializeError::TrailingBytes(remaining));
+    }
+
+    let contract = Contract {
+        wasm,
+        name,
+        author,
+        admins,
+        deps,
+        pages_count,
+        ctor: None,
+        nonce: 0,
+        salt: None,
+        gas_limit: 0,
+        gas_price: 0,
+    };
+
+    Ok(contract)
+}
+
+fn read_name(cursor: &mut Cursor<&[u8]>) -> Result<String, ContractDeserializeError> {
+    let res = cursor.read_u16::<BigEndian>();
+    ensure_enough_bytes!(res, "name length");
+
+    let mut name = vec![0; res.unwrap() as usize];
+    let res = cursor.read_exact(&mut name);
+    ensure_enough_bytes!(res, "name");
+
+    String::from_utf8(name).map_err(|_| ContractDeserializeError::NameNotValidUTF8String)
+}
+
+fn read_admins(cursor: &mut Cursor<&[u8]>) -> Result<Vec<Address>, ContractDeserializeError> {
+    let res = cursor.read_u16::<BigEndian>();
+    ensure_enough_bytes!(res, "admins count");
+
+    (0..res.unwrap())
+        .map(|_| read_address(cursor, "admins"))
+        .collect()
+}
+
+fn read_deps(cursor: &mut Cursor<&[u8]>) -> Result<Vec<Dependency>, ContractDeserializeError> {
+    let res = cursor.read_u16::<BigEndian>();
+    ensure_enough_bytes!(res, "deps count");
+
+    (0..res.unwrap()).map(|_| read_dep(cursor)).collect()
+}
+
+fn read_dep(cursor: &mut Cursor<&[u8]>) -> Result<Dependency, ContractDeserializeError> {
+    let res = cursor.read_u8();
+    ensure_enough_bytes!(res, "dep kind");
+
+    match res.unwrap() {
+        0 => {
+            let addr = read_address(cursor, "dep")?;
+
+            Ok(Dependency::Address(addr))
+        }
+        1 => {
+            let mut hash = [0; 32];
+
+            let res = cursor.read_exact(&mut hash);
+            ensure_enough_bytes!(res, "dep");
+
+            Ok(Dependency::CodeHash(CodeHash(hash)))
+        }
+        kind => Err(ContractDeserializeError::InvalidDependencyKind(kind)),
+    }
+}
+
+fn read_pages_count(cursor: &mut Cursor<&[u8]>) -> Result<u16, ContractDeserializeError> {
+    let res = cursor.read_u16::<BigEndian>();
+    ensure_enough_bytes!(res, "pages count");
+
+    Ok(res.unwrap())
+}
+
+fn read_code(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ContractDeserializeError> {
+    let res = cursor.read_u32::<BigEndian>();
+    ensure_enough_bytes!(res, "code length");
+
+    let mut code = vec![0; res.unwrap() as usize];
+    let res = cursor.read_exact(&mut code);
+    ensure_enough_bytes!(res, "code");
+
+    Ok(code)
+}
+
+fn read_address(
+    cursor: &mut Cursor<&[u8]>,
+    field: &'static str,
+) -> Result<Address, ContractDeserializeError> {
+    let mut addr = vec![0; Address::len()];
+
+    let res = cursor.read_exact(&mut addr);
+    ensure_enough_bytes!(res, field);
+
+    Ok(Address::from(addr.as_ref()))
+}
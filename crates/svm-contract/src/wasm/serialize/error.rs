@@ -0,0 +1,58 @@
+/// Deserializing a stored contract error
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum ContractDeserializeError {
+    EmptyInput,
+    UnsupportedVersion(u8),
+    NotEnoughBytes(&'static str),
+    NameNotValidUTF8String,
+    InvalidDependencyKind(u8),
+    TrailingBytes(usize),
+    InvalidJson(String),
+}
+
+impl std::error::Error for ContractDeserializeError {
+    fn description(&self) -> &'static str {
+        match self {
+            ContractDeserializeError::EmptyInput => "Empty input",
+            ContractDeserializeError::UnsupportedVersion(_) => "Unsupported version",
+            ContractDeserializeError::NotEnoughBytes(_) => "Not enough bytes",
+            ContractDeserializeError::NameNotValidUTF8String => "Name isn't a valid UTF-8 string",
+            ContractDeserializeError::InvalidDependencyKind(_) => "Invalid dependency kind",
+            ContractDeserializeError::TrailingBytes(_) => "Trailing bytes",
+            ContractDeserializeError::InvalidJson(_) => "Invalid JSON",
+        }
+    }
+}
+
+impl std::fmt::Display for ContractDeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            ContractDeserializeError::EmptyInput => "Empty input".to_string(),
+            ContractDeserializeError::UnsupportedVersion(version) => {
+                format!("Unsupported version: `{}`", version)
+            }
+            ContractDeserializeError::NotEnoughBytes(field) => {
+                format!("Not enough bytes (field: `{}`)", field)
+            }
+            ContractDeserializeError::NameNotValidUTF8String => {
+                "Name isn't a valid UTF-8 string".to_string()
+            }
+            ContractDeserializeError::InvalidDependencyKind(kind) => {
+                format!("Invalid dependency kind: `{}`", kind)
+            }
+            ContractDeserializeError::TrailingBytes(count) => {
+                format!("Trailing bytes: `{}`", count)
+            }
+            ContractDeserializeError::InvalidJson(e) => format!("Invalid JSON: {}", e),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for ContractDeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
@@ -0,0 +1,23 @@
+use super::error::ContractDeserializeError;
+
+use crate::traits::{ContractDeserializer, ContractSerializer};
+use crate::wasm::Contract;
+
+/// Serializes a contract as JSON. Kept for reading (and migrating) stores written by previous releases.
+pub struct WasmContractJsonSerializer;
+
+/// Deserializes a JSON contract (see `WasmContractJsonSerializer`)
+pub struct WasmContractJsonDeserializer;
+
+impl ContractSerializer for WasmContractJsonSerializer {
+    fn serialize(contract: &Contract) -> Vec<u8> {
+        serde_json::to_vec(&contract).unwrap()
+    }
+}
+
+impl ContractDeserializer for WasmContractJsonDeserializer {
+    fn deserialize(bytes: Vec<u8>) -> Result<Contract, ContractDeserializeError> {
+        serde_json::from_slice(&bytes)
+            .map_err(|e| ContractDeserializeError::InvalidJson(e.to_string()))
+    }
+}
@@ -0,0 +1,17 @@
+//! Serializing contracts for storage (see `ContractStore`).
+//!
+//! The default format is a compact, versioned, binary format (see `WasmContractSerializer`).
+//! Contracts stored by previous releases were serialized as JSON (see `WasmContractJsonSerializer`).
+//! `WasmContractDeserializer` reads both formats, so existing stores keep working and can be migrated
+//! contract by contract (see `migrate_json_contract`).
+
+mod binary;
+mod error;
+mod json;
+
+pub use binary::{
+    migrate_json_contract, WasmContractDeserializer, WasmContractSerializer,
+    CONTRACT_SERIALIZE_VERSION,
+};
+pub use error::ContractDeserializeError;
+pub use json::{WasmContractJsonDeserializer, WasmContractJsonSerializer};
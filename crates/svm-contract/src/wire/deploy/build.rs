@@ -1,12 +1,23 @@
 use byteorder::{BigEndian, WriteBytesExt};
 use svm_common::Address;
 
+use crate::types::Dependency;
+use crate::wasm::{ContractCtor, WasmArgValue};
+
 /// A raw contract builder. Used for testing.
 pub struct WireContractBuilder {
     version: Option<u32>,
     name: Option<String>,
     author: Option<Address>,
+    admins: Vec<Address>,
+    deps: Vec<Dependency>,
+    pages_count: u16,
     code: Option<Vec<u8>>,
+    ctor: Option<ContractCtor>,
+    nonce: u64,
+    salt: Option<[u8; 32]>,
+    gas_limit: Option<u64>,
+    gas_price: Option<u64>,
 }
 
 #[allow(missing_docs)]
@@ -17,7 +28,15 @@ impl WireContractBuilder {
             version: None,
             name: None,
             author: None,
+            admins: Vec::new(),
+            deps: Vec::new(),
+            pages_count: 0,
             code: None,
+            ctor: None,
+            nonce: 0,
+            salt: None,
+            gas_limit: None,
+            gas_price: None,
         }
     }
 
@@ -36,11 +55,54 @@ impl WireContractBuilder {
         self
     }
 
+    pub fn with_admins(mut self, admins: &[Address]) -> Self {
+        self.admins = admins.to_vec();
+        self
+    }
+
+    pub fn with_deps(mut self, deps: &[Dependency]) -> Self {
+        self.deps = deps.to_vec();
+        self
+    }
+
+    pub fn with_pages_count(mut self, pages_count: u16) -> Self {
+        self.pages_count = pages_count;
+        self
+    }
+
     pub fn with_code(mut self, code: &[u8]) -> Self {
         self.code = Some(code.to_vec());
         self
     }
 
+    pub fn with_ctor(mut self, func_name: &str, func_args: &[WasmArgValue]) -> Self {
+        self.ctor = Some(ContractCtor {
+            func_name: func_name.to_string(),
+            func_args: func_args.to_vec(),
+        });
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: u64) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn with_salt(mut self, salt: &[u8; 32]) -> Self {
+        self.salt = Some(*salt);
+        self
+    }
+
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
     pub fn build(&mut self) -> Vec<u8> {
         let mut buf = Vec::new();
 
@@ -49,7 +111,12 @@ impl WireContractBuilder {
         self.write_author(&mut buf);
         self.write_admins(&mut buf);
         self.write_deps(&mut buf);
+        self.write_pages_count(&mut buf);
         self.write_code(&mut buf);
+        self.write_ctor(&mut buf);
+        self.write_nonce(&mut buf);
+        self.write_salt(&mut buf);
+        self.write_gas(&mut buf);
 
         buf
     }
@@ -75,11 +142,35 @@ impl WireContractBuilder {
     }
 
     fn write_admins(&self, buf: &mut Vec<u8>) {
-        buf.write_u16::<BigEndian>(0).unwrap();
+        assert!(self.admins.len() <= std::u16::MAX as usize);
+        buf.write_u16::<BigEndian>(self.admins.len() as u16)
+            .unwrap();
+
+        for admin in self.admins.iter() {
+            buf.extend_from_slice(admin.as_slice());
+        }
     }
 
     fn write_deps(&self, buf: &mut Vec<u8>) {
-        buf.write_u16::<BigEndian>(0).unwrap();
+        assert!(self.deps.len() <= std::u16::MAX as usize);
+        buf.write_u16::<BigEndian>(self.deps.len() as u16).unwrap();
+
+        for dep in self.deps.iter() {
+            match dep {
+                Dependency::Address(addr) => {
+                    buf.write_u8(0).unwrap();
+                    buf.extend_from_slice(addr.as_slice());
+                }
+                Dependency::CodeHash(hash) => {
+                    buf.write_u8(1).unwrap();
+                    buf.extend_from_slice(&hash.0);
+                }
+            }
+        }
+    }
+
+    fn write_pages_count(&self, buf: &mut Vec<u8>) {
+        buf.write_u16::<BigEndian>(self.pages_count).unwrap();
     }
 
     fn write_code(&self, buf: &mut Vec<u8>) {
@@ -88,4 +179,58 @@ impl WireContractBuilder {
         buf.write_u64::<BigEndian>(code.len() as u64).unwrap();
         buf.extend_from_slice(code.as_slice());
     }
+
+    fn write_ctor(&self, buf: &mut Vec<u8>) {
+        // version `0` has no constructor field
+        if self.version.unwrap() == 0 {
+            return;
+        }
+
+        match self.ctor {
+            None => buf.write_u8(0).unwrap(),
+            Some(ref ctor) => {
+                let bytes = ctor.func_name.as_bytes();
+
+                assert!(!bytes.is_empty() && bytes.len() <= 255);
+                buf.write_u8(bytes.len() as u8).unwrap();
+                buf.extend_from_slice(bytes);
+
+                crate::wire::exec::write_func_args(&ctor.func_args, buf);
+            }
+        }
+    }
+
+    fn write_nonce(&self, buf: &mut Vec<u8>) {
+        // versions prior to `2` have no nonce field
+        if self.version.unwrap() < 2 {
+            return;
+        }
+
+        buf.write_u64::<BigEndian>(self.nonce).unwrap();
+    }
+
+    fn write_salt(&self, buf: &mut Vec<u8>) {
+        // versions prior to `2` have no salt field
+        if self.version.unwrap() < 2 {
+            return;
+        }
+
+        match self.salt {
+            None => buf.write_u8(0).unwrap(),
+            Some(ref salt) => {
+                buf.write_u8(1).unwrap();
+                buf.extend_from_slice(salt);
+            }
+        }
+    }
+
+    fn write_gas(&self, buf: &mut Vec<u8>) {
+        // versions prior to `3` have no gas fields
+        if self.version.unwrap() < 3 {
+            return;
+        }
+
+        buf.write_u64::<BigEndian>(self.gas_limit.unwrap()).unwrap();
+        buf.write_u64::<BigEndian>(self.gas_price.unwrap()).unwrap();
+    }
 }
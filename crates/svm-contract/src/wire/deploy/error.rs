@@ -1,15 +1,36 @@
 use super::field::Field;
 
+use crate::load_error::ContractLoadError;
+use crate::types::{Dependency, MAX_DEPS};
+
+use svm_common::Address;
+
 #[allow(missing_docs)]
 pub enum ContractBuildError {
     EmptyName,
     NameNotValidUTF8String,
-    DepsNotSupportedYet,
-    AdminsNotSupportedYet,
+    InvalidDependencyKind(u8),
+    DependencyNotFound(Dependency),
+    DuplicateDependency(Dependency),
+    TooManyDependencies(usize),
+    InvalidDependency(Dependency),
     NotEnoughBytes(Field),
     UnsupportedProtoVersion(u32),
     NoAuthors,
+    DuplicateAdmin(Address),
     InvalidWasm,
+    CodeTooLarge(u64),
+    FloatsNotAllowed,
+    UnsupportedOpcode(u32),
+    UnsupportedImport(String, String),
+    NoExportedFunctions,
+    MultipleMemories,
+    MemoryLimitExceeded(u32),
+    MemoryMaximumExceeded(Option<u32>),
+    CtorNameNotValidUTF8String,
+    InvalidCtorArgs,
+    InvalidSaltFlag(u8),
+    LoadFailed(ContractLoadError),
 }
 
 impl std::error::Error for ContractBuildError {
@@ -17,12 +38,30 @@ impl std::error::Error for ContractBuildError {
         match self {
             ContractBuildError::EmptyName => "Name must not be empty",
             ContractBuildError::NameNotValidUTF8String => "Name must be a valid UTF-8 string",
-            ContractBuildError::DepsNotSupportedYet => "Dependencies are supported yet",
-            ContractBuildError::AdminsNotSupportedYet => "Admins are not supported yet",
+            ContractBuildError::InvalidDependencyKind(_) => "Invalid dependency kind",
+            ContractBuildError::DependencyNotFound(_) => "Dependency not found",
+            ContractBuildError::DuplicateDependency(_) => "Duplicate dependency",
+            ContractBuildError::TooManyDependencies(_) => "Too many dependencies",
+            ContractBuildError::InvalidDependency(_) => "Invalid dependency",
+            ContractBuildError::DuplicateAdmin(_) => "Duplicate admin",
             ContractBuildError::NotEnoughBytes(_) => "Not enough bytes",
             ContractBuildError::UnsupportedProtoVersion(_) => "Unsupported protocol version",
             ContractBuildError::NoAuthors => "Must have authors",
             ContractBuildError::InvalidWasm => "Invalid wasm format",
+            ContractBuildError::CodeTooLarge(_) => "Code too large",
+            ContractBuildError::FloatsNotAllowed => "Floats are not allowed",
+            ContractBuildError::UnsupportedOpcode(_) => "Unsupported opcode",
+            ContractBuildError::UnsupportedImport(..) => "Unsupported import",
+            ContractBuildError::NoExportedFunctions => "Must export functions",
+            ContractBuildError::MultipleMemories => "Must have at most a single memory",
+            ContractBuildError::MemoryLimitExceeded(_) => "Memory limit exceeded",
+            ContractBuildError::MemoryMaximumExceeded(_) => "Memory maximum exceeded",
+            ContractBuildError::CtorNameNotValidUTF8String => {
+                "Constructor name must be a valid UTF-8 string"
+            }
+            ContractBuildError::InvalidCtorArgs => "Invalid constructor args",
+            ContractBuildError::InvalidSaltFlag(_) => "Invalid salt flag",
+            ContractBuildError::LoadFailed(_) => "Load failed",
         }
     }
 }
@@ -34,12 +73,23 @@ impl std::fmt::Display for ContractBuildError {
             ContractBuildError::NameNotValidUTF8String => {
                 String::from("Name must be a valid UTF-8 string")
             }
-            ContractBuildError::DepsNotSupportedYet => {
-                String::from("Dependencies are supported yet")
+            ContractBuildError::InvalidDependencyKind(kind) => {
+                format!("Invalid dependency kind: `{}`", kind)
             }
-            ContractBuildError::AdminsNotSupportedYet => {
-                String::from("Admins are not supported yet")
+            ContractBuildError::DependencyNotFound(dep) => {
+                format!("Dependency not found: `{:?}`", dep)
             }
+            ContractBuildError::DuplicateDependency(dep) => {
+                format!("Duplicate dependency: `{:?}`", dep)
+            }
+            ContractBuildError::TooManyDependencies(count) => format!(
+                "Too many dependencies: `{}` (the maximum is `{}`)",
+                count, MAX_DEPS
+            ),
+            ContractBuildError::InvalidDependency(dep) => {
+                format!("Invalid dependency: `{:?}`", dep)
+            }
+            ContractBuildError::DuplicateAdmin(addr) => format!("Duplicate admin: `{:?}`", addr),
             ContractBuildError::NotEnoughBytes(field) => {
                 format!("Not enough bytes (field: {})", field)
             }
@@ -48,6 +98,33 @@ impl std::fmt::Display for ContractBuildError {
             }
             ContractBuildError::NoAuthors => String::from("Must have Authors"),
             ContractBuildError::InvalidWasm => String::from("Invalid wasm format"),
+            ContractBuildError::CodeTooLarge(len) => format!("Code too large: `{}` bytes", len),
+            ContractBuildError::FloatsNotAllowed => String::from("Floats are not allowed"),
+            ContractBuildError::UnsupportedOpcode(func_idx) => {
+                format!("Unsupported opcode (function: `{}`)", func_idx)
+            }
+            ContractBuildError::UnsupportedImport(module, field) => {
+                format!("Unsupported import: `{}.{}`", module, field)
+            }
+            ContractBuildError::NoExportedFunctions => String::from("Must export functions"),
+            ContractBuildError::MultipleMemories => {
+                String::from("Must have at most a single memory")
+            }
+            ContractBuildError::MemoryLimitExceeded(pages) => {
+                format!("Memory limit exceeded (initial pages: `{}`)", pages)
+            }
+            ContractBuildError::MemoryMaximumExceeded(None) => {
+                String::from("Memory must declare its maximum pages")
+            }
+            ContractBuildError::MemoryMaximumExceeded(Some(pages)) => {
+                format!("Memory maximum exceeded (maximum pages: `{}`)", pages)
+            }
+            ContractBuildError::CtorNameNotValidUTF8String => {
+                String::from("Constructor name must be a valid UTF-8 string")
+            }
+            ContractBuildError::InvalidCtorArgs => String::from("Invalid constructor args"),
+            ContractBuildError::InvalidSaltFlag(flag) => format!("Invalid salt flag: `{}`", flag),
+            ContractBuildError::LoadFailed(e) => format!("Load failed: {:?}", e),
         };
 
         write!(f, "{}", msg)
@@ -4,9 +4,21 @@ pub enum Field {
     Name,
     Author,
     AdminsCount,
+    Admins,
     CodeLength,
     DepsCount,
+    DepKind,
+    Dep,
+    PagesCount,
     Code,
+    CtorNameLength,
+    CtorName,
+    CtorArgs,
+    Nonce,
+    SaltFlag,
+    Salt,
+    GasLimit,
+    GasPrice,
 }
 
 impl std::fmt::Display for Field {
@@ -17,9 +29,21 @@ impl std::fmt::Display for Field {
             Field::Name => "Name",
             Field::Author => "Author",
             Field::AdminsCount => "AdminsCount",
+            Field::Admins => "Admins",
             Field::CodeLength => "CodeLength",
             Field::DepsCount => "DepsCount",
+            Field::DepKind => "DepKind",
+            Field::Dep => "Dep",
+            Field::PagesCount => "PagesCount",
             Field::Code => "Code",
+            Field::CtorNameLength => "CtorNameLength",
+            Field::CtorName => "CtorName",
+            Field::CtorArgs => "CtorArgs",
+            Field::Nonce => "Nonce",
+            Field::SaltFlag => "SaltFlag",
+            Field::Salt => "Salt",
+            Field::GasLimit => "GasLimit",
+            Field::GasPrice => "GasPrice",
         };
 
         write!(f, "{}", field)
@@ -14,12 +14,81 @@
 //!  |_____________|_______________________________________|
 //!  |           |                                         |
 //!  |   #deps   |           dependencies                  |
-//!  | (2 bytes) |              (TBD)                      |
+//!  | (2 bytes) |  (kind (1 byte) + address / code-hash)  |
 //!  |___________|_________________________________________|
+//!  |           |
+//!  |  #pages   |
+//!  | (2 bytes) |
+//!  |___________|
 //!  |                |                                    |
 //!  |  code length   |              code                  |
 //!  |   (8 bytes)    |             (wasm)                 |
 //!  |________________|____________________________________|
+//!
+//!  Each dependency is encoded as a kind byte followed by:
+//!  * kind `0` - a deployed contract address (20 bytes)
+//!  * kind `1` - a deployed contract code-hash (32 bytes)
+//!
+//!  `#pages` is the number of storage pages allocated for the contract at deploy time.
+//!  The contract storage may grow beyond it when running (charging gas for each new page).
+//!
+//!
+//!     Deploy Contract Wire Protocol Version 0.0.0.1
+//!  -------------------------------------------------------
+//!  Same as version `0.0.0.0` with the constructor fields following the code:
+//!
+//!  |                |                                    |
+//!  |  code length   |              code                  |
+//!  |   (8 bytes)    |             (wasm)                 |
+//!  |________________|____________________________________|
+//!  |             |                                       |
+//!  |  ctor name  |                                       |
+//!  |   length    |          ctor name (UTF-8)            |
+//!  |  (1 byte)   |                                       |
+//!  |_____________|_______________________________________|
+//!  |                                                     |
+//!  |           ctor args (as exec func args)             |
+//!  |_____________________________________________________|
+//!
+//!  A zero ctor name length means the contract has no constructor (the ctor args are omitted).
+//!  The constructor is an exported function the runtime calls when the contract is deployed.
+//!
+//!
+//!     Deploy Contract Wire Protocol Version 0.0.0.2
+//!  -------------------------------------------------------
+//!  Same as version `0.0.0.1` with the address fields following the constructor fields:
+//!
+//!  |                                                     |
+//!  |           ctor args (as exec func args)             |
+//!  |_____________________________________________________|
+//!  |                                                     |
+//!  |                   author nonce                      |
+//!  |                    (8 bytes)                        |
+//!  |_____________________________________________________|
+//!  |             |                                       |
+//!  |  salt flag  |                salt                   |
+//!  |  (1 byte)   |   (32 bytes, only when flag is `1`)   |
+//!  |_____________|_______________________________________|
+//!
+//!  The contract account address is derived from the author, nonce, salt and code
+//!  (see `SaltedContractAddressCompute`). Prior versions have a zero nonce and no salt.
+//!
+//!
+//!     Deploy Contract Wire Protocol Version 0.0.0.3
+//!  -------------------------------------------------------
+//!  Same as version `0.0.0.2` with the gas fields following the salt:
+//!
+//!  |             |                                       |
+//!  |  salt flag  |                salt                   |
+//!  |  (1 byte)   |   (32 bytes, only when flag is `1`)   |
+//!  |_____________|_______________________________________|
+//!  |                          |                          |
+//!  |        gas limit         |        gas price         |
+//!  |        (8 bytes)         |        (8 bytes)         |
+//!  |__________________________|__________________________|
+//!
+//!  The gas fields apply to the constructor execution (as the gas fields of an exec transaction).
+//!  Prior versions have no constructor gas limit (the runtime gas limit still applies) and a zero gas price.
 
 mod build;
 mod error;
@@ -31,3 +100,4 @@ pub use build::WireContractBuilder;
 pub use error::ContractBuildError;
 pub use parse::parse_contract;
 pub use validate::validate_contract;
+pub(crate) use validate::WASM_PREAMBLE;
@@ -1,6 +1,8 @@
 use super::error::ContractBuildError;
 use super::field::Field;
-use crate::wasm::Contract;
+use crate::types::{CodeHash, Dependency};
+use crate::wasm::{Contract, ContractCtor};
+use crate::wire::exec::TransactionBuildError;
 use svm_common::Address;
 
 use byteorder::{BigEndian, ReadBytesExt};
@@ -24,15 +26,32 @@ macro_rules! ensure_enough_bytes {
 pub fn parse_contract(bytes: &[u8]) -> Result<Contract, ContractBuildError> {
     let mut cursor = Cursor::new(bytes);
 
-    parse_version(&mut cursor)?;
+    let version = parse_version(&mut cursor)?;
 
     let name = parse_name(&mut cursor)?;
     let author = parse_author(&mut cursor)?;
-    let _admins = parse_admins(&mut cursor)?;
-    parse_deps(&mut cursor)?;
+    let admins = parse_admins(&mut cursor)?;
+    let deps = parse_deps(&mut cursor)?;
+    let pages_count = parse_pages_count(&mut cursor)?;
     let wasm = parse_code(&mut cursor)?;
-
-    let contract = Contract { name, wasm, author };
+    let ctor = parse_ctor(&mut cursor, version)?;
+    let nonce = parse_nonce(&mut cursor, version)?;
+    let salt = parse_salt(&mut cursor, version)?;
+    let (gas_limit, gas_price) = parse_gas(&mut cursor, version)?;
+
+    let contract = Contract {
+        name,
+        wasm,
+        author,
+        admins,
+        deps,
+        pages_count,
+        ctor,
+        nonce,
+        salt,
+        gas_limit,
+        gas_price,
+    };
 
     Ok(contract)
 }
@@ -43,7 +62,7 @@ fn parse_version(cursor: &mut Cursor<&[u8]>) -> Result<u32, ContractBuildError>
     ensure_enough_bytes!(res, Field::Version);
 
     let version = res.unwrap();
-    if version != 0 {
+    if version > 3 {
         return Err(ContractBuildError::UnsupportedProtoVersion(version));
     }
 
@@ -86,40 +105,81 @@ fn parse_admins(cursor: &mut Cursor<&[u8]>) -> Result<Vec<Address>, ContractBuil
 
     ensure_enough_bytes!(res, Field::AdminsCount);
 
-    let admin_count = res.unwrap() as usize;
-    if admin_count > 0 {
-        return Err(ContractBuildError::AdminsNotSupportedYet);
-    }
+    let admins_count = res.unwrap() as usize;
 
-    // let mut admins = Vec::<Address>::with_capacity(admin_count);
-    // for i in 0..admin_count {
-    //     let addr = parse_address(addr, Field::Admins);
-    //     admins.push(addr);
-    // }
+    let mut admins = Vec::<Address>::with_capacity(admins_count);
 
-    Ok(Vec::new())
+    for _ in 0..admins_count {
+        let addr = parse_address(cursor, Field::Admins)?;
+        admins.push(addr);
+    }
+
+    Ok(admins)
 }
 
-fn parse_deps(cursor: &mut Cursor<&[u8]>) -> Result<(), ContractBuildError> {
+fn parse_deps(cursor: &mut Cursor<&[u8]>) -> Result<Vec<Dependency>, ContractBuildError> {
     let res = cursor.read_u16::<BigEndian>();
 
     ensure_enough_bytes!(res, Field::DepsCount);
 
     let deps_count = res.unwrap() as usize;
 
-    if deps_count > 0 {
-        return Err(ContractBuildError::DepsNotSupportedYet);
+    let mut deps = Vec::<Dependency>::with_capacity(deps_count);
+
+    for _ in 0..deps_count {
+        let dep = parse_dep(cursor)?;
+        deps.push(dep);
     }
 
-    Ok(())
+    Ok(deps)
+}
+
+fn parse_dep(cursor: &mut Cursor<&[u8]>) -> Result<Dependency, ContractBuildError> {
+    let res = cursor.read_u8();
+
+    ensure_enough_bytes!(res, Field::DepKind);
+
+    match res.unwrap() {
+        0 => {
+            let addr = parse_address(cursor, Field::Dep)?;
+
+            Ok(Dependency::Address(addr))
+        }
+        1 => {
+            let mut hash = [0; 32];
+
+            let res = cursor.read_exact(&mut hash);
+            ensure_enough_bytes!(res, Field::Dep);
+
+            Ok(Dependency::CodeHash(CodeHash(hash)))
+        }
+        kind => Err(ContractBuildError::InvalidDependencyKind(kind)),
+    }
+}
+
+fn parse_pages_count(cursor: &mut Cursor<&[u8]>) -> Result<u16, ContractBuildError> {
+    let res = cursor.read_u16::<BigEndian>();
+
+    ensure_enough_bytes!(res, Field::PagesCount);
+
+    let pages_count = res.unwrap();
+
+    debug!("    parsed contract #pages: {}", pages_count);
+
+    Ok(pages_count)
 }
 
 fn parse_code(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ContractBuildError> {
     let res = cursor.read_u64::<BigEndian>();
     ensure_enough_bytes!(res, Field::CodeLength);
 
-    let code_len = res.unwrap() as usize;
-    let mut code = vec![0; code_len];
+    // the stored contract code length is 4 bytes long (see `WasmContractSerializer`)
+    let code_len = res.unwrap();
+    if code_len > u64::from(std::u32::MAX) {
+        return Err(ContractBuildError::CodeTooLarge(code_len));
+    }
+
+    let mut code = vec![0; code_len as usize];
 
     let res = cursor.read_exact(&mut code);
     ensure_enough_bytes!(res, Field::Code);
@@ -127,6 +187,112 @@ fn parse_code(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ContractBuildError>
     Ok(code)
 }
 
+/// Version `0` contracts have no constructor.
+/// Otherwise, a zero constructor name length means there is no constructor (and no args follow).
+fn parse_ctor(
+    cursor: &mut Cursor<&[u8]>,
+    version: u32,
+) -> Result<Option<ContractCtor>, ContractBuildError> {
+    if version == 0 {
+        return Ok(None);
+    }
+
+    let res = cursor.read_u8();
+    ensure_enough_bytes!(res, Field::CtorNameLength);
+
+    let name_len = res.unwrap() as usize;
+    if name_len == 0 {
+        return Ok(None);
+    }
+
+    let mut name_buf = vec![0; name_len];
+    let res = cursor.read_exact(&mut name_buf);
+    ensure_enough_bytes!(res, Field::CtorName);
+
+    let func_name =
+        String::from_utf8(name_buf).map_err(|_| ContractBuildError::CtorNameNotValidUTF8String)?;
+
+    let func_args = crate::wire::exec::parse_func_args(cursor).map_err(|e| match e {
+        TransactionBuildError::NotEnoughBytes(_) => {
+            ContractBuildError::NotEnoughBytes(Field::CtorArgs)
+        }
+        _ => ContractBuildError::InvalidCtorArgs,
+    })?;
+
+    debug!("    parsed contract constructor: {:?}", func_name);
+
+    Ok(Some(ContractCtor {
+        func_name,
+        func_args,
+    }))
+}
+
+/// Versions prior to `2` have no nonce (it's considered `0`)
+fn parse_nonce(cursor: &mut Cursor<&[u8]>, version: u32) -> Result<u64, ContractBuildError> {
+    if version < 2 {
+        return Ok(0);
+    }
+
+    let res = cursor.read_u64::<BigEndian>();
+    ensure_enough_bytes!(res, Field::Nonce);
+
+    let nonce = res.unwrap();
+
+    debug!("    parsed contract nonce: {}", nonce);
+
+    Ok(nonce)
+}
+
+/// Versions prior to `2` have no salt.
+/// Otherwise, a salt flag `0` means there is no salt, and a salt flag `1` is followed by a 32 bytes salt.
+fn parse_salt(
+    cursor: &mut Cursor<&[u8]>,
+    version: u32,
+) -> Result<Option<[u8; 32]>, ContractBuildError> {
+    if version < 2 {
+        return Ok(None);
+    }
+
+    let res = cursor.read_u8();
+    ensure_enough_bytes!(res, Field::SaltFlag);
+
+    match res.unwrap() {
+        0 => Ok(None),
+        1 => {
+            let mut salt = [0; 32];
+
+            let res = cursor.read_exact(&mut salt);
+            ensure_enough_bytes!(res, Field::Salt);
+
+            Ok(Some(salt))
+        }
+        flag => Err(ContractBuildError::InvalidSaltFlag(flag)),
+    }
+}
+
+/// Versions prior to `3` have no gas fields. Their constructor isn't limited by its own gas limit
+/// (the runtime gas limit still applies) and is free of charge.
+fn parse_gas(cursor: &mut Cursor<&[u8]>, version: u32) -> Result<(u64, u64), ContractBuildError> {
+    if version < 3 {
+        return Ok((std::u64::MAX, 0));
+    }
+
+    let res = cursor.read_u64::<BigEndian>();
+    ensure_enough_bytes!(res, Field::GasLimit);
+    let gas_limit = res.unwrap();
+
+    let res = cursor.read_u64::<BigEndian>();
+    ensure_enough_bytes!(res, Field::GasPrice);
+    let gas_price = res.unwrap();
+
+    debug!(
+        "    parsed contract gas limit: {}, gas price: {}",
+        gas_limit, gas_price
+    );
+
+    Ok((gas_limit, gas_price))
+}
+
 fn parse_address(cursor: &mut Cursor<&[u8]>, field: Field) -> Result<Address, ContractBuildError> {
     let mut addr = vec![0; Address::len()];
 
@@ -1,9 +1,13 @@
 use super::error::ContractBuildError;
 use super::field::Field;
 
+use crate::types::MAX_DEPS;
 use crate::wasm::Contract;
 use svm_common::Address;
 
+/// `\0asm` followed by version `1` (Little-Endian)
+pub(crate) const WASM_PREAMBLE: [u8; 8] = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+
 pub fn validate_contract(contract: &Contract) -> Result<(), ContractBuildError> {
     validate_author(contract)?;
     validate_admins(contract)?;
@@ -13,7 +17,18 @@ pub fn validate_contract(contract: &Contract) -> Result<(), ContractBuildError>
     Ok(())
 }
 
-fn validate_deps(_contract: &Contract) -> Result<(), ContractBuildError> {
+/// The existence of the dependencies is validated by the environment (see `ContractEnv::validate_deps`)
+fn validate_deps(contract: &Contract) -> Result<(), ContractBuildError> {
+    if contract.deps.len() > MAX_DEPS {
+        return Err(ContractBuildError::TooManyDependencies(contract.deps.len()));
+    }
+
+    for (i, dep) in contract.deps.iter().enumerate() {
+        if contract.deps[..i].contains(dep) {
+            return Err(ContractBuildError::DuplicateDependency(dep.clone()));
+        }
+    }
+
     Ok(())
 }
 
@@ -21,7 +36,15 @@ fn validate_author(contract: &Contract) -> Result<(), ContractBuildError> {
     validate_account(&contract.author, Field::Author)
 }
 
-fn validate_admins(_contract: &Contract) -> Result<(), ContractBuildError> {
+fn validate_admins(contract: &Contract) -> Result<(), ContractBuildError> {
+    for (i, admin) in contract.admins.iter().enumerate() {
+        validate_account(admin, Field::Admins)?;
+
+        if contract.admins[..i].contains(admin) {
+            return Err(ContractBuildError::DuplicateAdmin(admin.clone()));
+        }
+    }
+
     Ok(())
 }
 
@@ -29,6 +52,14 @@ fn validate_account(_addr: &Address, _field: Field) -> Result<(), ContractBuildE
     Ok(())
 }
 
-fn validate_wasm(_contract: &Contract) -> Result<(), ContractBuildError> {
+/// Only the wasm preamble (magic number and version) is checked here.
+/// The complete deploy-time validation of the wasm module is done by the runtime (see `svm_compiler::validate_program`).
+fn validate_wasm(contract: &Contract) -> Result<(), ContractBuildError> {
+    let wasm = &contract.wasm;
+
+    if wasm.len() < WASM_PREAMBLE.len() || wasm[0..WASM_PREAMBLE.len()] != WASM_PREAMBLE {
+        return Err(ContractBuildError::InvalidWasm);
+    }
+
     Ok(())
 }
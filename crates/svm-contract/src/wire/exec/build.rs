@@ -1,12 +1,15 @@
 use crate::wasm::{WasmArgType, WasmArgValue};
 use byteorder::{BigEndian, WriteBytesExt};
-use svm_common::Address;
+use svm_common::{Address, Balance};
 
 /// A raw transaction builder. Used for testing
 pub struct WireTxBuilder {
     version: Option<u32>,
     contract: Option<Address>,
     sender: Option<Address>,
+    gas_limit: Option<u64>,
+    gas_price: Option<u64>,
+    value: Balance,
     func_name: Option<String>,
     func_args: Option<Vec<WasmArgValue>>,
 }
@@ -19,6 +22,9 @@ impl WireTxBuilder {
             version: None,
             contract: None,
             sender: None,
+            gas_limit: None,
+            gas_price: None,
+            value: Balance(0),
             func_name: None,
             func_args: None,
         }
@@ -39,6 +45,21 @@ impl WireTxBuilder {
         self
     }
 
+    pub fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    pub fn with_value(mut self, value: Balance) -> Self {
+        self.value = value;
+        self
+    }
+
     pub fn with_func_name(mut self, func_name: &str) -> Self {
         self.func_name = Some(func_name.to_string());
         self
@@ -55,6 +76,8 @@ impl WireTxBuilder {
         self.write_version(&mut buf);
         self.write_contract(&mut buf);
         self.write_sender(&mut buf);
+        self.write_gas(&mut buf);
+        self.write_value(&mut buf);
         self.write_func_name(&mut buf);
         self.write_func_args(&mut buf);
 
@@ -74,6 +97,25 @@ impl WireTxBuilder {
         self.write_address(&self.sender, buf);
     }
 
+    fn write_gas(&self, buf: &mut Vec<u8>) {
+        // version `0` has no gas fields
+        if self.version.unwrap() == 0 {
+            return;
+        }
+
+        buf.write_u64::<BigEndian>(self.gas_limit.unwrap()).unwrap();
+        buf.write_u64::<BigEndian>(self.gas_price.unwrap()).unwrap();
+    }
+
+    fn write_value(&self, buf: &mut Vec<u8>) {
+        // versions prior to `2` have no value field
+        if self.version.unwrap() < 2 {
+            return;
+        }
+
+        buf.write_u128::<BigEndian>(self.value.0).unwrap();
+    }
+
     fn write_func_name(&mut self, buf: &mut Vec<u8>) {
         let name = self.func_name.take().unwrap();
         let bytes = name.as_bytes();
@@ -87,36 +129,7 @@ impl WireTxBuilder {
     fn write_func_args(&self, buf: &mut Vec<u8>) {
         let args = self.func_args.as_ref().unwrap();
 
-        buf.write_u8(args.len() as u8).unwrap();
-
-        for arg in args {
-            match arg {
-                WasmArgValue::I32(v) => {
-                    let arg_type = WasmArgType::I32.into();
-                    buf.write_u8(arg_type).unwrap();
-                    buf.write_u32::<BigEndian>(*v).unwrap();
-                }
-                WasmArgValue::I64(v) => {
-                    let arg_type = WasmArgType::I64.into();
-                    buf.write_u8(arg_type).unwrap();
-                    buf.write_u64::<BigEndian>(*v).unwrap();
-                }
-                WasmArgValue::Fixed(offset_type, bytes) => {
-                    let offset_type = offset_type.into();
-
-                    buf.write_u8(offset_type).unwrap();
-                    buf.extend_from_slice(bytes);
-                }
-                WasmArgValue::Slice(offset_type, length_type, bytes) => {
-                    let offset_type = offset_type.into();
-                    let length_type = length_type.into();
-
-                    buf.write_u8(offset_type).unwrap();
-                    buf.write_u8(length_type).unwrap();
-                    buf.extend_from_slice(bytes);
-                }
-            }
-        }
+        write_func_args(args, buf);
     }
 
     fn write_address(&self, address: &Option<Address>, buf: &mut Vec<u8>) {
@@ -125,3 +138,43 @@ impl WireTxBuilder {
         buf.extend_from_slice(&bytes);
     }
 }
+
+/// Writes the func args. Shared with the deploy transaction (the constructor args)
+pub(crate) fn write_func_args(args: &[WasmArgValue], buf: &mut Vec<u8>) {
+    buf.write_u8(args.len() as u8).unwrap();
+
+    for arg in args {
+        match arg {
+            WasmArgValue::I32(v) => {
+                let arg_type = WasmArgType::I32.into();
+                buf.write_u8(arg_type).unwrap();
+                buf.write_u32::<BigEndian>(*v).unwrap();
+            }
+            WasmArgValue::I64(v) => {
+                let arg_type = WasmArgType::I64.into();
+                buf.write_u8(arg_type).unwrap();
+                buf.write_u64::<BigEndian>(*v).unwrap();
+            }
+            WasmArgValue::Fixed(offset_type, bytes) => {
+                let arg_type = WasmArgType::Fixed.into();
+                let offset_type = offset_type.into();
+
+                buf.write_u8(arg_type).unwrap();
+                buf.write_u32::<BigEndian>(bytes.len() as u32).unwrap();
+                buf.write_u8(offset_type).unwrap();
+                buf.extend_from_slice(bytes);
+            }
+            WasmArgValue::Slice(offset_type, length_type, bytes) => {
+                let arg_type = WasmArgType::Slice.into();
+                let offset_type = offset_type.into();
+                let length_type = length_type.into();
+
+                buf.write_u8(arg_type).unwrap();
+                buf.write_u32::<BigEndian>(bytes.len() as u32).unwrap();
+                buf.write_u8(offset_type).unwrap();
+                buf.write_u8(length_type).unwrap();
+                buf.extend_from_slice(bytes);
+            }
+        }
+    }
+}
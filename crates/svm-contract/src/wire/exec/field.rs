@@ -2,6 +2,9 @@ pub enum Field {
     Version,
     Contract,
     Sender,
+    GasLimit,
+    GasPrice,
+    Value,
     FuncNameLength,
     FuncName,
     ArgsCount,
@@ -16,6 +19,9 @@ impl std::fmt::Display for Field {
             Field::Version => "Version",
             Field::Contract => "Contract",
             Field::Sender => "Sender",
+            Field::GasLimit => "GasLimit",
+            Field::GasPrice => "GasPrice",
+            Field::Value => "Value",
             Field::FuncNameLength => "FuncNameLength",
             Field::FuncName => "FuncName",
             Field::ArgsCount => "ArgsCount",
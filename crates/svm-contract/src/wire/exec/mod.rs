@@ -18,12 +18,61 @@
 //!  | (1 byte)  |  (1 byte)    |  value  |                |
 //!  |___________|______________|_________|________________|
 //!
+//!  Each arg value is encoded according to its type:
+//!  * `I32`   - 4 bytes (Big-Endian)
+//!  * `I64`   - 8 bytes (Big-Endian)
+//!  * `Fixed` - byte-length (4 bytes) | offset int type (1 byte) | bytes
+//!  * `Slice` - byte-length (4 bytes) | offset int type (1 byte) | length int type (1 byte) | bytes
+//!
+//!  (an int type is encoded as the arg type `I32` or `I64`)
+//!
+//!
+//!      Exec Contract Wire Protocol Version 0.0.0.1
+//!  -------------------------------------------------------
+//!  Same as version `0.0.0.0` with the gas fields following the sender address:
+//!
+//!  |                                                     |
+//!  |                  sender address                     |
+//!  |                    (20 bytes)                       |
+//!  |_____________________________________________________|
+//!  |                          |                          |
+//!  |        gas limit         |        gas price         |
+//!  |        (8 bytes)         |        (8 bytes)         |
+//!  |__________________________|__________________________|
+//!  |             |                                       |
+//!  |  func name  |                                       |
+//!  |   length    |          func name (UTF-8)            |
+//!  |  (1 byte)   |                                       |
+//!  |_____________|_______________________________________|
+//!  |                                                     |
+//!  |                 func args (as in 0.0.0.0)           |
+//!  |_____________________________________________________|
+//!
+//!
+//!     Exec Contract Wire Protocol Version 0.0.0.2
+//!  -------------------------------------------------------
+//!  Same as version `0.0.0.1` with the value field following the gas fields:
+//!
+//!  |                          |                          |
+//!  |        gas limit         |        gas price         |
+//!  |        (8 bytes)         |        (8 bytes)         |
+//!  |__________________________|__________________________|
+//!  |                                                     |
+//!  |                       value                         |
+//!  |              (16 bytes, Big-Endian)                 |
+//!  |_____________________________________________________|
+//!
+//!  The value is the amount (in coins) the sender transfers to the contract along with the call.
+//!  Prior versions transfer no value.
+//!
 
 mod build;
 mod error;
 mod field;
 mod parse;
 
+pub(crate) use build::write_func_args;
 pub use build::WireTxBuilder;
 pub use error::TransactionBuildError;
+pub(crate) use parse::parse_func_args;
 pub use parse::parse_transaction;
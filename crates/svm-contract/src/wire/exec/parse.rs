@@ -3,7 +3,7 @@ use super::field::Field;
 
 use crate::transaction::Transaction;
 use crate::wasm::{WasmArgType, WasmArgValue, WasmIntType};
-use svm_common::Address;
+use svm_common::{Address, Balance};
 
 use byteorder::{BigEndian, ReadBytesExt};
 
@@ -24,16 +24,21 @@ macro_rules! ensure_enough_bytes {
 pub fn parse_transaction(bytes: &[u8]) -> Result<Transaction, TransactionBuildError> {
     let mut cursor = Cursor::new(bytes);
 
-    parse_version(&mut cursor)?;
+    let version = parse_version(&mut cursor)?;
 
     let contract = parse_address(&mut cursor, Field::Contract)?;
     let sender = parse_address(&mut cursor, Field::Sender)?;
+    let (gas_limit, gas_price) = parse_gas(&mut cursor, version)?;
+    let value = parse_value(&mut cursor, version)?;
     let func_name = parse_func_name(&mut cursor)?;
     let func_args = parse_func_args(&mut cursor)?;
 
     let tx = Transaction {
         contract,
         sender,
+        gas_limit,
+        gas_price,
+        value,
         func_name,
         func_args,
     };
@@ -47,7 +52,7 @@ fn parse_version(cursor: &mut Cursor<&[u8]>) -> Result<u32, TransactionBuildErro
     ensure_enough_bytes!(res, Field::Version);
 
     let version = res.unwrap();
-    if version != 0 {
+    if version > 2 {
         return Err(TransactionBuildError::UnsupportedProtoVersion(version));
     }
 
@@ -68,6 +73,34 @@ fn parse_address(
     Ok(addr)
 }
 
+/// Version `0` transactions have no gas fields. These are treated as not limiting the gas
+/// (the runtime gas limit still applies) and as free of charge.
+fn parse_gas(
+    cursor: &mut Cursor<&[u8]>,
+    version: u32,
+) -> Result<(u64, u64), TransactionBuildError> {
+    if version == 0 {
+        return Ok((std::u64::MAX, 0));
+    }
+
+    let gas_limit = read_u64(cursor, Field::GasLimit)?;
+    let gas_price = read_u64(cursor, Field::GasPrice)?;
+
+    Ok((gas_limit, gas_price))
+}
+
+/// Versions prior to `2` transfer no value along with the call
+fn parse_value(cursor: &mut Cursor<&[u8]>, version: u32) -> Result<Balance, TransactionBuildError> {
+    if version < 2 {
+        return Ok(Balance(0));
+    }
+
+    let res = cursor.read_u128::<BigEndian>();
+    ensure_enough_bytes!(res, Field::Value);
+
+    Ok(Balance(res.unwrap()))
+}
+
 fn parse_func_name(cursor: &mut Cursor<&[u8]>) -> Result<String, TransactionBuildError> {
     let res = cursor.read_u8();
 
@@ -91,7 +124,10 @@ fn parse_func_name(cursor: &mut Cursor<&[u8]>) -> Result<String, TransactionBuil
     Ok(name)
 }
 
-fn parse_func_args(cursor: &mut Cursor<&[u8]>) -> Result<Vec<WasmArgValue>, TransactionBuildError> {
+/// Parses the func args. Shared with the deploy transaction (the constructor args)
+pub(crate) fn parse_func_args(
+    cursor: &mut Cursor<&[u8]>,
+) -> Result<Vec<WasmArgValue>, TransactionBuildError> {
     let args_count = read_u8(cursor, Field::ArgsCount)?;
 
     let mut args = Vec::with_capacity(args_count as usize);
@@ -125,8 +161,13 @@ fn parse_func_arg(cursor: &mut Cursor<&[u8]>) -> Result<WasmArgValue, Transactio
             WasmArgValue::Fixed(offset_int_type, buf)
         }
         WasmArgType::Slice => {
-            // TODO: implement
-            unimplemented!()
+            let slice_byte_length = read_u32(cursor, Field::ArgLength)?;
+            let offset_int_type = parse_func_arg_int_type(cursor)?;
+            let length_int_type = parse_func_arg_int_type(cursor)?;
+
+            let buf = read_buffer(cursor, slice_byte_length, Field::ArgValue)?;
+
+            WasmArgValue::Slice(offset_int_type, length_int_type, buf)
         }
     };
 
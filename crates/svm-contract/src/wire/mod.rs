@@ -1,2 +1,3 @@
 pub mod deploy;
 pub mod exec;
+pub mod upgrade;
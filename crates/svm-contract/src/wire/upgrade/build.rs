@@ -0,0 +1,72 @@
+use byteorder::{BigEndian, WriteBytesExt};
+use svm_common::Address;
+
+/// A raw upgrade transaction builder. Used for testing
+pub struct WireUpgradeBuilder {
+    version: Option<u32>,
+    contract: Option<Address>,
+    sender: Option<Address>,
+    code: Option<Vec<u8>>,
+}
+
+#[allow(missing_docs)]
+impl WireUpgradeBuilder {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            version: None,
+            contract: None,
+            sender: None,
+            code: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn with_contract(mut self, contract: Address) -> Self {
+        self.contract = Some(contract);
+        self
+    }
+
+    pub fn with_sender(mut self, sender: Address) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    pub fn with_code(mut self, code: &[u8]) -> Self {
+        self.code = Some(code.to_vec());
+        self
+    }
+
+    pub fn build(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        self.write_version(&mut buf);
+        self.write_address(&self.contract, &mut buf);
+        self.write_address(&self.sender, &mut buf);
+        self.write_code(&mut buf);
+
+        buf
+    }
+
+    fn write_version(&self, buf: &mut Vec<u8>) {
+        let version = self.version.unwrap();
+        buf.write_u32::<BigEndian>(version).unwrap();
+    }
+
+    fn write_address(&self, address: &Option<Address>, buf: &mut Vec<u8>) {
+        let bytes = address.as_ref().unwrap().bytes();
+
+        buf.extend_from_slice(&bytes);
+    }
+
+    fn write_code(&self, buf: &mut Vec<u8>) {
+        let code = self.code.as_ref().unwrap();
+
+        buf.write_u64::<BigEndian>(code.len() as u64).unwrap();
+        buf.extend_from_slice(code.as_slice());
+    }
+}
@@ -0,0 +1,71 @@
+use super::field::Field;
+
+use crate::load_error::ContractLoadError;
+use crate::wire::deploy::ContractBuildError;
+
+use svm_common::Address;
+use svm_kv::KVError;
+
+#[allow(missing_docs)]
+pub enum ContractUpgradeError {
+    UnsupportedProtoVersion(u32),
+    NotEnoughBytes(Field),
+    InvalidWasm,
+    CodeTooLarge(u64),
+    ContractNotFound(Address),
+    NotAdmin(Address),
+    CodeUnchanged,
+    InvalidCode(ContractBuildError),
+    StoreFailed(KVError),
+    LoadFailed(ContractLoadError),
+}
+
+impl std::error::Error for ContractUpgradeError {
+    fn description(&self) -> &'static str {
+        match self {
+            ContractUpgradeError::UnsupportedProtoVersion(_) => "Unsupported protocol version",
+            ContractUpgradeError::NotEnoughBytes(_) => "Not enough bytes",
+            ContractUpgradeError::InvalidWasm => "Invalid wasm format",
+            ContractUpgradeError::CodeTooLarge(_) => "Code too large",
+            ContractUpgradeError::ContractNotFound(_) => "Contract not found",
+            ContractUpgradeError::NotAdmin(_) => "Sender is not an admin",
+            ContractUpgradeError::CodeUnchanged => "Code unchanged",
+            ContractUpgradeError::InvalidCode(_) => "Invalid code",
+            ContractUpgradeError::StoreFailed(_) => "Store failed",
+            ContractUpgradeError::LoadFailed(_) => "Load failed",
+        }
+    }
+}
+
+impl std::fmt::Display for ContractUpgradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            ContractUpgradeError::UnsupportedProtoVersion(ver) => {
+                format!("Unsupported protocol version: `{}`", ver)
+            }
+            ContractUpgradeError::NotEnoughBytes(field) => {
+                format!("Not enough bytes (field: {})", field)
+            }
+            ContractUpgradeError::InvalidWasm => String::from("Invalid wasm format"),
+            ContractUpgradeError::CodeTooLarge(len) => format!("Code too large: `{}` bytes", len),
+            ContractUpgradeError::ContractNotFound(addr) => {
+                format!("Contract not found: `{:?}`", addr)
+            }
+            ContractUpgradeError::NotAdmin(addr) => {
+                format!("Sender is not an admin: `{:?}`", addr)
+            }
+            ContractUpgradeError::CodeUnchanged => String::from("Code unchanged"),
+            ContractUpgradeError::InvalidCode(e) => format!("Invalid code: {:?}", e),
+            ContractUpgradeError::StoreFailed(e) => format!("Store failed: {:?}", e),
+            ContractUpgradeError::LoadFailed(e) => format!("Load failed: {:?}", e),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for ContractUpgradeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
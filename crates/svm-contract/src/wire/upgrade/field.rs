@@ -0,0 +1,21 @@
+pub enum Field {
+    Version,
+    Contract,
+    Sender,
+    CodeLength,
+    Code,
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let field = match self {
+            Field::Version => "Version",
+            Field::Contract => "Contract",
+            Field::Sender => "Sender",
+            Field::CodeLength => "CodeLength",
+            Field::Code => "Code",
+        };
+
+        write!(f, "{}", field)
+    }
+}
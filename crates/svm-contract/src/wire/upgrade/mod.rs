@@ -0,0 +1,26 @@
+//!     Upgrade Contract Wire Protocol Version 0.0.0.0
+//!  -------------------------------------------------------
+//!  |   proto    |                                        |
+//!  |  version   |          contract address              |
+//!  |  (4 bytes) |             (20 bytes)                 |
+//!  |____________|________________________________________|
+//!  |                                                     |
+//!  |                  sender address                     |
+//!  |                    (20 bytes)                       |
+//!  |_____________________________________________________|
+//!  |                |                                    |
+//!  |  code length   |              code                  |
+//!  |   (8 bytes)    |             (wasm)                 |
+//!  |________________|____________________________________|
+//!
+//!  The sender must be one of the contract admins (see `ContractEnv::validate_upgrade`).
+//!  The upgraded contract keeps its account address and its storage.
+
+mod build;
+mod error;
+mod field;
+mod parse;
+
+pub use build::WireUpgradeBuilder;
+pub use error::ContractUpgradeError;
+pub use parse::parse_upgrade;
@@ -0,0 +1,88 @@
+use super::error::ContractUpgradeError;
+use super::field::Field;
+
+use crate::upgrade::Upgrade;
+use crate::wire::deploy::WASM_PREAMBLE;
+use svm_common::Address;
+
+use byteorder::{BigEndian, ReadBytesExt};
+
+use std::io::{Cursor, Read};
+
+macro_rules! ensure_enough_bytes {
+    ($res: expr, $field: expr) => {{
+        if $res.is_err() {
+            return Err(ContractUpgradeError::NotEnoughBytes($field));
+        }
+    }};
+}
+
+/// Parsing a on-the-wire contract upgrade transaction given as raw bytes.
+/// Returns the parsed upgrade as an `Upgrade` struct.
+///
+/// Only the new code wasm preamble is validated here. The complete validation of the upgrade
+/// requires the upgraded contract (see `ContractEnv::validate_upgrade`).
+pub fn parse_upgrade(bytes: &[u8]) -> Result<Upgrade, ContractUpgradeError> {
+    let mut cursor = Cursor::new(bytes);
+
+    parse_version(&mut cursor)?;
+
+    let contract = parse_address(&mut cursor, Field::Contract)?;
+    let sender = parse_address(&mut cursor, Field::Sender)?;
+    let wasm = parse_code(&mut cursor)?;
+
+    if wasm.len() < WASM_PREAMBLE.len() || wasm[0..WASM_PREAMBLE.len()] != WASM_PREAMBLE {
+        return Err(ContractUpgradeError::InvalidWasm);
+    }
+
+    let upgrade = Upgrade {
+        contract,
+        sender,
+        wasm,
+    };
+
+    Ok(upgrade)
+}
+
+fn parse_version(cursor: &mut Cursor<&[u8]>) -> Result<u32, ContractUpgradeError> {
+    let res = cursor.read_u32::<BigEndian>();
+
+    ensure_enough_bytes!(res, Field::Version);
+
+    let version = res.unwrap();
+    if version != 0 {
+        return Err(ContractUpgradeError::UnsupportedProtoVersion(version));
+    }
+
+    Ok(version)
+}
+
+fn parse_address(
+    cursor: &mut Cursor<&[u8]>,
+    field: Field,
+) -> Result<Address, ContractUpgradeError> {
+    let mut bytes = vec![0; Address::len()];
+
+    let res = cursor.read_exact(&mut bytes);
+    ensure_enough_bytes!(res, field);
+
+    Ok(Address::from(&bytes[..]))
+}
+
+fn parse_code(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, ContractUpgradeError> {
+    let res = cursor.read_u64::<BigEndian>();
+    ensure_enough_bytes!(res, Field::CodeLength);
+
+    // the stored contract code length is 4 bytes long (see `WasmContractSerializer`)
+    let code_len = res.unwrap();
+    if code_len > u64::from(std::u32::MAX) {
+        return Err(ContractUpgradeError::CodeTooLarge(code_len));
+    }
+
+    let mut code = vec![0; code_len as usize];
+
+    let res = cursor.read_exact(&mut code);
+    ensure_enough_bytes!(res, Field::Code);
+
+    Ok(code)
+}
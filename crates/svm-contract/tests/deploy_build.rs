@@ -1,6 +1,8 @@
 use svm_common::Address;
 
 use svm_contract::build::WireContractBuilder;
+use svm_contract::types::{CodeHash, Dependency, MAX_DEPS};
+use svm_contract::wasm::{ContractCtor, WasmArgValue, WasmIntType};
 use svm_contract::{env::ContractEnv, memory::MemoryEnv};
 
 #[test]
@@ -9,12 +11,320 @@ fn build_contract() {
         .with_version(0)
         .with_name("Contract #1")
         .with_author(Address::from(0x10_20_30_40))
-        .with_code(&[0xAA, 0xBB, 0xCC, 0xDD])
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
         .build();
 
     let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
 
     assert_eq!("Contract #1", contract.name);
     assert_eq!(Address::from(0x10_20_30_40), contract.author);
-    assert_eq!([0xAA, 0xBB, 0xCC, 0xDD], contract.wasm.as_ref());
+    assert_eq!(0, contract.pages_count);
+    assert_eq!(
+        [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00],
+        contract.wasm.as_ref()
+    );
+}
+
+#[test]
+fn build_contract_invalid_wasm() {
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0xAA, 0xBB, 0xCC, 0xDD])
+        .build();
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert_eq!("Invalid wasm format", format!("{:?}", res.unwrap_err()));
+}
+
+#[test]
+fn build_contract_code_too_large() {
+    let mut bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    // overriding the code length (the 8 bytes preceding the code) with `u32::MAX + 1`
+    let len = bytes.len();
+    bytes[len - 16..len - 8].copy_from_slice(&(1u64 << 32).to_be_bytes());
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert_eq!(
+        "Code too large: `4294967296` bytes",
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn build_contract_with_admins() {
+    let admins = [Address::from(0x50_60_70_80), Address::from(0x60_70_80_90)];
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_admins(&admins)
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    assert_eq!(admins.to_vec(), contract.admins);
+}
+
+#[test]
+fn build_contract_with_duplicate_admin() {
+    let admin = Address::from(0x50_60_70_80);
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_admins(&[admin.clone(), admin])
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert!(format!("{:?}", res.unwrap_err()).starts_with("Duplicate admin"));
+}
+
+#[test]
+fn build_contract_with_deps() {
+    let deps = [
+        Dependency::Address(Address::from(0x50_60_70_80)),
+        Dependency::CodeHash(CodeHash([0xAB; 32])),
+    ];
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_deps(&deps)
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    assert_eq!(deps.to_vec(), contract.deps);
+}
+
+#[test]
+fn build_contract_with_too_many_deps() {
+    let deps = (0..=MAX_DEPS)
+        .map(|i| Dependency::CodeHash(CodeHash([i as u8; 32])))
+        .collect::<Vec<_>>();
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_deps(&deps)
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert!(format!("{:?}", res.unwrap_err()).starts_with("Too many dependencies"));
+}
+
+#[test]
+fn build_contract_with_pages_count() {
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_pages_count(5)
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    assert_eq!(5, contract.pages_count);
+}
+
+#[test]
+fn build_contract_with_ctor() {
+    let args = vec![
+        WasmArgValue::I64(10),
+        WasmArgValue::Fixed(WasmIntType::I32, vec![1, 2, 3]),
+    ];
+
+    let bytes = WireContractBuilder::new()
+        .with_version(1)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .with_ctor("init", &args)
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    assert_eq!(
+        Some(ContractCtor {
+            func_name: "init".to_string(),
+            func_args: args
+        }),
+        contract.ctor
+    );
+}
+
+#[test]
+fn build_contract_without_ctor() {
+    for version in 0..=1 {
+        let bytes = WireContractBuilder::new()
+            .with_version(version)
+            .with_name("Contract #1")
+            .with_author(Address::from(0x10_20_30_40))
+            .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+            .build();
+
+        let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+        assert_eq!(None, contract.ctor);
+    }
+}
+
+#[test]
+fn build_contract_truncated_ctor_args() {
+    let mut bytes = WireContractBuilder::new()
+        .with_version(1)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .with_ctor("init", &[WasmArgValue::I32(10)])
+        .build();
+
+    // dropping the arg last byte
+    bytes.pop();
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert_eq!(
+        "Not enough bytes (field: CtorArgs)",
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn build_contract_with_nonce_and_salt() {
+    let bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .with_ctor("init", &[WasmArgValue::I32(10)])
+        .with_nonce(7)
+        .with_salt(&[0xAB; 32])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    assert_eq!("init", contract.ctor.unwrap().func_name);
+    assert_eq!(7, contract.nonce);
+    assert_eq!(Some([0xAB; 32]), contract.salt);
+}
+
+#[test]
+fn build_contract_without_nonce_and_salt() {
+    for version in 0..=1 {
+        let bytes = WireContractBuilder::new()
+            .with_version(version)
+            .with_name("Contract #1")
+            .with_author(Address::from(0x10_20_30_40))
+            .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+            .with_nonce(7)
+            .with_salt(&[0xAB; 32])
+            .build();
+
+        // versions prior to `2` carry no nonce nor salt
+        let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+        assert_eq!(0, contract.nonce);
+        assert_eq!(None, contract.salt);
+    }
+}
+
+#[test]
+fn build_contract_invalid_salt_flag() {
+    let mut bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    // overriding the salt flag (the last byte)
+    *bytes.last_mut().unwrap() = 2;
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert_eq!("Invalid salt flag: `2`", format!("{:?}", res.unwrap_err()));
+}
+
+#[test]
+fn build_contract_with_gas() {
+    let bytes = WireContractBuilder::new()
+        .with_version(3)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .with_ctor("init", &[WasmArgValue::I32(10)])
+        .with_gas_limit(100_000)
+        .with_gas_price(2)
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    assert_eq!("init", contract.ctor.unwrap().func_name);
+    assert_eq!(100_000, contract.gas_limit);
+    assert_eq!(2, contract.gas_price);
+}
+
+#[test]
+fn build_contract_without_gas() {
+    for version in 0..=2 {
+        let bytes = WireContractBuilder::new()
+            .with_version(version)
+            .with_name("Contract #1")
+            .with_author(Address::from(0x10_20_30_40))
+            .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+            .with_gas_limit(100_000)
+            .with_gas_price(2)
+            .build();
+
+        // versions prior to `3` carry no gas fields
+        let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+        assert_eq!(std::u64::MAX, contract.gas_limit);
+        assert_eq!(0, contract.gas_price);
+    }
+}
+
+#[test]
+fn build_contract_missing_gas_price() {
+    let mut bytes = WireContractBuilder::new()
+        .with_version(3)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .with_gas_limit(100_000)
+        .with_gas_price(2)
+        .build();
+
+    // truncating the `gas_price` field (the last 8 bytes)
+    let len = bytes.len();
+    bytes.truncate(len - 8);
+
+    let res = <MemoryEnv as ContractEnv>::build_contract(&bytes);
+
+    assert_eq!(
+        "Not enough bytes (field: GasPrice)",
+        format!("{:?}", res.unwrap_err())
+    );
 }
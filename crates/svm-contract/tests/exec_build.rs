@@ -1,6 +1,6 @@
-use svm_common::Address;
+use svm_common::{Address, Balance};
 
-use svm_contract::wasm::WasmArgValue;
+use svm_contract::wasm::{WasmArgValue, WasmIntType};
 use svm_contract::{
     build::WireTxBuilder, env::ContractEnv, memory::MemoryEnv, transaction::Transaction,
 };
@@ -20,9 +20,161 @@ fn build_transaction() {
     let expected = Transaction {
         contract: Address::from(0x10_20_30_40),
         sender: Address::from(0x50_60_70_80),
+        gas_limit: std::u64::MAX,
+        gas_price: 0,
+        value: Balance(0),
         func_name: "run".to_string(),
         func_args: vec![WasmArgValue::I32(10), WasmArgValue::I64(20)],
     };
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn build_transaction_with_gas() {
+    let bytes = WireTxBuilder::new()
+        .with_version(1)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_gas_limit(100_000)
+        .with_gas_price(2)
+        .with_func_name("run")
+        .with_func_args(&vec![WasmArgValue::I32(10)])
+        .build();
+
+    let actual = <MemoryEnv as ContractEnv>::build_transaction(&bytes).unwrap();
+
+    let expected = Transaction {
+        contract: Address::from(0x10_20_30_40),
+        sender: Address::from(0x50_60_70_80),
+        gas_limit: 100_000,
+        gas_price: 2,
+        value: Balance(0),
+        func_name: "run".to_string(),
+        func_args: vec![WasmArgValue::I32(10)],
+    };
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn build_transaction_missing_gas_price() {
+    let mut bytes = WireTxBuilder::new()
+        .with_version(1)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_gas_limit(100_000)
+        .with_gas_price(2)
+        .with_func_name("run")
+        .with_func_args(&vec![])
+        .build();
+
+    // truncating right after the `gas_limit` field
+    bytes.truncate(4 + 20 + 20 + 8 + 4);
+
+    let res = <MemoryEnv as ContractEnv>::build_transaction(&bytes);
+
+    assert_eq!("Not enough bytes", format!("{:?}", res.unwrap_err()));
+}
+
+#[test]
+fn build_transaction_with_value() {
+    let bytes = WireTxBuilder::new()
+        .with_version(2)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_gas_limit(100_000)
+        .with_gas_price(2)
+        .with_value(Balance(1_000_000_000_000_000_000_000))
+        .with_func_name("run")
+        .with_func_args(&vec![WasmArgValue::I32(10)])
+        .build();
+
+    let actual = <MemoryEnv as ContractEnv>::build_transaction(&bytes).unwrap();
+
+    let expected = Transaction {
+        contract: Address::from(0x10_20_30_40),
+        sender: Address::from(0x50_60_70_80),
+        gas_limit: 100_000,
+        gas_price: 2,
+        value: Balance(1_000_000_000_000_000_000_000),
+        func_name: "run".to_string(),
+        func_args: vec![WasmArgValue::I32(10)],
+    };
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn build_transaction_missing_value() {
+    let mut bytes = WireTxBuilder::new()
+        .with_version(2)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_gas_limit(100_000)
+        .with_gas_price(2)
+        .with_value(Balance(10))
+        .with_func_name("run")
+        .with_func_args(&vec![])
+        .build();
+
+    // truncating in the middle of the `value` field
+    bytes.truncate(4 + 20 + 20 + 8 + 8 + 8);
+
+    let res = <MemoryEnv as ContractEnv>::build_transaction(&bytes);
+
+    assert_eq!("Not enough bytes", format!("{:?}", res.unwrap_err()));
+}
+
+#[test]
+fn build_transaction_with_fixed_and_slice_args() {
+    let args = vec![
+        WasmArgValue::Fixed(WasmIntType::I32, vec![10, 20, 30]),
+        WasmArgValue::Slice(WasmIntType::I32, WasmIntType::I64, b"Hello World".to_vec()),
+        WasmArgValue::I32(10),
+    ];
+
+    let bytes = WireTxBuilder::new()
+        .with_version(0)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_func_name("run")
+        .with_func_args(&args)
+        .build();
+
+    let actual = <MemoryEnv as ContractEnv>::build_transaction(&bytes).unwrap();
+
+    let expected = Transaction {
+        contract: Address::from(0x10_20_30_40),
+        sender: Address::from(0x50_60_70_80),
+        gas_limit: std::u64::MAX,
+        gas_price: 0,
+        value: Balance(0),
+        func_name: "run".to_string(),
+        func_args: args,
+    };
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn build_transaction_truncated_slice_arg() {
+    let mut bytes = WireTxBuilder::new()
+        .with_version(0)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_func_name("run")
+        .with_func_args(&vec![WasmArgValue::Slice(
+            WasmIntType::I32,
+            WasmIntType::I32,
+            vec![1, 2, 3],
+        )])
+        .build();
+
+    // dropping the slice last byte
+    bytes.pop();
+
+    let res = <MemoryEnv as ContractEnv>::build_transaction(&bytes);
+
+    assert_eq!("Not enough bytes", format!("{:?}", res.unwrap_err()));
+}
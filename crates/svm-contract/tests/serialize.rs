@@ -0,0 +1,155 @@
+use svm_common::Address;
+
+use svm_contract::{
+    error::ContractDeserializeError,
+    traits::{ContractDeserializer, ContractSerializer},
+    types::{CodeHash, Dependency},
+    wasm::{
+        migrate_json_contract, Contract, ContractCtor, WasmArgValue, WasmContractDeserializer,
+        WasmContractJsonSerializer, WasmContractSerializer, CONTRACT_SERIALIZE_VERSION,
+    },
+};
+
+fn contract() -> Contract {
+    Contract {
+        wasm: vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00],
+        name: "Contract #1".to_string(),
+        author: Address::from(0x10_20_30_40),
+        admins: vec![Address::from(0x50_60_70_80)],
+        deps: vec![
+            Dependency::Address(Address::from(0x11_22_33_44)),
+            Dependency::CodeHash(CodeHash([0xAB; 32])),
+        ],
+        pages_count: 10,
+        ctor: None,
+        nonce: 0,
+        salt: None,
+        gas_limit: 0,
+        gas_price: 0,
+    }
+}
+
+#[test]
+fn binary_serialize_roundtrip() {
+    let bytes = WasmContractSerializer::serialize(&contract());
+
+    assert_eq!(CONTRACT_SERIALIZE_VERSION, bytes[0]);
+    assert_eq!(Ok(contract()), WasmContractDeserializer::deserialize(bytes));
+}
+
+#[test]
+fn binary_serialize_skips_ctor() {
+    let with_ctor = Contract {
+        ctor: Some(ContractCtor {
+            func_name: "init".to_string(),
+            func_args: vec![WasmArgValue::I32(10)],
+        }),
+        ..contract()
+    };
+
+    let bytes = WasmContractSerializer::serialize(&with_ctor);
+
+    // the constructor runs only at deploy time
+    assert_eq!(Ok(contract()), WasmContractDeserializer::deserialize(bytes));
+}
+
+#[test]
+fn binary_serialize_skips_nonce_and_salt() {
+    let with_salt = Contract {
+        nonce: 7,
+        salt: Some([0xAB; 32]),
+        ..contract()
+    };
+
+    let bytes = WasmContractSerializer::serialize(&with_salt);
+
+    // the nonce and salt are used only for computing the contract address
+    assert_eq!(Ok(contract()), WasmContractDeserializer::deserialize(bytes));
+}
+
+#[test]
+fn binary_serialize_skips_gas() {
+    let with_gas = Contract {
+        gas_limit: 100_000,
+        gas_price: 2,
+        ..contract()
+    };
+
+    let bytes = WasmContractSerializer::serialize(&with_gas);
+
+    // the gas fields apply only to the constructor execution
+    assert_eq!(Ok(contract()), WasmContractDeserializer::deserialize(bytes));
+}
+
+#[test]
+fn binary_serialize_is_more_compact_than_json() {
+    let binary = WasmContractSerializer::serialize(&contract());
+    let json = WasmContractJsonSerializer::serialize(&contract());
+
+    assert!(binary.len() * 2 < json.len());
+}
+
+#[test]
+fn binary_deserialize_json_contract() {
+    let json = WasmContractJsonSerializer::serialize(&contract());
+
+    assert_eq!(Ok(contract()), WasmContractDeserializer::deserialize(json));
+}
+
+#[test]
+fn binary_deserialize_errors() {
+    let bytes = WasmContractSerializer::serialize(&contract());
+
+    let truncated = bytes[..bytes.len() - 1].to_vec();
+    assert_eq!(
+        Err(ContractDeserializeError::NotEnoughBytes("code")),
+        WasmContractDeserializer::deserialize(truncated)
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        Err(ContractDeserializeError::TrailingBytes(1)),
+        WasmContractDeserializer::deserialize(trailing)
+    );
+
+    let mut unsupported = bytes;
+    unsupported[0] = 0xFF;
+    assert_eq!(
+        Err(ContractDeserializeError::UnsupportedVersion(0xFF)),
+        WasmContractDeserializer::deserialize(unsupported)
+    );
+
+    assert_eq!(
+        Err(ContractDeserializeError::EmptyInput),
+        WasmContractDeserializer::deserialize(Vec::new())
+    );
+}
+
+#[test]
+fn migrate_json_contract_to_binary() {
+    let json = WasmContractJsonSerializer::serialize(&contract());
+    let binary = migrate_json_contract(&json).unwrap();
+
+    assert_eq!(WasmContractSerializer::serialize(&contract()), binary);
+
+    // migrating is idempotent
+    assert_eq!(binary, migrate_json_contract(&binary).unwrap());
+}
+
+#[test]
+fn binary_deserialize_version_1_contract() {
+    let mut bytes = WasmContractSerializer::serialize(&contract());
+
+    // a version `1` contract has no `#pages` field (it's right before the code length)
+    let code_len_pos = bytes.len() - contract().wasm.len() - 4;
+    bytes.drain(code_len_pos - 2..code_len_pos);
+    bytes[0] = 1;
+
+    let expected = Contract {
+        pages_count: 0,
+        ..contract()
+    };
+
+    assert_eq!(Ok(expected), WasmContractDeserializer::deserialize(bytes));
+}
@@ -3,8 +3,12 @@ use svm_common::Address;
 use svm_contract::{
     build::WireContractBuilder,
     env::ContractEnv,
+    error::{ContractDeserializeError, ContractLoadError},
     memory::{MemContractStore, MemoryEnv},
-    traits::ContractStore,
+    traits::{ContractDeserializer, ContractStore},
+    types::Dependency,
+    upgrade::Upgrade,
+    wasm::{Contract, WasmContractSerializer},
 };
 
 #[test]
@@ -13,7 +17,8 @@ fn store_contract() {
         .with_version(0)
         .with_name("Contract #1")
         .with_author(Address::from(0x10_20_30_40))
-        .with_code(&[0xAA, 0xBB, 0xCC, 0xDD])
+        .with_admins(&[Address::from(0x50_60_70_80)])
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
         .build();
 
     let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
@@ -22,10 +27,300 @@ fn store_contract() {
     let store = MemContractStore::new();
     let mut env = MemoryEnv::new(store);
 
-    env.store_contract(&contract, &addr);
+    env.store_contract(&contract, &addr).unwrap();
 
     let store = env.get_store();
 
-    let stored = store.load(&addr).unwrap();
-    assert_eq!(stored, contract);
+    // the constructor gas fields aren't persisted
+    let expected = Contract {
+        gas_limit: 0,
+        gas_price: 0,
+        ..contract
+    };
+
+    let stored = store.load(&addr).unwrap().unwrap();
+    assert_eq!(stored, expected);
+    assert_eq!(vec![Address::from(0x50_60_70_80)], stored.admins);
+}
+
+#[test]
+fn load_corrupted_contract() {
+    struct CorruptedDeserializer;
+
+    impl ContractDeserializer for CorruptedDeserializer {
+        fn deserialize(_bytes: Vec<u8>) -> Result<Contract, ContractDeserializeError> {
+            Err(ContractDeserializeError::TrailingBytes(1))
+        }
+    }
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+    let addr = <MemoryEnv as ContractEnv>::compute_address(&contract);
+    let hash = <MemoryEnv as ContractEnv>::compute_code_hash(&contract);
+
+    let mut store = MemContractStore::<WasmContractSerializer, CorruptedDeserializer>::new();
+    store.store(&contract, &addr, hash).unwrap();
+
+    let expected = ContractLoadError::Corrupted(hash, ContractDeserializeError::TrailingBytes(1));
+    assert_eq!(Err(expected), store.load(&addr));
+}
+
+#[test]
+fn store_contracts_having_the_same_code() {
+    macro_rules! build_contract {
+        ($admin: expr, $nonce: expr) => {{
+            let bytes = WireContractBuilder::new()
+                .with_version(2)
+                .with_name("Contract #1")
+                .with_author(Address::from(0x10_20_30_40))
+                .with_admins(&[Address::from($admin)])
+                .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+                .with_nonce($nonce)
+                .build();
+
+            <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap()
+        }};
+    }
+
+    let contract1 = build_contract!(0x50_60_70_80, 0);
+    let contract2 = build_contract!(0x60_70_80_90, 1);
+
+    let addr1 = <MemoryEnv as ContractEnv>::compute_address(&contract1);
+    let addr2 = <MemoryEnv as ContractEnv>::compute_address(&contract2);
+    let hash = <MemoryEnv as ContractEnv>::compute_code_hash(&contract1);
+    assert_eq!(
+        hash,
+        <MemoryEnv as ContractEnv>::compute_code_hash(&contract2)
+    );
+
+    let mut env = MemoryEnv::new(MemContractStore::new());
+    env.store_contract(&contract1, &addr1).unwrap();
+    env.store_contract(&contract2, &addr2).unwrap();
+
+    let store = env.get_store();
+
+    // each contract keeps its own admins (only the code is shared)
+    let stored1 = store.load(&addr1).unwrap().unwrap();
+    let stored2 = store.load(&addr2).unwrap().unwrap();
+    assert_eq!(vec![Address::from(0x50_60_70_80)], stored1.admins);
+    assert_eq!(vec![Address::from(0x60_70_80_90)], stored2.admins);
+
+    assert_eq!(Ok(Some(contract1.wasm)), store.load_code(&hash));
+}
+
+#[test]
+fn store_contract_address_taken() {
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+    let addr = <MemoryEnv as ContractEnv>::compute_address(&contract);
+
+    let mut env = MemoryEnv::new(MemContractStore::new());
+    env.store_contract(&contract, &addr).unwrap();
+
+    // deploying the same contract again (the previous contract is kept)
+    let res = env.store_contract(&contract, &addr);
+    assert!(format!("{:?}", res.unwrap_err()).ends_with("is already taken"));
+}
+
+#[test]
+fn compute_address_with_nonce_and_salt() {
+    macro_rules! compute_address {
+        ($nonce: expr, $salt: expr) => {{
+            let mut builder = WireContractBuilder::new()
+                .with_version(2)
+                .with_name("Contract #1")
+                .with_author(Address::from(0x10_20_30_40))
+                .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+                .with_nonce($nonce);
+
+            let salt: Option<[u8; 32]> = $salt;
+            if let Some(ref salt) = salt {
+                builder = builder.with_salt(salt);
+            }
+
+            let contract = <MemoryEnv as ContractEnv>::build_contract(&builder.build()).unwrap();
+            <MemoryEnv as ContractEnv>::compute_address(&contract)
+        }};
+    }
+
+    let addr = compute_address!(0, None);
+
+    // the address is deterministic
+    assert_eq!(addr, compute_address!(0, None));
+
+    // the same author deploying the same code gets a new address per nonce and salt
+    assert_ne!(addr, compute_address!(1, None));
+    assert_ne!(addr, compute_address!(0, Some([0xAB; 32])));
+    assert_ne!(
+        compute_address!(0, Some([0xAB; 32])),
+        compute_address!(0, Some([0xCD; 32]))
+    );
+}
+
+#[test]
+fn validate_contract_deps() {
+    let lib_bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Library")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let lib = <MemoryEnv as ContractEnv>::build_contract(&lib_bytes).unwrap();
+    let lib_addr = <MemoryEnv as ContractEnv>::compute_address(&lib);
+    let lib_hash = <MemoryEnv as ContractEnv>::compute_code_hash(&lib);
+
+    let mut env = MemoryEnv::new(MemContractStore::new());
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_deps(&[
+            Dependency::Address(lib_addr.clone()),
+            Dependency::CodeHash(lib_hash),
+        ])
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+
+    // the library hasn't been deployed yet
+    let res = env.validate_deps(&contract);
+    assert!(format!("{:?}", res.unwrap_err()).starts_with("Dependency not found"));
+
+    env.store_contract(&lib, &lib_addr).unwrap();
+    assert!(env.validate_deps(&contract).is_ok());
+}
+
+#[test]
+fn upgrade_contract() {
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_admins(&[Address::from(0x50_60_70_80)])
+        .with_pages_count(5)
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <MemoryEnv as ContractEnv>::build_contract(&bytes).unwrap();
+    let addr = <MemoryEnv as ContractEnv>::compute_address(&contract);
+    let hash = <MemoryEnv as ContractEnv>::compute_code_hash(&contract);
+
+    let mut env = MemoryEnv::new(MemContractStore::new());
+    env.store_contract(&contract, &addr).unwrap();
+
+    macro_rules! upgrade {
+        ($sender: expr, $code: expr) => {{
+            Upgrade {
+                contract: addr.clone(),
+                sender: Address::from($sender),
+                wasm: $code.to_vec(),
+            }
+        }};
+    }
+
+    let new_code = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00, 0xFF];
+
+    // only an admin may upgrade the contract
+    let res = env.validate_upgrade(&upgrade!(0x10_20_30_40, new_code));
+    assert!(format!("{:?}", res.unwrap_err()).starts_with("Sender is not an admin"));
+
+    // the code must change
+    let res = env.validate_upgrade(&upgrade!(0x50_60_70_80, contract.wasm));
+    assert_eq!("Code unchanged", format!("{:?}", res.unwrap_err()));
+
+    let upgraded = env
+        .validate_upgrade(&upgrade!(0x50_60_70_80, new_code))
+        .unwrap();
+    let new_hash = env.store_upgrade(&upgraded, &addr).unwrap();
+    assert_ne!(hash, new_hash);
+
+    let store = env.get_store();
+
+    // the address now refers to the new code (everything else is kept)
+    let stored = store.load(&addr).unwrap().unwrap();
+    assert_eq!(new_code.to_vec(), stored.wasm);
+    assert_eq!(contract.name, stored.name);
+    assert_eq!(contract.admins, stored.admins);
+    assert_eq!(5, stored.pages_count);
+
+    assert_eq!(Ok(Some(new_hash)), store.load_code_hash(&addr));
+    assert_eq!(Ok(vec![hash]), store.load_code_history(&addr));
+
+    // the prior code is still available by its code-hash
+    assert_eq!(Ok(Some(contract.wasm)), store.load_code(&hash));
+}
+
+#[test]
+fn upgrade_non_existing_contract() {
+    let env = MemoryEnv::new(MemContractStore::new());
+
+    let upgrade = Upgrade {
+        contract: Address::from(0x10_20_30_40),
+        sender: Address::from(0x50_60_70_80),
+        wasm: vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00],
+    };
+
+    let res = env.validate_upgrade(&upgrade);
+    assert!(format!("{:?}", res.unwrap_err()).starts_with("Contract not found"));
+    assert!(env
+        .get_store()
+        .load_code_history(&upgrade.contract)
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(feature = "default-leveldb")]
+#[test]
+fn leveldb_store_contract() {
+    use svm_contract::leveldb::{LDBContractStore, LDBEnv};
+    use svm_contract::wasm::{WasmContractDeserializer as D, WasmContractSerializer as S};
+
+    let bytes = WireContractBuilder::new()
+        .with_version(0)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let contract = <LDBEnv as ContractEnv>::build_contract(&bytes).unwrap();
+    let addr = <LDBEnv as ContractEnv>::compute_address(&contract);
+    let hash = <LDBEnv as ContractEnv>::compute_code_hash(&contract);
+
+    let path = std::path::Path::new("leveldb-contract-store-tests");
+
+    // the contract address is already taken when the database is left by a prior run
+    let _ = std::fs::remove_dir_all(path);
+
+    let mut env = LDBEnv::new(LDBContractStore::<S, D>::new(path).unwrap());
+    env.store_contract(&contract, &addr).unwrap();
+    drop(env);
+
+    // reopening the database
+    let env = LDBEnv::new(LDBContractStore::<S, D>::new(path).unwrap());
+    let store = env.get_store();
+
+    // the constructor gas fields aren't persisted
+    let expected = Contract {
+        gas_limit: 0,
+        gas_price: 0,
+        ..contract
+    };
+
+    assert_eq!(Ok(Some(expected)), store.load(&addr));
+    assert!(store.load_code(&hash).unwrap().is_some());
 }
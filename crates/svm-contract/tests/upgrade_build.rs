@@ -0,0 +1,76 @@
+use svm_common::Address;
+
+use svm_contract::{
+    build::WireUpgradeBuilder, env::ContractEnv, memory::MemoryEnv, upgrade::Upgrade,
+};
+
+#[test]
+fn build_upgrade() {
+    let bytes = WireUpgradeBuilder::new()
+        .with_version(0)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let actual = <MemoryEnv as ContractEnv>::build_upgrade(&bytes).unwrap();
+
+    let expected = Upgrade {
+        contract: Address::from(0x10_20_30_40),
+        sender: Address::from(0x50_60_70_80),
+        wasm: vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00],
+    };
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn build_upgrade_unsupported_version() {
+    let bytes = WireUpgradeBuilder::new()
+        .with_version(1)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    let res = <MemoryEnv as ContractEnv>::build_upgrade(&bytes);
+
+    assert_eq!(
+        "Unsupported protocol version: `1`",
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn build_upgrade_truncated_code() {
+    let mut bytes = WireUpgradeBuilder::new()
+        .with_version(0)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_code(&[0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00])
+        .build();
+
+    // dropping the code last byte
+    bytes.pop();
+
+    let res = <MemoryEnv as ContractEnv>::build_upgrade(&bytes);
+
+    assert_eq!(
+        "Not enough bytes (field: Code)",
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn build_upgrade_invalid_wasm() {
+    let bytes = WireUpgradeBuilder::new()
+        .with_version(0)
+        .with_contract(Address::from(0x10_20_30_40))
+        .with_sender(Address::from(0x50_60_70_80))
+        .with_code(&[0xAA, 0xBB, 0xCC, 0xDD])
+        .build();
+
+    let res = <MemoryEnv as ContractEnv>::build_upgrade(&bytes);
+
+    assert_eq!("Invalid wasm format", format!("{:?}", res.unwrap_err()));
+}
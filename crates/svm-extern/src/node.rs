@@ -13,6 +13,41 @@ macro_rules! include_extern_node_vmcalls {
             /// * `reg_idx`  - The register index that holds the account address we want to set its balance
             /// * `balance`  - The new balance
             fn set_balance_from_reg(reg_bits: i32, reg_idx: i32, balance: i64);
+
+            /// * `reg_bits` - The #bits of `reg_idx` (at least 128)
+            /// * `reg_idx`  - The register index to copy the value sent with the transaction into (16 bytes, Little-Endian)
+            fn get_value(reg_bits: i32, reg_idx: i32);
+
+            /// * `reg_bits` - The #bits of `reg_idx` (at least 128)
+            /// * `reg_idx`  - The register index to copy the running contract balance into (16 bytes, Little-Endian)
+            fn get_self_balance(reg_bits: i32, reg_idx: i32);
+
+            /// * `reg_idx` - The `160` bits register index to copy the caller address into (the sender or the calling contract)
+            fn get_sender_to_reg(reg_idx: i32);
+
+            /// * `mem_idx` - The memory index to copy the caller address into
+            /// * `mem_ptr` - The memory address to copy the caller address into (`20` bytes)
+            fn get_sender_to_mem(mem_idx: i32, mem_ptr: i32);
+
+            /// * `reg_idx` - The `160` bits register index to copy the running contract address into
+            fn get_self_address_to_reg(reg_idx: i32);
+
+            /// * `mem_idx` - The memory index to copy the running contract address into
+            /// * `mem_ptr` - The memory address to copy the running contract address into (`20` bytes)
+            fn get_self_address_to_mem(mem_idx: i32, mem_ptr: i32);
+
+            /// Returns the id of the layer the transaction is executed in
+            fn get_layer_id() -> i64;
+
+            /// Returns the timestamp of the layer the transaction is executed in
+            fn get_layer_timestamp() -> i64;
+
+            /// * `reg_idx` - The `256` bits register index to copy the layer randomness beacon into
+            fn get_beacon_to_reg(reg_idx: i32);
+
+            /// * `mem_idx` - The memory index to copy the layer randomness beacon into
+            /// * `mem_ptr` - The memory address to copy the layer randomness beacon into (`32` bytes)
+            fn get_beacon_to_mem(mem_idx: i32, mem_ptr: i32);
         }
     };
 }
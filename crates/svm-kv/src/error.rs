@@ -0,0 +1,40 @@
+/// A key-value store operation error
+#[derive(PartialEq, Clone)]
+pub enum KVError {
+    /// Opening the database failed (the underlying database error message)
+    OpenFailed(String),
+
+    /// Reading the value of a key failed (the underlying database error message)
+    ReadFailed(String),
+
+    /// Writing a batch of changes failed (the underlying database error message)
+    WriteFailed(String),
+}
+
+impl std::error::Error for KVError {
+    fn description(&self) -> &'static str {
+        match self {
+            KVError::OpenFailed(_) => "Open failed",
+            KVError::ReadFailed(_) => "Read failed",
+            KVError::WriteFailed(_) => "Write failed",
+        }
+    }
+}
+
+impl std::fmt::Display for KVError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            KVError::OpenFailed(e) => format!("Key-value store open failed: {}", e),
+            KVError::ReadFailed(e) => format!("Key-value store read failed: {}", e),
+            KVError::WriteFailed(e) => format!("Key-value store write failed: {}", e),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for KVError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
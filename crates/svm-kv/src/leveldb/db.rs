@@ -1,8 +1,9 @@
 use crate::leveldb::LDBKey;
-use crate::traits::KVStore;
+use crate::traits::{KVChange, KVEntries, KVStore};
+use crate::KVError;
 
 use db_key::Key;
-use log::trace;
+use log::{error, info, trace};
 use std::path::Path;
 
 use leveldb::database::{
@@ -10,6 +11,7 @@ use leveldb::database::{
     kv::KV,
     Database,
 };
+use leveldb::iterator::{Iterable, LevelDBIterator};
 use leveldb::options::{Options, ReadOptions, WriteOptions};
 
 /// An implementation of `KVStore` trait against `leveldb`.
@@ -18,57 +20,81 @@ pub struct LDBStore {
 }
 
 impl LDBStore {
-    pub fn new(path: &Path) -> Self {
+    /// New `LDBStore` under the given `path` (the database is created if missing).
+    /// Returns an error when the database can't be opened.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, KVError> {
+        info!("opening leveldb. (path = \"{}\")", path.as_ref().display());
+
         let mut opts = Options::new();
         opts.create_if_missing = true;
 
-        let db = match Database::open(path, opts) {
-            Ok(db) => db,
-            Err(e) => panic!("failed to open database: {:?}", e),
-        };
+        let db = Database::open(path.as_ref(), opts).map_err(|e| {
+            error!("failed to open database: {:?}", e);
+            KVError::OpenFailed(format!("{:?}", e))
+        })?;
 
-        Self { db }
+        Ok(Self { db })
     }
 }
 
 impl KVStore for LDBStore {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, KVError> {
         let ldb_key = LDBKey(key.to_vec());
 
         let read_opts = ReadOptions::new();
-        let res = self.db.get(read_opts, ldb_key);
-        match res {
-            Ok(data) => data,
-            Err(_) => panic!("failed reading data"),
-        }
+
+        self.db
+            .get(read_opts, ldb_key)
+            .map_err(|e| KVError::ReadFailed(format!("{:?}", e)))
     }
 
-    fn store(&mut self, changes: &[(&[u8], &[u8])]) {
+    fn write_batch(&mut self, changes: &[KVChange]) -> Result<(), KVError> {
         let mut batch = Writebatch::<LDBKey>::new();
 
-        for (k, v) in changes {
-            let k = LDBKey::from_u8(k);
-
-            batch.put(k, v);
+        for change in changes {
+            match change {
+                KVChange::Put(k, v) => batch.put(LDBKey::from_u8(k), v),
+                KVChange::Delete(k) => batch.delete(LDBKey::from_u8(k)),
+            }
         }
 
-        let res = self.db.write(WriteOptions::new(), &batch);
+        self.db
+            .write(WriteOptions::new(), &batch)
+            .map_err(|e| KVError::WriteFailed(format!("{:?}", e)))
+    }
 
-        if res.is_err() {
-            panic!("failed writing data");
-        }
+    fn range(&self, from: &[u8], to: Option<&[u8]>) -> Result<KVEntries, KVError> {
+        let from = LDBKey::from_u8(from);
+
+        let entries = self
+            .db
+            .iter(ReadOptions::new())
+            .from(&from)
+            .take_while(|(k, _)| to.map_or(true, |to| k.0.as_slice() < to))
+            .map(|(k, v)| (k.0, v))
+            .collect();
+
+        Ok(entries)
     }
 
-    fn close(&mut self) {
-        trace!("dropping `LDBStore`");
+    fn keys_from(&self, from: &[u8], limit: usize) -> Result<Vec<Vec<u8>>, KVError> {
+        let from = LDBKey::from_u8(from);
 
-        drop(self)
+        let keys = self
+            .db
+            .keys_iter(ReadOptions::new())
+            .from(&from)
+            .take(limit)
+            .map(|k| k.0)
+            .collect();
+
+        Ok(keys)
     }
 }
 
 impl Drop for LDBStore {
     fn drop(&mut self) {
-        self.close();
+        trace!("dropping `LDBStore`");
     }
 }
 
@@ -78,17 +104,44 @@ mod tests {
 
     #[test]
     fn leveldb_sanity() {
-        let mut db = LDBStore::new(Path::new("leveldb-tests"));
+        let mut db = LDBStore::new("leveldb-tests").unwrap();
 
-        db.store(&[(&[10, 20, 30], &[40, 50, 60])]);
+        db.store(&[(&[10, 20, 30], &[40, 50, 60])]).unwrap();
 
-        let v = db.get(&[10, 20, 30]).unwrap();
+        let v = db.get(&[10, 20, 30]).unwrap().unwrap();
         assert_eq!(vec![40, 50, 60], v);
 
-        db.close();
+        drop(db);
 
-        let mut db = LDBStore::new(Path::new("leveldb-tests"));
-        let v = db.get(&[10, 20, 30]).unwrap();
+        let db = LDBStore::new("leveldb-tests").unwrap();
+        let v = db.get(&[10, 20, 30]).unwrap().unwrap();
         assert_eq!(vec![40, 50, 60], v);
     }
+
+    #[test]
+    fn leveldb_delete_and_prefix_iteration() {
+        let mut db = LDBStore::new("leveldb-iter-tests").unwrap();
+
+        db.store(&[(&[1, 1], &[10]), (&[1, 2], &[20]), (&[2, 1], &[30])])
+            .unwrap();
+        db.delete(&[&[1, 1]]).unwrap();
+
+        assert_eq!(Ok(None), db.get(&[1, 1]));
+        assert_eq!(vec![(vec![1, 2], vec![20])], db.prefix(&[1]).unwrap());
+        assert_eq!(2, db.range(&[1], None).unwrap().len());
+    }
+
+    #[test]
+    fn leveldb_open_failure() {
+        // a regular file can't serve as the database directory
+        let path = std::env::temp_dir().join("leveldb-open-failure-tests");
+        std::fs::write(&path, b"not a database").unwrap();
+
+        match LDBStore::new(&path) {
+            Err(KVError::OpenFailed(_)) => (),
+            _ => unreachable!(),
+        }
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
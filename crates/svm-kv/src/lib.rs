@@ -7,6 +7,9 @@
 /// Defines the `KVStore` trait.
 pub mod traits;
 
+mod error;
+pub use error::KVError;
+
 /// An in-memory implementation for `KVStore`
 #[cfg(feature = "memory")]
 pub mod memory;
@@ -1,4 +1,5 @@
-use crate::traits::KVStore;
+use crate::traits::{KVChange, KVEntries, KVStore};
+use crate::KVError;
 use std::collections::HashMap;
 
 use log::info;
@@ -38,22 +39,52 @@ impl MemKVStore {
 }
 
 impl KVStore for MemKVStore {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, KVError> {
         let entry = self.map.get(key);
 
         if let Some(entry) = entry {
-            Some(entry.clone())
+            Ok(Some(entry.clone()))
         } else {
-            None
+            Ok(None)
         }
     }
 
-    fn store(&mut self, changes: &[(&[u8], &[u8])]) {
+    fn write_batch(&mut self, changes: &[KVChange]) -> Result<(), KVError> {
         info!("storing in-memory kv changeset");
 
-        for (k, v) in changes {
-            self.map.insert(k.to_vec(), v.to_vec());
+        for change in changes {
+            match change {
+                KVChange::Put(k, v) => {
+                    self.map.insert(k.to_vec(), v.to_vec());
+                }
+                KVChange::Delete(k) => {
+                    self.map.remove(*k);
+                }
+            }
         }
+
+        Ok(())
+    }
+
+    fn range(&self, from: &[u8], to: Option<&[u8]>) -> Result<KVEntries, KVError> {
+        let mut entries: KVEntries = self
+            .map
+            .iter()
+            .filter(|(k, _)| k.as_slice() >= from && to.map_or(true, |to| k.as_slice() < to))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        entries.sort();
+
+        Ok(entries)
+    }
+
+    fn keys_from(&self, from: &[u8], limit: usize) -> Result<Vec<Vec<u8>>, KVError> {
+        let mut keys: Vec<&Vec<u8>> = self.map.keys().filter(|k| k.as_slice() >= from).collect();
+
+        keys.sort();
+
+        Ok(keys.into_iter().take(limit).cloned().collect())
     }
 }
 
@@ -73,7 +104,7 @@ mod tests {
         let kv = MemKVStore::new();
         let addr = Address::from(0x11_22_33_44 as u32);
 
-        assert_eq!(None, kv.get(addr.as_slice()));
+        assert_eq!(Ok(None), kv.get(addr.as_slice()));
     }
 
     #[test]
@@ -83,8 +114,8 @@ mod tests {
         let mut kv = MemKVStore::new();
         let addr = Address::from(0x11_22_33_44 as u32);
 
-        kv.store(&[(addr.as_slice(), &[10, 20, 30])]);
-        assert_eq!(vec![10, 20, 30], kv.get(addr.as_slice()).unwrap());
+        kv.store(&[(addr.as_slice(), &[10, 20, 30])]).unwrap();
+        assert_eq!(vec![10, 20, 30], kv.get(addr.as_slice()).unwrap().unwrap());
     }
 
     #[test]
@@ -94,11 +125,109 @@ mod tests {
         let mut kv = MemKVStore::new();
         let addr = Address::from(0x11_22_33_44 as u32);
 
-        kv.store(&[(addr.as_slice(), &[10, 20, 30])]);
-        assert_eq!(vec![10, 20, 30], kv.get(addr.as_slice()).unwrap());
+        kv.store(&[(addr.as_slice(), &[10, 20, 30])]).unwrap();
+        assert_eq!(vec![10, 20, 30], kv.get(addr.as_slice()).unwrap().unwrap());
+
+        kv.store(&[(addr.as_slice(), &[40, 50, 60])]).unwrap();
+        assert_eq!(vec![40, 50, 60], kv.get(addr.as_slice()).unwrap().unwrap());
+    }
+
+    #[test]
+    fn key_delete() {
+        init();
+
+        let mut kv = MemKVStore::new();
+        let addr1 = Address::from(0x11_22_33_44 as u32);
+        let addr2 = Address::from(0x55_66_77_88 as u32);
+
+        kv.store(&[
+            (addr1.as_slice(), &[10, 20, 30]),
+            (addr2.as_slice(), &[40, 50, 60]),
+        ])
+        .unwrap();
+
+        kv.delete(&[addr1.as_slice()]).unwrap();
+
+        assert_eq!(Ok(None), kv.get(addr1.as_slice()));
+        assert_eq!(vec![40, 50, 60], kv.get(addr2.as_slice()).unwrap().unwrap());
+    }
+
+    #[test]
+    fn write_batch_with_tombstones() {
+        init();
+
+        let mut kv = MemKVStore::new();
+
+        kv.store(&[(&[1], &[10]), (&[2], &[20])]).unwrap();
+
+        kv.write_batch(&[
+            KVChange::Delete(&[1]),
+            KVChange::Put(&[3], &[30]),
+            KVChange::Put(&[2], &[21]),
+            KVChange::Delete(&[3]),
+        ])
+        .unwrap();
+
+        assert_eq!(Ok(None), kv.get(&[1]));
+        assert_eq!(Ok(Some(vec![21])), kv.get(&[2]));
+        assert_eq!(Ok(None), kv.get(&[3]));
+    }
+
+    #[test]
+    fn range_and_prefix_iteration() {
+        init();
+
+        let mut kv = MemKVStore::new();
+
+        kv.store(&[
+            (&[1, 0xFF], &[10]),
+            (&[1, 2], &[20]),
+            (&[1], &[30]),
+            (&[2, 0], &[40]),
+            (&[0xFF, 0xFF], &[50]),
+        ])
+        .unwrap();
+
+        let prefix = kv.prefix(&[1]).unwrap();
+        let expected = vec![
+            (vec![1], vec![30]),
+            (vec![1, 2], vec![20]),
+            (vec![1, 0xFF], vec![10]),
+        ];
+        assert_eq!(expected, prefix);
+
+        let range = kv.range(&[1, 2], Some(&[2, 0])).unwrap();
+        let expected = vec![(vec![1, 2], vec![20]), (vec![1, 0xFF], vec![10])];
+        assert_eq!(expected, range);
+
+        let prefix = kv.prefix(&[0xFF]).unwrap();
+        assert_eq!(vec![(vec![0xFF, 0xFF], vec![50])], prefix);
+
+        assert_eq!(5, kv.prefix(&[]).unwrap().len());
+    }
+
+    #[test]
+    fn keys_from_in_chunks() {
+        init();
+
+        let mut kv = MemKVStore::new();
+
+        kv.store(&[
+            (&[1, 0xFF], &[10]),
+            (&[1, 2], &[20]),
+            (&[1], &[30]),
+            (&[2, 0], &[40]),
+        ])
+        .unwrap();
+
+        let keys = kv.keys_from(&[], 2).unwrap();
+        assert_eq!(vec![vec![1], vec![1, 2]], keys);
+
+        let keys = kv.keys_from(&[1, 2, 0], 2).unwrap();
+        assert_eq!(vec![vec![1, 0xFF], vec![2, 0]], keys);
 
-        kv.store(&[(addr.as_slice(), &[40, 50, 60])]);
-        assert_eq!(vec![40, 50, 60], kv.get(addr.as_slice()).unwrap());
+        let keys = kv.keys_from(&[2, 0, 0], 2).unwrap();
+        assert!(keys.is_empty());
     }
 
     #[test]
@@ -112,14 +241,15 @@ mod tests {
         kv.store(&[
             (addr1.as_slice(), &[10, 20, 30]),
             (addr2.as_slice(), &[40, 50, 60]),
-        ]);
+        ])
+        .unwrap();
 
-        assert_eq!(vec![10, 20, 30], kv.get(addr1.as_slice()).unwrap());
-        assert_eq!(vec![40, 50, 60], kv.get(addr2.as_slice()).unwrap());
+        assert_eq!(vec![10, 20, 30], kv.get(addr1.as_slice()).unwrap().unwrap());
+        assert_eq!(vec![40, 50, 60], kv.get(addr2.as_slice()).unwrap().unwrap());
 
         kv.clear();
 
-        assert_eq!(None, kv.get(addr1.as_slice()));
-        assert_eq!(None, kv.get(addr2.as_slice()));
+        assert_eq!(Ok(None), kv.get(addr1.as_slice()));
+        assert_eq!(Ok(None), kv.get(addr2.as_slice()));
     }
 }
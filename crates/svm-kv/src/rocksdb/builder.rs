@@ -0,0 +1,153 @@
+use crate::rocksdb::RocksStore;
+use crate::KVError;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use log::{error, info};
+
+/// The compression applied to `rocksdb` data blocks
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RocksCompression {
+    /// No compression
+    None,
+
+    /// `lz4` compression
+    Lz4,
+}
+
+/// Tuning of a `rocksdb` column family.
+/// Settings left unset fall back to the `rocksdb` defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RocksTuning {
+    /// The size (in bytes) of the blocks LRU cache
+    pub cache_size: Option<usize>,
+
+    /// The compression of the data blocks
+    pub compression: Option<RocksCompression>,
+
+    /// The number of bits per key of the bloom filter (no bloom filter when unset)
+    pub bloom_filter_bits: Option<i32>,
+}
+
+impl RocksTuning {
+    fn to_options(&self) -> rocksdb::Options {
+        let mut opts = rocksdb::Options::default();
+
+        if let Some(compression) = self.compression {
+            let compression = match compression {
+                RocksCompression::None => rocksdb::DBCompressionType::None,
+                RocksCompression::Lz4 => rocksdb::DBCompressionType::Lz4,
+            };
+
+            opts.set_compression_type(compression);
+        }
+
+        if self.cache_size.is_some() || self.bloom_filter_bits.is_some() {
+            let mut block_opts = rocksdb::BlockBasedOptions::default();
+
+            if let Some(cache_size) = self.cache_size {
+                block_opts.set_lru_cache(cache_size);
+            }
+
+            if let Some(bits) = self.bloom_filter_bits {
+                block_opts.set_bloom_filter(bits, true);
+            }
+
+            opts.set_block_based_table_factory(&block_opts);
+        }
+
+        opts
+    }
+}
+
+/// Builds a `RocksStore`.
+///
+/// ```rust, no_run
+/// use svm_kv::rocksdb::{RocksCompression, RocksStoreBuilder, RocksTuning};
+///
+/// let code_tuning = RocksTuning {
+///     compression: Some(RocksCompression::Lz4),
+///     ..RocksTuning::default()
+/// };
+///
+/// let db = RocksStoreBuilder::new("svm-db")
+///     .with_cache_size(64 * 1024 * 1024)
+///     .with_bloom_filter(10)
+///     .with_sync_writes(true)
+///     .with_column_family("code", code_tuning)
+///     .build()
+///     .unwrap();
+///
+/// let code = db.column_family("code").unwrap();
+/// ```
+pub struct RocksStoreBuilder {
+    path: PathBuf,
+    tuning: RocksTuning,
+    sync_writes: bool,
+    column_families: Vec<(String, RocksTuning)>,
+}
+
+impl RocksStoreBuilder {
+    /// A new builder of a `RocksStore` under the given `path` (the database is created if missing)
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            tuning: RocksTuning::default(),
+            sync_writes: false,
+            column_families: Vec::new(),
+        }
+    }
+
+    /// Sets the size (in bytes) of the default column family blocks cache
+    pub fn with_cache_size(mut self, cache_size: usize) -> Self {
+        self.tuning.cache_size = Some(cache_size);
+        self
+    }
+
+    /// Sets the compression of the default column family
+    pub fn with_compression(mut self, compression: RocksCompression) -> Self {
+        self.tuning.compression = Some(compression);
+        self
+    }
+
+    /// Enables a bloom filter (of `bits_per_key` bits per key) for the default column family
+    pub fn with_bloom_filter(mut self, bits_per_key: i32) -> Self {
+        self.tuning.bloom_filter_bits = Some(bits_per_key);
+        self
+    }
+
+    /// When `true`, each write batch is synced to the write-ahead-log before returning
+    pub fn with_sync_writes(mut self, sync: bool) -> Self {
+        self.sync_writes = sync;
+        self
+    }
+
+    /// Adds a column family named `name` (created if missing) tuned by `tuning`
+    pub fn with_column_family(mut self, name: &str, tuning: RocksTuning) -> Self {
+        self.column_families.push((name.to_string(), tuning));
+        self
+    }
+
+    /// Opens the database.
+    /// Returns an error when the database can't be opened (for example, it's already opened by another handle).
+    pub fn build(self) -> Result<RocksStore, KVError> {
+        info!("opening rocksdb. (path = \"{}\")", self.path.display());
+
+        let mut opts = self.tuning.to_options();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+
+        let cfs = self
+            .column_families
+            .iter()
+            .map(|(name, tuning)| rocksdb::ColumnFamilyDescriptor::new(name, tuning.to_options()));
+
+        let db = rocksdb::DB::open_cf_descriptors(&opts, &self.path, cfs).map_err(|e| {
+            error!("failed to open database: {}", e);
+            KVError::OpenFailed(e.to_string())
+        })?;
+
+        Ok(RocksStore::with_db(Arc::new(db), None, self.sync_writes))
+    }
+}
@@ -1,78 +1,263 @@
-use crate::traits::KVStore;
+use crate::rocksdb::RocksStoreBuilder;
+use crate::traits::{KVChange, KVEntries, KVStore};
+use crate::KVError;
 use std::path::Path;
+use std::sync::{Arc, Weak};
 
-use log::info;
+use log::{error, trace};
 
 /// An implementation of `KVStore` trait against `rocksdb`.
+///
+/// A `RocksStore` is a handle to a column family of an opened database.
+/// Cloned handles (and handles returned by `column_family`) share the same underlying database.
+#[derive(Clone)]
 pub struct RocksStore {
-    pub(crate) db: rocksdb::DB,
+    pub(crate) db: Arc<rocksdb::DB>,
+    cf: Option<String>,
+    sync_writes: bool,
+}
+
+/// A non-owning reference to a `RocksStore` database (see `RocksStore::downgrade`)
+pub struct WeakRocksStore {
+    db: Weak<rocksdb::DB>,
+    cf: Option<String>,
+    sync_writes: bool,
 }
 
 impl RocksStore {
-    /// New `RocksStore` under the given `path`
-    pub fn new<P: AsRef<Path>>(path: P) -> Self {
-        info!("opening rocksdb. (path = \"{}\")", path.as_ref().display());
+    /// New `RocksStore` under the given `path` with the default `rocksdb` options.
+    /// Returns an error when the database can't be opened.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, KVError> {
+        RocksStoreBuilder::new(path).build()
+    }
 
+    pub(crate) fn with_db(db: Arc<rocksdb::DB>, cf: Option<String>, sync_writes: bool) -> Self {
         Self {
-            db: rocksdb::DB::open_default(path).unwrap(),
+            db,
+            cf,
+            sync_writes,
+        }
+    }
+
+    /// Returns a handle to the column family `name` of the same database.
+    /// Returns `None` when the database has no such column family (see `RocksStoreBuilder::with_column_family`)
+    pub fn column_family(&self, name: &str) -> Option<Self> {
+        self.db.cf_handle(name).map(|_| {
+            Self::with_db(
+                Arc::clone(&self.db),
+                Some(name.to_string()),
+                self.sync_writes,
+            )
+        })
+    }
+
+    /// Returns a `WeakRocksStore` which doesn't keep the database open
+    pub fn downgrade(&self) -> WeakRocksStore {
+        WeakRocksStore {
+            db: Arc::downgrade(&self.db),
+            cf: self.cf.clone(),
+            sync_writes: self.sync_writes,
         }
     }
+
+    fn cf_handle(&self) -> Option<rocksdb::ColumnFamily> {
+        self.cf.as_ref().map(|name| {
+            self.db
+                .cf_handle(name)
+                .unwrap_or_else(|| panic!("column family `{}` not found", name))
+        })
+    }
+
+    fn write_opts(&self) -> rocksdb::WriteOptions {
+        let mut opts = rocksdb::WriteOptions::default();
+        opts.set_sync(self.sync_writes);
+
+        opts
+    }
+}
+
+impl WeakRocksStore {
+    /// Returns the `RocksStore` if its database is still open
+    pub fn upgrade(&self) -> Option<RocksStore> {
+        self.db
+            .upgrade()
+            .map(|db| RocksStore::with_db(db, self.cf.clone(), self.sync_writes))
+    }
 }
 
 impl KVStore for RocksStore {
-    #[allow(clippy::match_wild_err_arm)]
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
-        match self.db.get(key) {
-            Ok(dbvec) => match dbvec {
-                None => None,
-                Some(dbvec) => Some(dbvec.to_vec()),
-            },
-            Err(_) => panic!("Error reading key: `{:?}`", key),
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, KVError> {
+        let res = match self.cf_handle() {
+            Some(cf) => self.db.get_cf(cf, key),
+            None => self.db.get(key),
+        };
+
+        match res {
+            Ok(dbvec) => Ok(dbvec.map(|dbvec| dbvec.to_vec())),
+            Err(e) => {
+                error!("error reading key: `{:?}` ({})", key, e);
+
+                Err(KVError::ReadFailed(e.to_string()))
+            }
         }
     }
 
-    fn store(&mut self, changes: &[(&[u8], &[u8])]) {
+    fn write_batch(&mut self, changes: &[KVChange]) -> Result<(), KVError> {
         let mut batch = rocksdb::WriteBatch::default();
+        let cf = self.cf_handle();
 
-        for (k, v) in changes {
-            let res = batch.put(k, v);
+        for change in changes {
+            let res = match (change, cf) {
+                (KVChange::Put(k, v), Some(cf)) => batch.put_cf(cf, k, v),
+                (KVChange::Put(k, v), None) => batch.put(k, v),
+                (KVChange::Delete(k), Some(cf)) => batch.delete_cf(cf, k),
+                (KVChange::Delete(k), None) => batch.delete(k),
+            };
 
-            if res.is_err() {
-                panic!("failed `put`-ing bach data");
-            }
+            res.map_err(|e| KVError::WriteFailed(e.to_string()))?;
         }
 
-        let res = self.db.write(batch);
+        self.db.write_opt(batch, &self.write_opts()).map_err(|e| {
+            error!("failed `write`-ing batch ({})", e);
+
+            KVError::WriteFailed(e.to_string())
+        })
+    }
+
+    fn range(&self, from: &[u8], to: Option<&[u8]>) -> Result<KVEntries, KVError> {
+        use rocksdb::{Direction, IteratorMode};
+
+        let mode = IteratorMode::From(from, Direction::Forward);
+
+        let iter = match self.cf_handle() {
+            Some(cf) => self
+                .db
+                .iterator_cf(cf, mode)
+                .map_err(|e| KVError::ReadFailed(e.to_string()))?,
+            None => self.db.iterator(mode),
+        };
+
+        let entries = iter
+            .take_while(|(k, _)| to.map_or(true, |to| k.as_ref() < to))
+            .map(|(k, v)| (k.to_vec(), v.to_vec()))
+            .collect();
+
+        Ok(entries)
+    }
+
+    fn keys_from(&self, from: &[u8], limit: usize) -> Result<Vec<Vec<u8>>, KVError> {
+        let mut iter = match self.cf_handle() {
+            Some(cf) => self
+                .db
+                .raw_iterator_cf(cf)
+                .map_err(|e| KVError::ReadFailed(e.to_string()))?,
+            None => self.db.raw_iterator(),
+        };
 
-        if res.is_err() {
-            panic!("failed `write`-ing bach");
+        iter.seek(from);
+
+        let mut keys = Vec::new();
+
+        while keys.len() < limit && iter.valid() {
+            if let Some(key) = iter.key() {
+                keys.push(key.to_vec());
+            }
+
+            iter.next();
         }
+
+        Ok(keys)
     }
 }
 
 impl Drop for RocksStore {
     fn drop(&mut self) {
-        info!("dropping `RocksStore`");
+        trace!("dropping `RocksStore` (column family = {:?})", self.cf);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rocksdb::{RocksCompression, RocksTuning};
 
     #[test]
     fn rocksdb_sanity() {
-        let mut db = RocksStore::new("rocksdb-tests");
+        let mut db = RocksStore::new("rocksdb-tests").unwrap();
 
-        db.store(&[(&[10, 20, 30], &[40, 50, 60])]);
+        db.store(&[(&[10, 20, 30], &[40, 50, 60])]).unwrap();
 
-        let v = db.get(&[10, 20, 30]).unwrap();
+        let v = db.get(&[10, 20, 30]).unwrap().unwrap();
         assert_eq!(vec![40, 50, 60], v);
 
         drop(db);
 
-        let db = RocksStore::new("rocksdb-tests");
-        let v = db.get(&[10, 20, 30]).unwrap();
+        let db = RocksStore::new("rocksdb-tests").unwrap();
+        let v = db.get(&[10, 20, 30]).unwrap().unwrap();
         assert_eq!(vec![40, 50, 60], v);
     }
+
+    #[test]
+    fn rocksdb_open_failure_returns_an_error() {
+        let db = RocksStore::new("rocksdb-open-tests").unwrap();
+
+        // the database is locked by `db`
+        match RocksStore::new("rocksdb-open-tests") {
+            Err(KVError::OpenFailed(_)) => (),
+            _ => panic!("expected `KVError::OpenFailed`"),
+        }
+
+        drop(db);
+        assert!(RocksStore::new("rocksdb-open-tests").is_ok());
+    }
+
+    #[test]
+    fn rocksdb_delete_and_prefix_iteration() {
+        let mut db = RocksStore::new("rocksdb-iter-tests").unwrap();
+
+        db.store(&[(&[1, 1], &[10]), (&[1, 2], &[20]), (&[2, 1], &[30])])
+            .unwrap();
+        db.delete(&[&[1, 1]]).unwrap();
+
+        assert_eq!(Ok(None), db.get(&[1, 1]));
+        assert_eq!(vec![(vec![1, 2], vec![20])], db.prefix(&[1]).unwrap());
+        assert_eq!(2, db.range(&[1], None).unwrap().len());
+    }
+
+    #[test]
+    fn rocksdb_column_families() {
+        let tuning = RocksTuning {
+            cache_size: Some(1024 * 1024),
+            compression: Some(RocksCompression::Lz4),
+            bloom_filter_bits: Some(10),
+        };
+
+        let db = RocksStoreBuilder::new("rocksdb-cf-tests")
+            .with_sync_writes(true)
+            .with_column_family("pages", tuning)
+            .with_column_family("code", RocksTuning::default())
+            .build()
+            .unwrap();
+
+        assert!(db.column_family("unknown").is_none());
+
+        let mut pages = db.column_family("pages").unwrap();
+        let mut code = db.column_family("code").unwrap();
+
+        pages.store(&[(&[1, 2], &[10])]).unwrap();
+        code.store(&[(&[1, 2], &[20])]).unwrap();
+
+        assert_eq!(Ok(None), db.get(&[1, 2]));
+        assert_eq!(Ok(Some(vec![10])), pages.get(&[1, 2]));
+        assert_eq!(Ok(Some(vec![20])), code.get(&[1, 2]));
+        assert_eq!(vec![(vec![1, 2], vec![20])], code.prefix(&[1]).unwrap());
+
+        let weak = code.downgrade();
+        drop(db);
+        drop(pages);
+        assert!(weak.upgrade().is_some());
+
+        drop(code);
+        assert!(weak.upgrade().is_none());
+    }
 }
@@ -1,3 +1,5 @@
+mod builder;
 mod db;
 
-pub use db::RocksStore;
+pub use builder::{RocksCompression, RocksStoreBuilder, RocksTuning};
+pub use db::{RocksStore, WeakRocksStore};
@@ -1,9 +1,73 @@
+use crate::KVError;
+
+/// A single change of a `KVStore::write_batch` batch
+#[derive(Debug, Clone, PartialEq)]
+pub enum KVChange<'a> {
+    /// Associates `key` with `value` (overriding an existing value)
+    Put(&'a [u8], &'a [u8]),
+
+    /// A tombstone. Removes `key` (if it exists)
+    Delete(&'a [u8]),
+}
+
+/// The `(key, value)` entries returned by the iteration methods of `KVStore` (ordered by key)
+pub type KVEntries = Vec<(Vec<u8>, Vec<u8>)>;
+
 /// `KVStore` is a trait for defining an interface against key-value stores. for example `in-memory / leveldb / rocksdb`
 pub trait KVStore {
     /// Retrieves the value pointed by `key` (Optional).
-    #[must_use]
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+    /// Returns an error when the underlying database failed reading.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, KVError>;
+
+    /// Applies a batch of changes (puts and tombstones) in order.
+    /// Returns an error when the underlying database failed writing the batch (none of the changes is applied).
+    fn write_batch(&mut self, changes: &[KVChange]) -> Result<(), KVError>;
+
+    /// Returns the entries having `from <= key < to` (or `from <= key` when `to` is `None`), ordered by key.
+    fn range(&self, from: &[u8], to: Option<&[u8]>) -> Result<KVEntries, KVError>;
+
+    /// Returns up to `limit` keys having `from <= key`, ordered by key (the values aren't read).
+    /// Meant for scanning the whole store in bounded chunks (see `KVStore::range` for reading the entries).
+    fn keys_from(&self, from: &[u8], limit: usize) -> Result<Vec<Vec<u8>>, KVError>;
 
     /// Stores a batch of changes. Each change is `key` -> `value` association.
-    fn store(&mut self, changes: &[(&[u8], &[u8])]);
+    /// Returns an error when the underlying database failed writing the batch (none of the changes is applied).
+    fn store(&mut self, changes: &[(&[u8], &[u8])]) -> Result<(), KVError> {
+        let changes: Vec<KVChange> = changes
+            .iter()
+            .map(|(key, value)| KVChange::Put(key, value))
+            .collect();
+
+        self.write_batch(&changes)
+    }
+
+    /// Deletes a batch of keys. Missing keys are ignored.
+    fn delete(&mut self, keys: &[&[u8]]) -> Result<(), KVError> {
+        let changes: Vec<KVChange> = keys.iter().map(|key| KVChange::Delete(key)).collect();
+
+        self.write_batch(&changes)
+    }
+
+    /// Returns the entries whose key starts with `prefix`, ordered by key.
+    fn prefix(&self, prefix: &[u8]) -> Result<KVEntries, KVError> {
+        match prefix_upper_bound(prefix) {
+            Some(to) => self.range(prefix, Some(&to)),
+            None => self.range(prefix, None),
+        }
+    }
+}
+
+/// Returns the smallest key greater than all the keys starting with `prefix`.
+/// Returns `None` when there is no such key (i.e `prefix` is empty or made of `0xFF` bytes only).
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut bound = prefix.to_vec();
+
+    while let Some(last) = bound.pop() {
+        if last < 0xFF {
+            bound.push(last + 1);
+            return Some(bound);
+        }
+    }
+
+    None
 }
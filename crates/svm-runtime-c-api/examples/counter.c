@@ -231,8 +231,9 @@ wasmer_result_t create_import_object(
 
     uint32_t max_pages = 5;
     uint32_t max_pages_slices = 100;
+    uint64_t gas_limit = 1000000;
 
-    return svm_import_object(import_object, addr, state, max_pages, max_pages_slices, node, imports, imports_len);
+    return svm_import_object(import_object, addr, state, max_pages, max_pages_slices, gas_limit, NULL, NULL, 0, 0, NULL, node, imports, imports_len);
 }
 
 wasmer_import_t* prepare_imports() {
@@ -42,6 +42,16 @@ void* svm_contract_compute_address(svm_contract_t *contract);
  */
 wasmer_result_t svm_contract_store(svm_contract_t *contract, void *address);
 
+/**
+ * Writes into `gas` the maximum gas a transaction executing function `func_name` (exported by the contract
+ * deployed under `address`) may consume, using the ahead-of-time gas estimation of the contract code.
+ *
+ * Returns `wasmer_result_t::WASMER_ERROR` when the contract or the function don't exist,
+ * or when the function gas isn't fixed (e.g the function has loops or calls other contracts).
+ * Use `wasmer_last_error_length` and `wasmer_last_error_message` to get an error message.
+ */
+wasmer_result_t svm_contract_export_gas(uint64_t *gas, void *address, void *func_name, uint32_t func_name_len);
+
 
 /**
  * Creates a Transaction from given bytes.
@@ -81,17 +91,37 @@ void svm_register_set(const wasmer_instance_context_t *ctx,
 void *svm_instance_context_node_data_get(const wasmer_instance_context_t *ctx);
 
 
+/**
+ * Resolves the state of a contract called by another contract.
+ * Returns a pointer to the contract state (32 bytes), or `NULL` when it can't be resolved.
+ */
+typedef const void* (*svm_state_resolver_t)(const void *node_data, const void *addr);
+
+/**
+ * Resolves the balance of an account (a running contract).
+ * Returns a pointer to the account balance (16 bytes, Little-Endian), or `NULL` when the account has no balance.
+ */
+typedef const void* (*svm_balance_resolver_t)(const void *node_data, const void *addr);
+
 /**
  * Creates a new Import object
  * Returns `wasmer_result_t::WASMER_OK` upon success.
  * Returns `wasmer_result_t::WASMER_ERROR` upon failure. Use `wasmer_last_error_length`
  * and `wasmer_last_error_message` to get an error message.
+ *
+ * `beacon` points to the layer randomness beacon (32 bytes). `NULL` stands for a zeros beacon.
  */
 wasmer_result_t svm_import_object(wasmer_import_object_t** import_object,
                                   void *addr,
                                   void *state,
                                   uint32_t max_pages,
                                   uint32_t max_page_slices,
+                                  uint64_t gas_limit,
+                                  svm_state_resolver_t state_resolver,
+                                  svm_balance_resolver_t balance_resolver,
+                                  uint64_t layer_id,
+                                  uint64_t layer_timestamp,
+                                  const void *beacon,
                                   void *node_data,
                                   wasmer_import_t *imports,
                                   uint32_t imports_len);
@@ -117,4 +147,57 @@ void svm_receipt_error(svm_receipt_t *receipt);
  */
 void* svm_receipt_new_state(svm_receipt_t *receipt);
 
+/**
+ * Returns the number of contracts called (directly or indirectly) by the executed contract.
+ * Returns `0` for a failed transaction.
+ */
+uint32_t svm_receipt_callees_count(svm_receipt_t *receipt);
+
+/**
+ * Returns pointers to the address and new state of the called contract indexed `idx`.
+ * Returns `WASMER_ERROR` when `idx` is out of bounds (use `wasmer_last_error_message` to get an error message).
+ */
+wasmer_result_t svm_receipt_callee(svm_receipt_t *receipt, uint32_t idx, void **addr, void **state);
+
+/**
+ * Returns the number of events emitted by the executed contract (and the contracts it called).
+ * Returns `0` for a failed transaction.
+ */
+uint32_t svm_receipt_logs_count(svm_receipt_t *receipt);
+
+/**
+ * Returns the event indexed `idx`: the emitting contract address, the event topic (32 bytes),
+ * the event payload data and the number of layers the event should be kept for.
+ * Returns `WASMER_ERROR` when `idx` is out of bounds (use `wasmer_last_error_message` to get an error message).
+ */
+wasmer_result_t svm_receipt_log(svm_receipt_t *receipt,
+                                uint32_t idx,
+                                void **addr,
+                                void **topic,
+                                void **data,
+                                uint32_t *data_len,
+                                uint32_t *expiration);
+
+/**
+ * Returns the number of balance changes the node should apply (atomically) for the executed transaction.
+ * Returns `0` for a failed transaction.
+ */
+uint32_t svm_receipt_transfers_count(svm_receipt_t *receipt);
+
+/**
+ * Returns the balance change indexed `idx`: the debited account address, the credited account address,
+ * and the transferred amount (written to `amount` as 16 bytes, Little-Endian).
+ * Returns `WASMER_ERROR` when `idx` is out of bounds (use `wasmer_last_error_message` to get an error message).
+ */
+wasmer_result_t svm_receipt_transfer(svm_receipt_t *receipt,
+                                     uint32_t idx,
+                                     void **from,
+                                     void **to,
+                                     uint8_t *amount);
+
+/**
+ * Returns the gas consumed by the transaction execution (succeeded or failed)
+ */
+uint64_t svm_receipt_gas_used(svm_receipt_t *receipt);
+
 #endif /* WASMER_SVM_H */
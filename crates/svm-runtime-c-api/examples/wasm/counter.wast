@@ -2,7 +2,7 @@
   (func $vmcall_get_counter (import "node" "vmcall_get_counter") (result i32))
   (func $vmcall_inc_counter (import "node" "vmcall_inc_counter") (param i32))
 
-  (memory 1)
+  (memory 1 1)
 
   (func (export "inc") (param i32)
         get_local 0
@@ -15,9 +15,12 @@ macro_rules! include_svm_runtime_c_api {
         use svm_contract::transaction::Transaction;
 
         use svm_runtime::register::SvmReg;
-        use svm_runtime::runtime::Receipt;
+        use svm_runtime::runtime::{DeployReceipt, Receipt};
 
-        use crate::c_types::{svm_contract_t, svm_receipt_t, svm_transaction_t};
+        use crate::c_types::{
+            svm_contract_t, svm_deploy_receipt_t, svm_receipt_t, svm_transaction_t, svm_upgrade_t,
+        };
+        use crate::error::{ExportGasError, ReceiptError};
 
         use log::{debug, error, trace};
         use std::ffi::c_void;
@@ -95,6 +98,7 @@ macro_rules! include_svm_runtime_c_api {
         /// (see `svm_transaction_exec`)
         ///
         /// This function should be called after performing validation.
+        /// Returns `WASMER_ERROR` when a contract has already been stored under `raw_addr`.
         ///
         /// * `raw_contract` - The wasm contract to be stored
         ///
@@ -108,13 +112,157 @@ macro_rules! include_svm_runtime_c_api {
 
             let contract = cast_to_rust_type!(raw_contract, svm_contract::wasm::Contract);
             let addr = Address::from(raw_addr);
-            runtime::contract_store(contract, &addr);
 
-            debug!("`svm_contract_build returns `WASMER_OK`");
+            match runtime::contract_store(contract, &addr) {
+                Ok(()) => {
+                    debug!("`svm_contract_store returns `WASMER_OK`");
+                    wasmer_result_t::WASMER_OK
+                }
+                Err(err) => {
+                    update_last_error(err);
+                    error!("`svm_contract_store returns `WASMER_ERROR`");
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
+        /// Deploys a contract. Runs the contract constructor (if any), and stores the contract
+        /// only if the constructor succeeded (see `svm_deploy_receipt_status`).
+        ///
+        /// * `raw_receipt`       - The deploy receipt (holds the contract initial state)
+        /// * `raw_contract`      - The contract to deploy (see `svm_contract_build`)
+        /// * `raw_addr`          - The contract account address (see `svm_contract_compute_address`)
+        /// * `raw_import_object` - The import object created for the contract address and an empty state
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_contract_deploy(
+            raw_receipt: *mut *mut svm_deploy_receipt_t,
+            raw_contract: *const svm_contract_t,
+            raw_addr: *const c_void,
+            raw_import_object: *const wasmer_import_object_t,
+        ) -> wasmer_result_t {
+            debug!("`svm_contract_deploy` start");
+
+            let contract = cast_to_rust_type!(raw_contract, svm_contract::wasm::Contract);
+            let addr = Address::from(raw_addr);
+            let import_object = cast_to_rust_type!(raw_import_object, ImportObject);
+
+            let receipt = runtime::contract_deploy(contract, &addr, import_object);
+            *raw_receipt = into_raw!(receipt, svm_deploy_receipt_t);
+
+            debug!("`svm_contract_deploy returns `WASMER_OK`");
 
             wasmer_result_t::WASMER_OK
         }
 
+        /// Returns the ahead-of-time gas estimation of function `raw_func_name` exported by the contract
+        /// deployed under `raw_addr` (see `runtime::contract_export_gas`).
+        ///
+        /// * `raw_gas`           - The maximum gas a transaction executing the function may consume (written on success)
+        /// * `raw_addr`          - The contract account address
+        /// * `raw_func_name`     - The function name (UTF-8 string)
+        /// * `raw_func_name_len` - The function name length (in bytes)
+        ///
+        /// Returns `WASMER_ERROR` when the contract or the function don't exist,
+        /// or when the function gas isn't fixed (e.g the function has loops or calls other contracts).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_contract_export_gas(
+            raw_gas: *mut u64,
+            raw_addr: *const c_void,
+            raw_func_name: *const c_void,
+            raw_func_name_len: u32,
+        ) -> wasmer_result_t {
+            use svm_compiler::gas::GasEstimate;
+
+            debug!("`svm_contract_export_gas` start");
+
+            let addr = Address::from(raw_addr);
+            let func_name =
+                std::slice::from_raw_parts(raw_func_name as *const u8, raw_func_name_len as usize);
+            let func_name = String::from_utf8_lossy(func_name);
+
+            match runtime::contract_export_gas(&addr, &func_name) {
+                Ok(GasEstimate::Fixed(gas)) => {
+                    *raw_gas = gas;
+
+                    debug!("`svm_contract_export_gas returns `WASMER_OK`");
+                    wasmer_result_t::WASMER_OK
+                }
+                Ok(estimate) => {
+                    update_last_error(ExportGasError::NotFixed(func_name.to_string(), estimate));
+
+                    error!("`svm_contract_export_gas returns `WASMER_ERROR`");
+                    wasmer_result_t::WASMER_ERROR
+                }
+                Err(err) => {
+                    update_last_error(err);
+
+                    error!("`svm_contract_export_gas returns `WASMER_ERROR`");
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
+        /// Builds an instance of `svm_upgrade_t`.
+        /// Should be called while the transaction is in the `mempool` of the full-node (prior mining it).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_upgrade_build(
+            raw_upgrade: *mut *mut svm_upgrade_t,
+            raw_bytes: *const c_void,
+            raw_bytes_len: u64,
+        ) -> wasmer_result_t {
+            debug!("`svm_upgrade_build start`");
+
+            let bytes = std::slice::from_raw_parts(raw_bytes as *const u8, raw_bytes_len as usize);
+            let result = runtime::upgrade_build(&bytes);
+
+            match result {
+                Ok(upgrade) => {
+                    *raw_upgrade = into_raw!(upgrade, svm_upgrade_t);
+                    debug!("`svm_upgrade_build returns `WASMER_OK`");
+                    wasmer_result_t::WASMER_OK
+                }
+                Err(err) => {
+                    update_last_error(err);
+                    error!("`svm_upgrade_build returns `WASMER_ERROR`");
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
+        /// Upgrades an already deployed contract to the upgrade new code.
+        /// The contract keeps its account address (and its state).
+        ///
+        /// * `raw_code_hash` - Set to a pointer to the new code-hash (32 bytes) upon success
+        /// * `raw_upgrade`   - The upgrade to apply (see `svm_upgrade_build`)
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_contract_upgrade(
+            raw_code_hash: *mut *const c_void,
+            raw_upgrade: *const svm_upgrade_t,
+        ) -> wasmer_result_t {
+            debug!("`svm_contract_upgrade` start");
+
+            let upgrade = cast_to_rust_type!(raw_upgrade, svm_contract::upgrade::Upgrade);
+
+            match runtime::contract_upgrade(upgrade) {
+                Ok(hash) => {
+                    let hash = Box::leak(Box::new(hash));
+                    *raw_code_hash = hash.0.as_ptr() as *const c_void;
+
+                    debug!("`svm_contract_upgrade returns `WASMER_OK`");
+                    wasmer_result_t::WASMER_OK
+                }
+                Err(err) => {
+                    update_last_error(err);
+                    error!("`svm_contract_upgrade returns `WASMER_ERROR`");
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
         /// Builds an instance of `svm_transaction_t`.
         /// Should be called while the transaction is in the `mempool` of the full-node (prior mining it).
         #[must_use]
@@ -220,6 +368,9 @@ macro_rules! include_svm_runtime_c_api {
         /// The import object will include imports of two flavors:
         /// * external vmcalls (i.e: node vmcalls)
         /// * internal vmcalls (i.e: register/storage/etc vmcalls)
+        ///
+        /// The executed contract can query the layer (id, timestamp and randomness beacon) using the `env` vmcalls.
+        /// `raw_beacon` points to 32 bytes (`NULL` stands for a zeros beacon).
         #[must_use]
         #[no_mangle]
         pub unsafe extern "C" fn svm_import_object(
@@ -228,6 +379,12 @@ macro_rules! include_svm_runtime_c_api {
             raw_state: *const c_void,
             raw_max_pages: libc::c_int,
             raw_max_page_slices: libc::c_int,
+            raw_gas_limit: u64,
+            state_resolver: Option<svm_runtime::opts::StateResolver>,
+            balance_resolver: Option<svm_runtime::opts::BalanceResolver>,
+            raw_layer_id: u64,
+            raw_layer_timestamp: u64,
+            raw_beacon: *const c_void,
             node_data: *const c_void,
             imports: *mut wasmer_import_t,
             imports_len: libc::c_uint,
@@ -237,9 +394,25 @@ macro_rules! include_svm_runtime_c_api {
             let addr = Address::from(raw_addr);
             let state = State::from(raw_state);
 
+            let mut beacon = [0; 32];
+            if !raw_beacon.is_null() {
+                let raw_beacon = std::slice::from_raw_parts(raw_beacon as *const u8, 32);
+                beacon.copy_from_slice(raw_beacon);
+            }
+
+            let layer = svm_runtime::opts::Layer {
+                id: raw_layer_id,
+                timestamp: raw_layer_timestamp,
+                beacon,
+            };
+
             let opts = svm_runtime::opts::Opts {
                 max_pages: raw_max_pages as usize,
                 max_pages_slices: raw_max_page_slices as usize,
+                gas_limit: raw_gas_limit,
+                state_resolver,
+                balance_resolver,
+                layer,
             };
 
             let import_object = runtime::import_object_create(addr, state, node_data, opts);
@@ -328,5 +501,198 @@ macro_rules! include_svm_runtime_c_api {
                 panic!("method not allowed to be called when transaction execution failed");
             }
         }
+
+        /// Returns the number of contracts called (directly or indirectly) by the executed contract.
+        /// Returns `0` when the transaction failed (the called contracts states are discarded).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_callees_count(
+            raw_receipt: *const svm_receipt_t,
+        ) -> u32 {
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+
+            receipt.callees_states.len() as u32
+        }
+
+        /// Returns pointers to the address and new state of the called contract indexed `idx`
+        /// (see `svm_receipt_callees_count`).
+        /// Returns `WASMER_ERROR` when `idx` is out of bounds.
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_callee(
+            raw_receipt: *const svm_receipt_t,
+            idx: u32,
+            raw_addr: *mut *const c_void,
+            raw_state: *mut *const c_void,
+        ) -> wasmer_result_t {
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+
+            match receipt.callees_states.get(idx as usize) {
+                Some((addr, state)) => {
+                    *raw_addr = addr.as_ptr() as *const c_void;
+                    *raw_state = state.as_ptr() as *const c_void;
+
+                    wasmer_result_t::WASMER_OK
+                }
+                None => {
+                    let count = receipt.callees_states.len();
+                    update_last_error(ReceiptError::CalleeIndexOutOfBounds(idx, count));
+
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
+        /// Returns the number of events emitted by the executed contract (and the contracts it called).
+        /// Returns `0` when the transaction failed (the emitted events are discarded).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_logs_count(raw_receipt: *const svm_receipt_t) -> u32 {
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+
+            receipt.logs.len() as u32
+        }
+
+        /// Returns the event indexed `idx` (see `svm_receipt_logs_count`):
+        /// * `raw_addr`       - a pointer to the emitting contract address
+        /// * `raw_topic`      - a pointer to the event topic (32 bytes)
+        /// * `raw_data`       - a pointer to the event payload data (`raw_data_len` bytes)
+        /// * `raw_expiration` - the number of layers the event should be kept for
+        ///
+        /// Returns `WASMER_ERROR` when `idx` is out of bounds.
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_log(
+            raw_receipt: *const svm_receipt_t,
+            idx: u32,
+            raw_addr: *mut *const c_void,
+            raw_topic: *mut *const c_void,
+            raw_data: *mut *const c_void,
+            raw_data_len: *mut u32,
+            raw_expiration: *mut u32,
+        ) -> wasmer_result_t {
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+
+            match receipt.logs.get(idx as usize) {
+                Some(log) => {
+                    *raw_addr = log.addr.as_ptr() as *const c_void;
+                    *raw_topic = log.topic.as_ptr() as *const c_void;
+                    *raw_data = log.data.as_ptr() as *const c_void;
+                    *raw_data_len = log.data.len() as u32;
+                    *raw_expiration = log.expiration;
+
+                    wasmer_result_t::WASMER_OK
+                }
+                None => {
+                    let count = receipt.logs.len();
+                    update_last_error(ReceiptError::LogIndexOutOfBounds(idx, count));
+
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
+        /// Returns the number of balance changes the node should apply (atomically) for the executed transaction.
+        /// Returns `0` when the transaction failed (no coins are transferred).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_transfers_count(
+            raw_receipt: *const svm_receipt_t,
+        ) -> u32 {
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+
+            receipt.transfers.len() as u32
+        }
+
+        /// Returns the balance change indexed `idx` (see `svm_receipt_transfers_count`):
+        /// * `raw_from`   - a pointer to the debited account address
+        /// * `raw_to`     - a pointer to the credited account address
+        /// * `raw_amount` - the transferred amount is written to it (16 bytes, Little-Endian)
+        ///
+        /// Returns `WASMER_ERROR` when `idx` is out of bounds.
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_transfer(
+            raw_receipt: *const svm_receipt_t,
+            idx: u32,
+            raw_from: *mut *const c_void,
+            raw_to: *mut *const c_void,
+            raw_amount: *mut u8,
+        ) -> wasmer_result_t {
+            use byteorder::{ByteOrder, LittleEndian};
+
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+
+            match receipt.transfers.get(idx as usize) {
+                Some(transfer) => {
+                    *raw_from = transfer.from.as_ptr() as *const c_void;
+                    *raw_to = transfer.to.as_ptr() as *const c_void;
+
+                    let amount = std::slice::from_raw_parts_mut(raw_amount, 16);
+                    LittleEndian::write_u128(amount, transfer.amount.0);
+
+                    wasmer_result_t::WASMER_OK
+                }
+                None => {
+                    let count = receipt.transfers.len();
+                    update_last_error(ReceiptError::TransferIndexOutOfBounds(idx, count));
+
+                    wasmer_result_t::WASMER_ERROR
+                }
+            }
+        }
+
+        /// Returns the gas consumed by the transaction execution (succeeded or failed).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_receipt_gas_used(raw_receipt: *const svm_receipt_t) -> u64 {
+            let receipt = cast_to_rust_type!(raw_receipt, Receipt);
+            debug!("`svm_receipt_gas_used` gas_used={}", receipt.gas_used);
+
+            receipt.gas_used
+        }
+
+        /// Returns the deploy receipt outcome (`true` for success and `false` otherwise)
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_deploy_receipt_status(
+            raw_receipt: *const svm_deploy_receipt_t,
+        ) -> bool {
+            let receipt = cast_to_rust_type!(raw_receipt, DeployReceipt);
+            debug!("`svm_deploy_receipt_status` status={}", receipt.success);
+
+            receipt.success
+        }
+
+        /// Returns a pointer to the initial state of the deployed contract account.
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_deploy_receipt_new_state(
+            raw_receipt: *const svm_deploy_receipt_t,
+        ) -> *const u8 {
+            let receipt = cast_to_rust_type!(raw_receipt, DeployReceipt);
+
+            if receipt.success {
+                let state = receipt.new_state.as_ref().unwrap();
+                state.as_ptr()
+            } else {
+                panic!("method not allowed to be called when the contract deploy failed");
+            }
+        }
+
+        /// Returns the gas consumed by the contract constructor (succeeded or failed).
+        #[must_use]
+        #[no_mangle]
+        pub unsafe extern "C" fn svm_deploy_receipt_gas_used(
+            raw_receipt: *const svm_deploy_receipt_t,
+        ) -> u64 {
+            let receipt = cast_to_rust_type!(raw_receipt, DeployReceipt);
+            debug!(
+                "`svm_deploy_receipt_gas_used` gas_used={}",
+                receipt.gas_used
+            );
+
+            receipt.gas_used
+        }
     };
 }
@@ -12,6 +12,14 @@ pub struct svm_contract_t;
 #[repr(C)]
 pub struct svm_transaction_t;
 
+/// `*const svm_deploy_receipt_t` is a raw pointer to a Rust `DeployReceipt` struct.
+#[repr(C)]
+pub struct svm_deploy_receipt_t;
+
+/// `*const svm_upgrade_t` is a raw pointer to a Rust `svm_contract::upgrade::Upgrade` struct.
+#[repr(C)]
+pub struct svm_upgrade_t;
+
 /// `*const svm_receipt_t` is a raw pointer to a Rust `Receipt` struct.
 #[repr(C)]
 pub struct svm_receipt_t;
@@ -0,0 +1,81 @@
+use svm_compiler::gas::GasEstimate;
+
+/// A receipt accessor error (see `svm_receipt_callee / svm_receipt_log / svm_receipt_transfer`)
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum ReceiptError {
+    CalleeIndexOutOfBounds(u32, usize),
+    LogIndexOutOfBounds(u32, usize),
+    TransferIndexOutOfBounds(u32, usize),
+}
+
+impl std::error::Error for ReceiptError {
+    fn description(&self) -> &'static str {
+        match self {
+            ReceiptError::CalleeIndexOutOfBounds(..) => "Callee index out of bounds",
+            ReceiptError::LogIndexOutOfBounds(..) => "Log index out of bounds",
+            ReceiptError::TransferIndexOutOfBounds(..) => "Transfer index out of bounds",
+        }
+    }
+}
+
+impl std::fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            ReceiptError::CalleeIndexOutOfBounds(idx, count) => format!(
+                "Callee index `{}` out of bounds (callees count: `{}`)",
+                idx, count
+            ),
+            ReceiptError::LogIndexOutOfBounds(idx, count) => format!(
+                "Log index `{}` out of bounds (logs count: `{}`)",
+                idx, count
+            ),
+            ReceiptError::TransferIndexOutOfBounds(idx, count) => format!(
+                "Transfer index `{}` out of bounds (transfers count: `{}`)",
+                idx, count
+            ),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for ReceiptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
+
+/// The function passed to `svm_contract_export_gas` has no fixed gas estimation
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum ExportGasError {
+    NotFixed(String, GasEstimate),
+}
+
+impl std::error::Error for ExportGasError {
+    fn description(&self) -> &'static str {
+        match self {
+            ExportGasError::NotFixed(..) => "Function gas isn't fixed",
+        }
+    }
+}
+
+impl std::fmt::Display for ExportGasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            ExportGasError::NotFixed(func_name, estimate) => format!(
+                "Function `{}` gas isn't fixed (estimation: `{:?}`)",
+                func_name, estimate
+            ),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for ExportGasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
@@ -14,3 +14,6 @@ pub mod c_types;
 
 /// C-API utilities to be used primarily for tests / integration-tests
 pub mod c_utils;
+
+/// C-API errors (see `wasmer_last_error_message`)
+pub mod error;
@@ -21,6 +21,7 @@ use wasmer_runtime_c_api::{
     import::wasmer_import_t,
     instance::{wasmer_instance_context_t, wasmer_module_import_instantiate},
     module::wasmer_module_t,
+    wasmer_result_t,
 };
 use wasmer_runtime_core::types::Type;
 
@@ -144,6 +145,12 @@ fn runtime_tx_exec_changing_state() {
             State::from(0).as_ptr() as _, // `raw_state: *const c_void`
             5,                            // `max_pages:  libc::c_int`
             100,                          // `max_pages_slices: libc::c_int`
+            1_000_000,                    // `gas_limit: u64`
+            None,                         // `state_resolver: Option<StateResolver>`
+            None,                         // `balance_resolver: Option<BalanceResolver>`
+            0,                            // `layer_id: u64`
+            0,                            // `layer_timestamp: u64`
+            std::ptr::null(),             // `beacon: *const c_void`
             full_node_as_ptr(&node),      // `node_data_ptr:: *const c_void`
             std::ptr::null_mut(),         // `imports: *mut wasmer_import_t`
             0,                            // `imports_len: libc::c_int`
@@ -165,13 +172,24 @@ fn runtime_tx_exec_changing_state() {
         let _ = svm_transaction_exec(raw_receipt, *raw_tx, *raw_import_object);
 
         assert_eq!(true, svm_receipt_status(*raw_receipt));
+        assert!(svm_receipt_gas_used(*raw_receipt) > 0);
+        assert_eq!(0, svm_receipt_callees_count(*raw_receipt));
+        assert_eq!(0, svm_receipt_logs_count(*raw_receipt));
+
+        // out of bounds indexes are rejected
+        let mut raw_addr = std::ptr::null();
+        let mut raw_state = std::ptr::null();
+        match svm_receipt_callee(*raw_receipt, 0, &mut raw_addr, &mut raw_state) {
+            wasmer_result_t::WASMER_ERROR => (),
+            _ => unreachable!(),
+        }
 
         let new_state = svm_receipt_new_state(*raw_receipt);
         let new_state = State::from(new_state);
 
         // 3) asserting data has been persisted as expected
         let pages_storage =
-            svm_runtime::gen_rocksdb_pages_storage!(addr, new_state, 5, "tests-contract-storage");
+            svm_runtime::gen_rocksdb_cf_pages_storage!(addr, new_state, 5, "tests-contract-db");
         let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 5);
         let mut storage = PageSliceCache::new(page_cache, 100);
 
@@ -195,6 +213,50 @@ fn runtime_tx_exec_changing_state() {
     }
 }
 
+#[test]
+fn runtime_contract_export_gas() {
+    unsafe {
+        let raw_contract = alloc_raw_contract!();
+        let author_addr = Address::from([0xFF; 20].as_ref());
+
+        let bytes = build_raw_contract!("wasm/export_gas.wast", &author_addr);
+        let _ = svm_contract_build(
+            raw_contract,
+            bytes.as_ptr() as *const c_void,
+            bytes.len() as u64,
+        );
+        let raw_addr = svm_contract_compute_address(*raw_contract);
+        let _ = svm_contract_store(*raw_contract, raw_addr);
+
+        let mut gas = 0;
+
+        // `get_local` + `i32.const` x 2 + `call` (the `reg_write_be_i64` vmcall has no gas of its own)
+        let func_name = "set_reg";
+        match svm_contract_export_gas(
+            &mut gas,
+            raw_addr,
+            func_name.as_ptr() as *const c_void,
+            func_name.len() as u32,
+        ) {
+            wasmer_result_t::WASMER_OK => assert_eq!(1 + 1 + 1 + 10, gas),
+            _ => unreachable!(),
+        }
+
+        // writing to the storage may grow it, so its gas isn't fixed
+        for func_name in ["store", "no_such_func"].iter() {
+            match svm_contract_export_gas(
+                &mut gas,
+                raw_addr,
+                func_name.as_ptr() as *const c_void,
+                func_name.len() as u32,
+            ) {
+                wasmer_result_t::WASMER_ERROR => (),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
+
 #[test]
 fn runtime_node_vmcalls() {
     unsafe {
@@ -236,6 +298,12 @@ fn runtime_node_vmcalls() {
             State::from(0).as_ptr() as _, // `raw_state: *const u8`,
             5,                            // `max_pages: libc::c_int`
             100,                          // `max_pages_slices: libc::c_int`
+            1_000_000,                    // `gas_limit: u64`
+            None,                         // `state_resolver: Option<StateResolver>`
+            None,                         // `balance_resolver: Option<BalanceResolver>`
+            0,                            // `layer_id: u64`
+            0,                            // `layer_timestamp: u64`
+            std::ptr::null(),             // `beacon: *const c_void`
             full_node_as_ptr(&node),      // `node_data_ptr:: *const c_void`
             imports.as_mut_ptr(),         // `imports: *mut wasmer_import_t`
             imports.len() as _,           // `imports_len: libc::c_int`
@@ -0,0 +1,22 @@
+(module
+  ;; import `svm` vmcalls
+  (func $reg_write_be_i64 (import "svm" "reg_write_be_i64") (param i64 i32 i32))
+  (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported functions to be estimated
+  (func (export "set_reg") (param i64)
+        get_local 0  ;; value
+        i32.const 64 ;; reg_bits
+        i32.const 0  ;; reg_idx
+        call $reg_write_be_i64)
+
+  (func (export "store")
+        i32.const 64 ;; src_reg_bits
+        i32.const 0  ;; src_reg_idx
+        i32.const 8  ;; len
+        i32.const 0  ;; dst_page
+        i32.const 0  ;; dst_slice
+        i32.const 0  ;; dst_offset
+        call $storage_write_from_reg))
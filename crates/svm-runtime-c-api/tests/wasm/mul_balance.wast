@@ -5,7 +5,7 @@
   (func $vmcall_get_balance  (import "node" "vmcall_get_balance") (param i32 i32) (result i64))
   (func $vmcall_set_balance  (import "node" "vmcall_set_balance") (param i64 i32 i32))
 
-  (memory 1)  ;; memory `0` (default) is initialized with one page
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
 
   ;; exported function to be called
   (func (export "mul_balance") (param i64)
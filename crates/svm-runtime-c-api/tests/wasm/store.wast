@@ -3,7 +3,7 @@
   (func $reg_write_be_i64 (import "svm" "reg_write_be_i64") (param i64 i32 i32))
   (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32 i32))
 
-  (memory 1)  ;; memory `0` (default) is initialized with one page
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
 
   ;; exported function to be called
   (func (export "run") (param i64)
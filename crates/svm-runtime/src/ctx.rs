@@ -7,6 +7,10 @@ use svm_storage::traits::PageCache;
 use svm_storage::PageSliceCache;
 
 use crate::ctx_data_wrapper::SvmCtxDataWrapper;
+use crate::event::Event;
+use crate::opts::{Layer, Opts};
+
+use svm_common::{Address, Balance, State};
 
 use log::debug;
 
@@ -25,6 +29,35 @@ pub const REGS_256_COUNT: usize = 4;
 /// The number of allocated `SvmReg512` registers for each `SvmCtx`
 pub const REGS_512_COUNT: usize = 4;
 
+/// The maximum depth of nested contract-to-contract calls (the transaction's contract is at depth `1`)
+pub const MAX_CALL_DEPTH: usize = 8;
+
+/// The gas charged for each page the contract storage grows by (beyond the pages allocated at deploy time)
+pub const PAGE_GROW_GAS: u64 = 1_000;
+
+/// The gas charged for instantiating each of the contract dependencies (on each execution of the contract)
+pub const DEP_INSTANTIATE_GAS: u64 = 10_000;
+
+/// The gas charged for each event emitted by the contract (on top of its data bytes gas)
+pub const EVENT_GAS: u64 = 1_000;
+
+/// The gas charged for each byte of an emitted event data
+pub const EVENT_DATA_BYTE_GAS: u64 = 10;
+
+/// The execution environment of the running contract (see the `env` vmcalls)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecEnv {
+    /// The account calling the running contract: the transaction sender,
+    /// or the calling contract (for contracts called by other contracts)
+    pub sender: Address,
+
+    /// The address of the running contract
+    pub contract: Address,
+
+    /// The layer the transaction is executed in (see `Opts#layer`)
+    pub layer: Layer,
+}
+
 /// `SvmCtx` is a container for the accessible data by `wasmer` instances
 /// * `node_data` - A pointer to the *node* data
 /// * `regs_32`   - A static array (`REGS_32_COUNT` elements)  of `SvmReg32`
@@ -32,6 +65,14 @@ pub const REGS_512_COUNT: usize = 4;
 /// * `regs_160`  - A static array (`REGS_160_COUNT` elements) of `SvmReg160`
 /// * `regs_256`  - A static array (`REGS_256_COUNT` elements) of `SvmReg256`
 /// * `regs_512`  - A static array (`REGS_512_COUNT` elements) of `SvmReg512`
+/// * `opts`      - The runtime options the instance was created with
+/// * `call_stack`     - The addresses of the contracts on the contracts call-stack
+/// * `callees_states` - The new states of the contracts called by the running contract
+/// * `logs`      - The events emitted by the running contract (and the contracts it called)
+/// * `admins`    - The admins of the running contract
+/// * `value`     - The value transferred to the running contract by the transaction sender
+/// * `env`       - The execution environment of the running contract (sender, address, layer)
+/// * `libs`      - The instances of the running contract dependencies (libraries)
 /// * `storage`   - An instance of `PageSliceCache`
 #[repr(C)]
 pub struct SvmCtx<PC: PageCache> {
@@ -54,6 +95,38 @@ pub struct SvmCtx<PC: PageCache> {
     /// An array that holds the `SvmReg512` registers
     pub regs_512: [SvmReg; REGS_512_COUNT],
 
+    /// The runtime options the instance was created with.
+    /// Their `gas_limit` caps the instance remaining gas, set by the runtime right after instantiation.
+    pub opts: Opts,
+
+    /// The addresses of the contracts on the current contracts call-stack.
+    /// The last address is the running contract's address.
+    pub call_stack: Vec<Address>,
+
+    /// The new states of the contracts called (directly or indirectly) by the running contract,
+    /// in the order they were first called. These states are adopted only if the transaction succeeds.
+    pub callees_states: Vec<(Address, State)>,
+
+    /// The events emitted by the running contract and by the contracts it called, in emission order.
+    /// These events are surfaced only if the transaction succeeds.
+    pub logs: Vec<Event>,
+
+    /// The admins of the running contract (as specified when the contract has been deployed)
+    pub admins: Vec<Address>,
+
+    /// The value (in coins) transferred to the running contract by the transaction sender.
+    /// Zero for contracts called by other contracts. The transfer is applied only if the transaction succeeds.
+    pub value: Balance,
+
+    /// The execution environment of the running contract.
+    /// Populated from the executed transaction and the host provided layer (see `Opts#layer`).
+    pub env: ExecEnv,
+
+    /// The instances of the running contract dependencies (in the order the dependencies were declared).
+    /// Their exported functions are linked into the contract import object under modules `dep0`, `dep1`, ...
+    /// They charge their gas from the contract instance remaining gas (see `svm_compiler::gas::share_gas_left`).
+    pub libs: Vec<wasmer_runtime::Instance>,
+
     /// An accessor to the contract's storage (of type `PageSliceCache`)
     pub storage: PageSliceCache<PC>,
 }
@@ -67,8 +140,10 @@ where
 {
     /// Initializes a new empty `SvmCtx`
     ///
+    /// * `opts` - the runtime options
+    ///
     /// * `storage` - a mutably borrowed `PageSliceCache`
-    pub fn new(data_wrapper: SvmCtxDataWrapper, storage: PageSliceCache<PC>) -> Self {
+    pub fn new(data_wrapper: SvmCtxDataWrapper, opts: Opts, storage: PageSliceCache<PC>) -> Self {
         let regs_32 = alloc_regs!(32, REGS_32_COUNT);
         let regs_64 = alloc_regs!(64, REGS_64_COUNT);
         let regs_160 = alloc_regs!(160, REGS_160_COUNT);
@@ -82,9 +157,59 @@ where
             regs_160,
             regs_256,
             regs_512,
+            opts,
+            call_stack: Vec::new(),
+            callees_states: Vec::new(),
+            logs: Vec::new(),
+            admins: Vec::new(),
+            value: Balance(0),
+            env: ExecEnv {
+                sender: Address::from(0),
+                contract: Address::from(0),
+                layer: opts.layer,
+            },
+            libs: Vec::new(),
             storage,
         }
     }
+
+    /// Records the new state of a called contract (overriding a previously recorded state)
+    pub fn set_callee_state(&mut self, addr: Address, state: State) {
+        let entry = self.callees_states.iter_mut().find(|(a, _)| *a == addr);
+
+        match entry {
+            Some((_, old_state)) => *old_state = state,
+            None => self.callees_states.push((addr, state)),
+        }
+    }
+
+    /// Returns the balance of the running contract: its balance resolved by the node (see `Opts#balance_resolver`)
+    /// along with the value transferred to it by the running transaction.
+    pub fn get_self_balance(&self) -> Balance {
+        let resolved = match (self.opts.balance_resolver, self.call_stack.last()) {
+            (Some(resolver), Some(addr)) => {
+                let balance_ptr =
+                    unsafe { resolver(self.node_data, addr.as_ptr() as *const c_void) };
+
+                if balance_ptr.is_null() {
+                    Balance(0)
+                } else {
+                    Balance::from(balance_ptr as *const u8)
+                }
+            }
+            _ => Balance(0),
+        };
+
+        Balance(resolved.0.saturating_add(self.value.0))
+    }
+
+    /// Returns the recorded new state of a called contract (if any)
+    pub fn get_callee_state(&self, addr: &Address) -> Option<&State> {
+        self.callees_states
+            .iter()
+            .find(|(a, _)| a == addr)
+            .map(|(_, state)| state)
+    }
 }
 
 impl<PC> Drop for SvmCtx<PC>
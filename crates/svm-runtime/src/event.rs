@@ -0,0 +1,17 @@
+use svm_common::Address;
+
+/// An event (a.k.a log) emitted by a running contract (see the `emit_event_from_mem / emit_event_from_reg` vmcalls)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// The emitting contract account address
+    pub addr: Address,
+
+    /// The event topic (the content of a `SvmReg256` register)
+    pub topic: Vec<u8>,
+
+    /// The event payload data
+    pub data: Vec<u8>,
+
+    /// The number of layers the event should be kept for (following the layer it has been emitted at)
+    pub expiration: u32,
+}
@@ -25,5 +25,11 @@ pub mod macros;
 /// to be injected into `wasmer` instances running in the `svm`.
 pub mod vmcalls;
 
+/// Implements the events (a.k.a logs) emitted by running contracts.
+pub mod event;
+
+/// Caches the compiled `wasmer` modules of the executed contracts.
+pub mod module_cache;
+
 /// Options when spawning a new `svm` runtime instance
 pub mod opts;
@@ -0,0 +1,39 @@
+/// Views the storage page `page_idx` of the running `svm` instance as a collection, and runs `$f` over it.
+/// The page isn't written back, so `$f` is expected to only read the collection.
+///
+/// Returns an error when `$f` fails (see `ContractExecError::CollectionFailed`).
+#[macro_export]
+macro_rules! svm_collection_read {
+    ($data: expr, $page_idx: expr, $PC: path, $f: expr) => {{
+        use svm_storage::page::PageIndex;
+        use $crate::runtime::ContractExecError;
+
+        let storage = $crate::wasmer_data_storage!($data, $PC);
+        let mut page = storage.read_page(PageIndex($page_idx as u32));
+
+        $f(&mut page[..]).map_err(ContractExecError::CollectionFailed)
+    }};
+}
+
+/// Views the storage page `page_idx` of the running `svm` instance as a collection, runs `$f` over it,
+/// and writes the modified page back (not persisting yet). See `svm_storage_write`.
+///
+/// Returns an error when `$f` fails (see `ContractExecError::CollectionFailed`),
+/// when the page index is out of range or when the storage growth runs out of gas.
+#[macro_export]
+macro_rules! svm_collection_write {
+    ($ctx: expr, $page_idx: expr, $PC: path, $f: expr) => {{
+        use svm_storage::page::PageIndex;
+        use $crate::runtime::ContractExecError;
+
+        let page_idx = PageIndex($page_idx as u32);
+        let mut page = $crate::wasmer_data_storage!($ctx.data, $PC).read_page(page_idx);
+
+        match $f(&mut page[..]) {
+            Err(e) => Err(ContractExecError::CollectionFailed(e)),
+            Ok(res) => $crate::svm_storage_write!($ctx, $PC, |storage| {
+                storage.write_page(page_idx, &page).map(|_| res)
+            }),
+        }
+    }};
+}
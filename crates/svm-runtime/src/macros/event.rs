@@ -0,0 +1,51 @@
+/// Appends a new `Event` emitted by the running contract into the `SvmCtx` logs.
+/// The event topic is the content of the `SvmReg256` register indexed `topic_reg_idx`.
+///
+/// Charges `EVENT_GAS` along with `EVENT_DATA_BYTE_GAS` for each byte of the event data.
+/// Returns an error when `expiration` is negative or when the instance runs out of gas.
+#[macro_export]
+macro_rules! svm_emit_event {
+    ($ctx: expr, $topic_reg_idx: expr, $event_data: expr, $expiration: expr, $PC: path) => {{
+        use $crate::ctx::{SvmCtx, EVENT_DATA_BYTE_GAS, EVENT_GAS};
+        use $crate::event::Event;
+        use $crate::runtime::ContractExecError;
+
+        let data: Vec<u8> = $event_data;
+        let expiration: i32 = $expiration;
+
+        if expiration < 0 {
+            return Err(ContractExecError::InvalidEventExpiration(expiration));
+        }
+
+        let gas = EVENT_GAS + (data.len() as u64) * EVENT_DATA_BYTE_GAS;
+        let gas_left = svm_compiler::gas::get_gas_left_ctx($ctx);
+
+        if gas > gas_left {
+            svm_compiler::gas::set_gas_left_ctx($ctx, 0);
+            return Err(ContractExecError::OutOfGas);
+        }
+
+        svm_compiler::gas::set_gas_left_ctx($ctx, gas_left - gas);
+
+        let topic = $crate::wasmer_data_reg!($ctx.data, 256, $topic_reg_idx, $PC).view();
+        let svm_ctx: &mut SvmCtx<$PC> = $crate::cast_wasmer_data_to_svm_ctx!($ctx.data, $PC);
+
+        let addr = svm_ctx.env.contract.clone();
+
+        log::debug!(
+            "emitting event (addr={:?}, topic={:?}, expiration={})",
+            addr,
+            topic,
+            expiration
+        );
+
+        svm_ctx.logs.push(Event {
+            addr,
+            topic,
+            data,
+            expiration: expiration as u32,
+        });
+
+        Ok(())
+    }};
+}
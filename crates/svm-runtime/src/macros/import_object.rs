@@ -12,7 +12,7 @@ macro_rules! create_svm_ctx {
         let page_cache = $page_cache_ctor(pages, $opts.max_pages);
         let storage = PageSliceCache::new(page_cache, $opts.max_pages_slices);
 
-        let ctx = SvmCtx::<$PC>::new($node_data, storage);
+        let ctx = SvmCtx::<$PC>::new($node_data, $opts, storage);
         let boxed_ctx = Box::new(ctx);
 
         let ctx_ptr = Box::leak(boxed_ctx);
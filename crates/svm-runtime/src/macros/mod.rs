@@ -7,9 +7,15 @@ mod register;
 #[macro_use]
 mod storage;
 
+#[macro_use]
+mod collections;
+
 #[macro_use]
 mod ctx;
 
+#[macro_use]
+mod event;
+
 #[macro_use]
 mod import_object;
 
@@ -63,6 +69,10 @@ mod tests {
             let opts = crate::opts::Opts {
                 max_pages: max_pages as usize,
                 max_pages_slices: max_pages_slices as usize,
+                gas_limit: 1_000_000,
+                state_resolver: None,
+                balance_resolver: None,
+                layer: crate::opts::Layer::default(),
             };
 
             create_svm_ctx!(
@@ -150,7 +160,9 @@ mod tests {
         let layout = svm_page_slice_layout!(1, 0, 100, 3);
 
         assert_eq!(None, storage.read_page_slice(&layout));
-        storage.write_page_slice(&layout, &vec![10, 20, 30]);
+        storage
+            .write_page_slice(&layout, &vec![10, 20, 30])
+            .unwrap();
         assert_eq!(vec![10, 20, 30], storage.read_page_slice(&layout).unwrap());
     }
 
@@ -163,7 +175,9 @@ mod tests {
         let reg0 = wasmer_data_reg!(data, 64, 0, MemMerklePageCache);
         let storage = wasmer_data_storage!(data, MemMerklePageCache);
 
-        storage.write_page_slice(&layout, &vec![10, 20, 30]);
+        storage
+            .write_page_slice(&layout, &vec![10, 20, 30])
+            .unwrap();
 
         // reading from page `1`, slice `0`, 3 bytes starting from offset `100`
         let slice = svm_read_page_slice!(storage, 1, 0, 100, 3);
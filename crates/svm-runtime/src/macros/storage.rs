@@ -28,13 +28,45 @@ macro_rules! svm_read_page_slice {
     }};
 }
 
-/// Calls `write_page_slice` on the given `PageSliceCache`
+/// Calls `write_page_slice` on the given `PageSliceCache` (returns its result)
 #[macro_export]
 macro_rules! svm_write_page_slice {
     ($storage: expr, $page_idx: expr, $slice_idx: expr, $offset: expr, $len: expr, $data: expr) => {{
         let layout = $crate::svm_page_slice_layout!($page_idx, $slice_idx, $offset, $len);
 
-        $storage.write_page_slice(&layout, $data);
+        $storage.write_page_slice(&layout, $data)
+    }};
+}
+
+/// Evaluates `$write` having `$storage` bound to the `PageSliceCache` of the running `svm` instance,
+/// and charges `PAGE_GROW_GAS` gas for each page the storage has grown by.
+///
+/// Returns `ContractExecError::StorageOutOfRange` when `$write` fails (nothing has been written),
+/// and `ContractExecError::OutOfGas` when the remaining gas doesn't cover the storage growth.
+#[macro_export]
+macro_rules! svm_storage_write {
+    ($ctx: expr, $PC: path, |$storage: ident| $write: expr) => {{
+        use $crate::runtime::ContractExecError;
+
+        let $storage = $crate::wasmer_data_storage!($ctx.data, $PC);
+        let pages_count = $storage.pages_count();
+
+        match $write {
+            Err(e) => Err(ContractExecError::StorageOutOfRange(e)),
+            Ok(res) => {
+                let new_pages = u64::from($storage.pages_count() - pages_count);
+                let gas = new_pages * $crate::ctx::PAGE_GROW_GAS;
+                let gas_left = svm_compiler::gas::get_gas_left_ctx($ctx);
+
+                if gas > gas_left {
+                    svm_compiler::gas::set_gas_left_ctx($ctx, 0);
+                    Err(ContractExecError::OutOfGas)
+                } else {
+                    svm_compiler::gas::set_gas_left_ctx($ctx, gas_left - gas);
+                    Ok(res)
+                }
+            }
+        }
     }};
 }
 
@@ -0,0 +1,355 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use log::{debug, warn};
+
+use svm_compiler::CompiledProgram;
+use svm_contract::types::CodeHash;
+use wasmer_runtime::Module;
+use wasmer_runtime_core::error::CompileResult;
+
+/// The default number of compiled modules kept in memory by a runtime
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 100;
+
+/// Counters of the `ModuleCache` lookups
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ModuleCacheStats {
+    /// Lookups satisfied by a module kept in memory
+    pub hits: u64,
+
+    /// Lookups satisfied by loading a module serialized under the cache directory
+    pub disk_hits: u64,
+
+    /// Lookups that required compiling the wasm program
+    pub misses: u64,
+}
+
+/// Caches compiled `wasmer` modules (along with the gas estimation of their exported functions)
+/// by the `CodeHash` of their wasm program.
+///
+/// * Up to `capacity` modules are kept in memory. When full, the least recently used module is evicted.
+/// * When having a cache directory, compiled modules are also serialized to it (file per `CodeHash`).
+///   Modules evicted from memory (or compiled by a previous process) are loaded from there
+///   instead of being recompiled.
+/// * The file name includes the `svm_compiler::COMPILER_VERSION`, so modules serialized by
+///   another compiler version (e.g having another opcodes cost table) are never loaded (a cache miss).
+pub struct ModuleCache {
+    capacity: usize,
+    dir: Option<PathBuf>,
+    modules: HashMap<CodeHash, (CompiledProgram, u64)>,
+    tick: u64,
+    stats: ModuleCacheStats,
+}
+
+impl ModuleCache {
+    /// Creates a new cache holding up to `capacity` modules in memory.
+    /// `dir` is the (optional) directory for the serialized modules.
+    pub fn new(capacity: usize, dir: Option<&Path>) -> Self {
+        assert!(capacity > 0, "`ModuleCache` capacity must be positive");
+
+        if let Some(dir) = dir {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                warn!("failed creating modules cache directory {:?}: {}", dir, e);
+            }
+        }
+
+        Self {
+            capacity,
+            dir: dir.map(|dir| dir.to_path_buf()),
+            modules: HashMap::new(),
+            tick: 0,
+            stats: ModuleCacheStats::default(),
+        }
+    }
+
+    /// Returns the compiled program of `wasm` (whose code hash is `hash`).
+    /// The program is compiled (using `svm_compiler::compile_program_with_gas`) only on a cache miss.
+    /// The gas estimation of its exported functions treats the vmcalls as having their fixed gas
+    /// (see `vmcalls::import_gas`).
+    pub fn get_or_compile(
+        &mut self,
+        hash: &CodeHash,
+        wasm: &[u8],
+    ) -> CompileResult<CompiledProgram> {
+        self.tick += 1;
+
+        if let Some((program, last_used)) = self.modules.get_mut(hash) {
+            debug!("module cache hit (hash={:?})", hash);
+
+            self.stats.hits += 1;
+            *last_used = self.tick;
+
+            return Ok(program.clone());
+        }
+
+        let program = match self.disk_load(hash, wasm) {
+            Some(program) => {
+                debug!("module cache disk hit (hash={:?})", hash);
+
+                self.stats.disk_hits += 1;
+                program
+            }
+            None => {
+                debug!("module cache miss (hash={:?})", hash);
+
+                self.stats.misses += 1;
+
+                let program =
+                    svm_compiler::compile_program_with_gas(wasm, crate::vmcalls::import_gas)?;
+                self.disk_store(hash, &program.module);
+
+                program
+            }
+        };
+
+        self.insert(hash, program.clone());
+
+        Ok(program)
+    }
+
+    /// The number of modules kept in memory
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns whether there are no modules kept in memory
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Returns the cache lookups counters
+    pub fn stats(&self) -> ModuleCacheStats {
+        self.stats
+    }
+
+    /// Zeroes the cache lookups counters
+    pub fn reset_stats(&mut self) {
+        self.stats = ModuleCacheStats::default();
+    }
+
+    fn insert(&mut self, hash: &CodeHash, program: CompiledProgram) {
+        if self.modules.len() == self.capacity {
+            self.evict_lru();
+        }
+
+        self.modules.insert(*hash, (program, self.tick));
+    }
+
+    fn evict_lru(&mut self) {
+        let lru = self
+            .modules
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(hash, _)| *hash);
+
+        if let Some(hash) = lru {
+            debug!("module cache evicting (hash={:?})", hash);
+
+            self.modules.remove(&hash);
+        }
+    }
+
+    fn module_path(&self, hash: &CodeHash) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(module_file_name(hash, svm_compiler::COMPILER_VERSION)))
+    }
+
+    /// Loads the module serialized under the cache directory. Only the module is persisted,
+    /// so the gas estimation of its exported functions is recomputed (it doesn't require compiling `wasm`).
+    fn disk_load(&self, hash: &CodeHash, wasm: &[u8]) -> Option<CompiledProgram> {
+        let path = self.module_path(hash)?;
+        let bytes = std::fs::read(&path).ok()?;
+
+        // the modules directory is owned by the cache, so its files are the output of `serialize_program`
+        let module = match unsafe { svm_compiler::deserialize_program(&bytes) } {
+            Ok(module) => module,
+            Err(e) => {
+                warn!("failed loading cached module {:?}: {:?}", path, e);
+                return None;
+            }
+        };
+
+        let exports_gas =
+            svm_compiler::gas::estimate_program_gas(wasm, crate::vmcalls::import_gas).ok()?;
+
+        Some(CompiledProgram {
+            module,
+            exports_gas,
+        })
+    }
+
+    fn disk_store(&self, hash: &CodeHash, module: &Module) {
+        let path = match self.module_path(hash) {
+            Some(path) => path,
+            None => return,
+        };
+
+        let res = svm_compiler::serialize_program(module)
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|bytes| std::fs::write(&path, bytes).map_err(|e| e.to_string()));
+
+        if let Err(e) = res {
+            warn!("failed persisting module {:?}: {}", path, e);
+        }
+    }
+}
+
+fn module_file_name(hash: &CodeHash, version: u32) -> String {
+    let name: String = hash.0.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    format!("{}.v{}.module", name, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_compiler::gas::GasEstimate;
+
+    fn wasm(func_name: &str) -> Vec<u8> {
+        let input = format!(
+            r#"(module
+                 (func (export "{}") (result i32)
+                   i32.const 1))"#,
+            func_name
+        );
+
+        wabt::wat2wasm(&input).unwrap()
+    }
+
+    #[test]
+    fn module_cache_estimates_exports_gas() {
+        let mut cache = ModuleCache::new(10, None);
+
+        let input = r#"(module
+                         (func $get_layer_id (import "svm" "get_layer_id") (result i64))
+                         (func $contract_call (import "svm" "contract_call") (param i32 i32) (result i64))
+
+                         (func (export "layer") (result i64)
+                           call $get_layer_id)
+
+                         (func (export "call") (result i64)
+                           i32.const 0
+                           i32.const 0
+                           call $contract_call))"#;
+
+        let wasm = wabt::wat2wasm(input).unwrap();
+        let program = cache.get_or_compile(&CodeHash([1; 32]), &wasm).unwrap();
+
+        // the `svm` vmcalls have a fixed gas, except for `contract_call` (`call` opcode costs `10`)
+        assert_eq!(Some(GasEstimate::Fixed(10)), program.export_gas("layer"));
+        assert_eq!(
+            Some(GasEstimate::HasImportedCalls),
+            program.export_gas("call")
+        );
+        assert_eq!(None, program.export_gas("no_such_func"));
+    }
+
+    #[test]
+    fn module_cache_compiles_once() {
+        let mut cache = ModuleCache::new(10, None);
+        let wasm = wasm("run");
+        let hash = CodeHash([1; 32]);
+
+        assert!(cache.get_or_compile(&hash, &wasm).is_ok());
+        assert!(cache.get_or_compile(&hash, &wasm).is_ok());
+
+        let expected = ModuleCacheStats {
+            hits: 1,
+            disk_hits: 0,
+            misses: 1,
+        };
+        assert_eq!(expected, cache.stats());
+        assert_eq!(1, cache.len());
+    }
+
+    #[test]
+    fn module_cache_evicts_least_recently_used() {
+        let mut cache = ModuleCache::new(2, None);
+        let (hash1, wasm1) = (CodeHash([1; 32]), wasm("a"));
+        let (hash2, wasm2) = (CodeHash([2; 32]), wasm("b"));
+        let (hash3, wasm3) = (CodeHash([3; 32]), wasm("c"));
+
+        cache.get_or_compile(&hash1, &wasm1).unwrap();
+        cache.get_or_compile(&hash2, &wasm2).unwrap();
+
+        // `hash1` is now more recently used than `hash2`
+        cache.get_or_compile(&hash1, &wasm1).unwrap();
+
+        // evicts `hash2`
+        cache.get_or_compile(&hash3, &wasm3).unwrap();
+        assert_eq!(2, cache.len());
+
+        cache.reset_stats();
+        cache.get_or_compile(&hash1, &wasm1).unwrap();
+        cache.get_or_compile(&hash2, &wasm2).unwrap();
+
+        let expected = ModuleCacheStats {
+            hits: 1,
+            disk_hits: 0,
+            misses: 1,
+        };
+        assert_eq!(expected, cache.stats());
+    }
+
+    #[test]
+    fn module_cache_loads_serialized_module() {
+        let dir = std::env::temp_dir().join("svm-module-cache-test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let wasm = wasm("run");
+        let hash = CodeHash([1; 32]);
+
+        let mut cache = ModuleCache::new(10, Some(&dir));
+        cache.get_or_compile(&hash, &wasm).unwrap();
+
+        // a new cache (e.g of a restarted node) finds the module on disk
+        let mut cache = ModuleCache::new(10, Some(&dir));
+        let program = cache.get_or_compile(&hash, &wasm).unwrap();
+
+        // the exported functions gas estimation is recomputed for a module loaded from disk
+        assert_eq!(Some(GasEstimate::Fixed(1)), program.export_gas("run"));
+
+        let expected = ModuleCacheStats {
+            hits: 0,
+            disk_hits: 1,
+            misses: 0,
+        };
+        assert_eq!(expected, cache.stats());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn module_cache_ignores_module_of_another_compiler_version() {
+        let dir = std::env::temp_dir().join("svm-module-cache-version-test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let wasm = wasm("run");
+        let hash = CodeHash([1; 32]);
+
+        let mut cache = ModuleCache::new(10, Some(&dir));
+        cache.get_or_compile(&hash, &wasm).unwrap();
+
+        // the module on disk now appears to be serialized by a previous compiler version
+        let version = svm_compiler::COMPILER_VERSION;
+        std::fs::rename(
+            dir.join(module_file_name(&hash, version)),
+            dir.join(module_file_name(&hash, version - 1)),
+        )
+        .unwrap();
+
+        let mut cache = ModuleCache::new(10, Some(&dir));
+        cache.get_or_compile(&hash, &wasm).unwrap();
+
+        let expected = ModuleCacheStats {
+            hits: 0,
+            disk_hits: 0,
+            misses: 1,
+        };
+        assert_eq!(expected, cache.stats());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
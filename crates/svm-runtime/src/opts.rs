@@ -1,9 +1,61 @@
+use std::ffi::c_void;
+
+/// Resolves the current state of a contract called by another contract (see the `contract_call` vmcall).
+///
+/// * `node_data` - A pointer to the *node* data
+/// * `addr`      - A pointer to the called contract address (`Address::len()` bytes)
+///
+/// Returns a pointer to the contract's `State` (`State::len()` bytes),
+/// or `NULL` when the contract state can't be resolved.
+pub type StateResolver =
+    unsafe extern "C" fn(node_data: *const c_void, addr: *const c_void) -> *const c_void;
+
+/// Resolves the current balance of an account (see the `get_self_balance` vmcall).
+///
+/// * `node_data` - A pointer to the *node* data
+/// * `addr`      - A pointer to the account address (`Address::len()` bytes)
+///
+/// Returns a pointer to the account's `Balance` (16 bytes, Little-Endian),
+/// or `NULL` when the account has no balance.
+pub type BalanceResolver =
+    unsafe extern "C" fn(node_data: *const c_void, addr: *const c_void) -> *const c_void;
+
+/// The layer a transaction is executed in (as provided by the host).
+/// Contracts query it using the `env` vmcalls (see `ExecEnv`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Layer {
+    /// the layer id
+    pub id: u64,
+
+    /// the layer timestamp (as set by the host, for example seconds since the Unix Epoch)
+    pub timestamp: u64,
+
+    /// the layer randomness beacon
+    pub beacon: [u8; 32],
+}
+
 /// Holds settings for using the runtime.
 #[derive(Debug, Clone, Copy)]
 pub struct Opts {
-    /// maximum pages required by the contract pages storage
+    /// maximum pages of the contract pages storage.
+    /// the storage starts with the pages count recorded at deploy time, and may grow up to `max_pages`
     pub max_pages: usize,
 
     /// maximum pages required by the contract page-cache slice
     pub max_pages_slices: usize,
+
+    /// the maximum gas a contract execution may consume
+    pub gas_limit: u64,
+
+    /// resolves the states of contracts called by the executed contract.
+    /// when `None`, only contracts already called during the current transaction can be called.
+    pub state_resolver: Option<StateResolver>,
+
+    /// resolves the balances of the running contracts.
+    /// when `None`, contracts have only the value transferred to them by the executed transaction.
+    pub balance_resolver: Option<BalanceResolver>,
+
+    /// the layer the executed transaction belongs to.
+    /// contracts called by the executed contract run within the same layer.
+    pub layer: Layer,
 }
@@ -1,4 +1,9 @@
 use svm_common::Address;
+use svm_contract::error::ContractLoadError;
+use svm_contract::types::Dependency;
+use svm_kv::KVError;
+use svm_storage::collections::CollectionError;
+use svm_storage::StorageError;
 
 /// Contract execution error
 #[allow(missing_docs)]
@@ -9,6 +14,21 @@ pub enum ContractExecError {
     InstantiationFailed(Address),
     FuncNotFound(String),
     ExecFailed,
+    OutOfGas,
+    CallDepthExceeded,
+    ReentrantCall(Address),
+    CalleeStateNotFound(Address),
+    DependencyNotFound(Dependency),
+    StorageFailed(KVError),
+    StorageOutOfRange(StorageError),
+    AddressTaken(Address),
+    LoadFailed(ContractLoadError),
+    CollectionFailed(CollectionError),
+    RegisterTooSmall(i32),
+    ArgsTooLarge(usize),
+    InvalidItemSize(i32),
+    InvalidEventLength(i32),
+    InvalidEventExpiration(i32),
 }
 
 impl std::error::Error for ContractExecError {
@@ -19,6 +39,21 @@ impl std::error::Error for ContractExecError {
             ContractExecError::InstantiationFailed(_) => "Instance Instantiation failed",
             ContractExecError::FuncNotFound(_) => "Function not found",
             ContractExecError::ExecFailed => "Execution failed",
+            ContractExecError::OutOfGas => "Out of gas",
+            ContractExecError::CallDepthExceeded => "Call depth exceeded",
+            ContractExecError::ReentrantCall(_) => "Reentrant call",
+            ContractExecError::CalleeStateNotFound(_) => "Callee state not found",
+            ContractExecError::DependencyNotFound(_) => "Dependency not found",
+            ContractExecError::StorageFailed(_) => "Storage failed",
+            ContractExecError::StorageOutOfRange(_) => "Storage out of range",
+            ContractExecError::AddressTaken(_) => "Address already taken",
+            ContractExecError::LoadFailed(_) => "Contract load failed",
+            ContractExecError::CollectionFailed(_) => "Collection failed",
+            ContractExecError::RegisterTooSmall(_) => "Register too small",
+            ContractExecError::ArgsTooLarge(_) => "Function args too large",
+            ContractExecError::InvalidItemSize(_) => "Invalid item size",
+            ContractExecError::InvalidEventLength(_) => "Invalid event length",
+            ContractExecError::InvalidEventExpiration(_) => "Invalid event expiration",
         }
     }
 }
@@ -35,6 +70,40 @@ impl std::fmt::Display for ContractExecError {
             }
             ContractExecError::FuncNotFound(func) => format!("Function `{}` not found", func),
             ContractExecError::ExecFailed => "Execution failed".to_string(),
+            ContractExecError::OutOfGas => "Out of gas".to_string(),
+            ContractExecError::CallDepthExceeded => "Call depth exceeded".to_string(),
+            ContractExecError::ReentrantCall(addr) => {
+                format!("Reentrant call to contract `{:?}`", addr)
+            }
+            ContractExecError::CalleeStateNotFound(addr) => {
+                format!("State not found for called contract `{:?}`", addr)
+            }
+            ContractExecError::DependencyNotFound(dep) => {
+                format!("Contract dependency `{:?}` not found", dep)
+            }
+            ContractExecError::StorageFailed(e) => format!("Storage failed: {:?}", e),
+            ContractExecError::StorageOutOfRange(e) => format!("Storage out of range: {:?}", e),
+            ContractExecError::AddressTaken(addr) => {
+                format!("Address `{:?}` is already taken", addr)
+            }
+            ContractExecError::LoadFailed(e) => format!("Contract load failed: {:?}", e),
+            ContractExecError::CollectionFailed(e) => format!("Collection failed: {:?}", e),
+            ContractExecError::RegisterTooSmall(reg_bits) => {
+                format!("Register too small: `{}` bits", reg_bits)
+            }
+            ContractExecError::ArgsTooLarge(len) => format!(
+                "Function args too large: `{}` bytes don't fit the instance memory",
+                len
+            ),
+            ContractExecError::InvalidItemSize(size) => {
+                format!("Invalid item size: `{}` bytes", size)
+            }
+            ContractExecError::InvalidEventLength(len) => {
+                format!("Invalid event length: `{}` bytes", len)
+            }
+            ContractExecError::InvalidEventExpiration(expiration) => {
+                format!("Invalid event expiration: `{}` layers", expiration)
+            }
         };
 
         write!(f, "{}", msg)
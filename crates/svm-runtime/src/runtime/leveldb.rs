@@ -0,0 +1,94 @@
+/// Generates pages-storage instance of `LDBPages`
+///
+/// A `leveldb` database can't be opened twice. Since a contract calling another contract
+/// requires a pages-storage per contract, the opened `LDBStore` is shared (while in use).
+///
+/// Panics when the database can't be opened (the runtime can't run contracts without their storage).
+#[macro_export]
+macro_rules! gen_leveldb_pages_storage {
+    ($addr: expr, $state: expr, $pages_count: expr, $contract_storage_path: expr) => {{
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+        use std::path::{Path, PathBuf};
+        use std::rc::{Rc, Weak};
+
+        use svm_kv::leveldb::LDBStore;
+        use svm_storage::leveldb::LDBPages;
+
+        thread_local! {
+            static OPENED_STORES: RefCell<HashMap<PathBuf, Weak<RefCell<LDBStore>>>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let path = Path::new($contract_storage_path);
+
+        let kv = OPENED_STORES.with(|stores| {
+            let mut stores = stores.borrow_mut();
+
+            match stores.get(path).and_then(|kv| kv.upgrade()) {
+                Some(kv) => kv,
+                None => {
+                    let kv = match LDBStore::new(path) {
+                        Ok(kv) => Rc::new(RefCell::new(kv)),
+                        Err(e) => panic!("failed to open contracts storage: {:?}", e),
+                    };
+                    stores.insert(path.to_path_buf(), Rc::downgrade(&kv));
+
+                    kv
+                }
+            }
+        });
+
+        LDBPages::new($addr, kv, $state, $pages_count as u32)
+    }};
+}
+
+/// Wraps a `LDBPages` pages-storage by a page-cache instance of `LDBMerklePageCache`
+#[macro_export]
+macro_rules! gen_leveldb_page_cache {
+    ($pages_storage: expr, $max_pages: expr) => {{
+        use svm_storage::leveldb::LDBMerklePageCache;
+
+        LDBMerklePageCache::new($pages_storage, $max_pages)
+    }};
+}
+
+/// Generates an environment instance of type `LDBEnv`.
+/// Panics when the code database can't be opened.
+#[macro_export]
+macro_rules! gen_leveldb_env {
+    ($code_db_path: expr) => {{
+        use std::path::Path;
+        use svm_contract::leveldb::{LDBContractStore, LDBEnv};
+
+        use svm_contract::wasm::{WasmContractDeserializer as D, WasmContractSerializer as S};
+
+        let path = Path::new($code_db_path);
+        let store = match LDBContractStore::<S, D>::new(path) {
+            Ok(store) => store,
+            Err(e) => panic!("failed to open contracts code database: {:?}", e),
+        };
+
+        LDBEnv::new(store)
+    }};
+}
+
+/// Injects `leveldb` backed implementation of `svm` runtime.
+/// Requires the `svm-runtime` crate `leveldb` feature.
+#[macro_export]
+macro_rules! include_svm_leveldb_runtime {
+    ($contract_storage_path: expr, $code_db_path: expr) => {
+        $crate::include_svm_runtime!(
+            |addr, state, pages_count| $crate::gen_leveldb_pages_storage!(
+                addr,
+                state,
+                pages_count,
+                $contract_storage_path
+            ),
+            |pages_storage, max_pages| $crate::gen_leveldb_page_cache!(pages_storage, max_pages),
+            svm_storage::leveldb::LDBMerklePageCache,
+            svm_contract::leveldb::LDBEnv,
+            || $crate::gen_leveldb_env!($code_db_path)
+        );
+    };
+}
@@ -1,6 +1,8 @@
 /// Injects a `svm` runtime into current file.
 ///
 /// * `pages_storage_gen` - a function generating a new `svm_storage::traits::PagesStorage`
+///     given `(addr, state, pages_count)`. `pages_count` is the initial number of pages of a contract
+///     running for the first time (otherwise, the pages count is recorded by the state).
 ///
 /// * `page_cache_ctor` - a function generating a new `svm_storage::traits::PageCache`
 ///     wrapping the `PagesStorage` generated by `pages_storage_gen` above.
@@ -16,37 +18,143 @@ macro_rules! include_svm_runtime {
         mod runtime {
             use log::{debug, error, info};
 
-            use $crate::runtime::{ContractExecError, Receipt};
+            use $crate::runtime::{ContractExecError, DeployReceipt, Receipt, Transfer};
 
             /// Iinjects `vmcalls` module into the current file
             svm_runtime::include_svm_vmcalls!($PC);
 
-            use svm_common::{Address, State};
+            use svm_common::{Address, Balance, State};
 
             use svm_contract::{
-                env::ContractEnv,
-                error::{ContractBuildError, TransactionBuildError},
-                traits::ContractStore,
+                env::{ContractEnv, ContractEnvTypes},
+                error::{
+                    ContractBuildError, ContractStoreError, ContractUpgradeError,
+                    TransactionBuildError,
+                },
+                traits::{ContractCodeHasher, ContractStore},
                 transaction::Transaction,
+                types::CodeHash,
+                upgrade::Upgrade,
                 wasm::Contract,
             };
 
+            use std::cell::RefCell;
+
+            use $crate::module_cache::{ModuleCache, ModuleCacheStats};
+
+            thread_local! {
+                static MODULE_CACHE: RefCell<ModuleCache> = RefCell::new(ModuleCache::new(
+                    $crate::module_cache::DEFAULT_MODULE_CACHE_CAPACITY,
+                    None,
+                ));
+            }
+
+            /// Replaces the compiled modules cache (of the current thread) with an empty one.
+            ///
+            /// * `capacity` - the maximum number of compiled modules kept in memory
+            /// * `dir`      - an optional directory for persisting the compiled modules
+            pub fn module_cache_configure(capacity: usize, dir: Option<&std::path::Path>) {
+                MODULE_CACHE.with(|cache| *cache.borrow_mut() = ModuleCache::new(capacity, dir));
+            }
+
+            /// Returns the compiled modules cache (of the current thread) lookups counters
+            pub fn module_cache_stats() -> ModuleCacheStats {
+                MODULE_CACHE.with(|cache| cache.borrow().stats())
+            }
+
             #[inline(always)]
             pub fn contract_build(bytes: &[u8]) -> Result<Contract, ContractBuildError> {
                 debug!("runtime `contract_build`");
 
-                <$ENV as ContractEnv>::build_contract(bytes)
+                let contract = <$ENV as ContractEnv>::build_contract(bytes)?;
+
+                contract_deploy_validate(&contract)?;
+
+                Ok(contract)
             }
 
-            #[inline(always)]
+            /// Validates the contract `wasm` (see `svm_compiler::validate_program`).
+            ///
+            /// The contract may import only the `svm` vmcalls (see `vmcalls_namespace`),
+            /// functions of the `node` import module (these are provided by the node when executing the contract)
+            /// and functions of its dependencies (the `i`-th dependency functions are imported from module `dep{i}`).
+            ///
+            /// The dependencies must be already deployed libraries. A library may not import anything.
             pub fn contract_deploy_validate(contract: &Contract) -> Result<(), ContractBuildError> {
-                // TODO:
-                // validate the `wasm`. should use the `deterministic` feature of `wasmparser`.
-                // (avoiding floats etc.)
+                use wasmer_runtime_core::import::LikeNamespace;
+
+                debug!("runtime `contract_deploy_validate`");
+
+                let ns = vmcalls_namespace();
+                let deps_modules = (0..contract.deps.len())
+                    .map(|i| format!("dep{}", i))
+                    .collect::<Vec<String>>();
+
+                let is_known_import = |module: &str, field: &str| match module {
+                    "svm" => ns.get_export(field).is_some(),
+                    "node" => true,
+                    _ => deps_modules.iter().any(|dep| dep == module),
+                };
+
+                let res = svm_compiler::validate_program(
+                    &contract.wasm,
+                    is_known_import,
+                    svm_compiler::MAX_MEMORY_PAGES,
+                );
+
+                res.map_err(validation_error)?;
+
+                deps_deploy_validate(contract)
+            }
+
+            fn deps_deploy_validate(contract: &Contract) -> Result<(), ContractBuildError> {
+                let env = $env_gen();
+
+                env.validate_deps(contract)?;
+
+                for dep in contract.deps.iter() {
+                    let lib = env
+                        .load_dependency(dep)
+                        .map_err(ContractBuildError::LoadFailed)?
+                        .ok_or_else(|| ContractBuildError::DependencyNotFound(dep.clone()))?;
+
+                    let res = svm_compiler::validate_program(
+                        &lib,
+                        |_module, _field| false,
+                        svm_compiler::MAX_MEMORY_PAGES,
+                    );
+
+                    if res.is_err() {
+                        return Err(ContractBuildError::InvalidDependency(dep.clone()));
+                    }
+                }
 
                 Ok(())
             }
 
+            fn validation_error(e: svm_compiler::ValidationError) -> ContractBuildError {
+                use svm_compiler::ValidationError;
+
+                match e {
+                    ValidationError::InvalidWasm => ContractBuildError::InvalidWasm,
+                    ValidationError::FloatsNotAllowed => ContractBuildError::FloatsNotAllowed,
+                    ValidationError::UnsupportedOpcode(func_idx) => {
+                        ContractBuildError::UnsupportedOpcode(func_idx)
+                    }
+                    ValidationError::UnsupportedImport { module, field } => {
+                        ContractBuildError::UnsupportedImport(module, field)
+                    }
+                    ValidationError::NoExportedFunctions => ContractBuildError::NoExportedFunctions,
+                    ValidationError::MultipleMemories => ContractBuildError::MultipleMemories,
+                    ValidationError::MemoryLimitExceeded(pages) => {
+                        ContractBuildError::MemoryLimitExceeded(pages)
+                    }
+                    ValidationError::MemoryMaximumExceeded(pages) => {
+                        ContractBuildError::MemoryMaximumExceeded(pages)
+                    }
+                }
+            }
+
             #[inline(always)]
             pub fn contract_compute_address(contract: &Contract) -> Address {
                 debug!("runtime `contract_compute_address`");
@@ -54,12 +162,17 @@ macro_rules! include_svm_runtime {
                 <$ENV as ContractEnv>::compute_address(contract)
             }
 
+            /// Stores `contract` under account address `addr`.
+            /// Fails when a contract has already been stored under `addr` (see `ContractEnv::store_contract`).
             #[inline(always)]
-            pub fn contract_store(contract: &Contract, addr: &Address) {
+            pub fn contract_store(
+                contract: &Contract,
+                addr: &Address,
+            ) -> Result<(), ContractStoreError> {
                 debug!("runtime `contract_store`");
 
                 let mut env = $env_gen();
-                env.store_contract(contract, addr);
+                env.store_contract(contract, addr)
             }
 
             #[inline(always)]
@@ -69,34 +182,203 @@ macro_rules! include_svm_runtime {
                 <$ENV as ContractEnv>::build_transaction(bytes)
             }
 
+            #[inline(always)]
+            pub fn upgrade_build(bytes: &[u8]) -> Result<Upgrade, ContractUpgradeError> {
+                debug!("runtime `upgrade_build`");
+
+                <$ENV as ContractEnv>::build_upgrade(bytes)
+            }
+
+            /// Upgrades an existing contract to the upgrade new code. Returns the new `CodeHash`.
+            ///
+            /// The upgrade is validated against the deployed contract (see `ContractEnv::validate_upgrade`),
+            /// and the new code is validated as when deploying a contract (see `contract_deploy_validate`).
+            ///
+            /// The contract keeps its address, so its `State` (and storage) is kept as well.
+            /// The prior code hashes are kept by the contract store (see `ContractStore::load_code_history`).
+            pub fn contract_upgrade(upgrade: &Upgrade) -> Result<CodeHash, ContractUpgradeError> {
+                info!("runtime `contract_upgrade` (addr={:?})", upgrade.contract);
+
+                // the environment is dropped before validating the new code
+                // (`contract_deploy_validate` generates its own environment)
+                let contract = {
+                    let env = $env_gen();
+                    env.validate_upgrade(upgrade)?
+                };
+
+                contract_deploy_validate(&contract).map_err(ContractUpgradeError::InvalidCode)?;
+
+                let mut env = $env_gen();
+                env.store_upgrade(&contract, &upgrade.contract)
+                    .map_err(ContractUpgradeError::StoreFailed)
+            }
+
             pub fn contract_exec(
                 tx: Transaction,
                 import_object: &wasmer_runtime::ImportObject,
             ) -> Receipt {
                 debug!("runtime `contract_exec`");
 
-                let receipt = match do_contract_exec(&tx, import_object) {
+                let (res, gas_used) = do_contract_exec(&tx, import_object, None);
+
+                let receipt = match res {
                     Err(e) => Receipt {
                         success: false,
                         error: Some(e),
                         tx,
                         results: Vec::new(),
+                        transfers: Vec::new(),
+                        new_state: None,
+                        callees_states: Vec::new(),
+                        logs: Vec::new(),
+                        gas_used,
+                    },
+                    Ok((state, callees_states, logs, results)) => {
+                        let transfers = value_transfers(&tx);
+
+                        Receipt {
+                            success: true,
+                            error: None,
+                            tx,
+                            results,
+                            transfers,
+                            new_state: Some(state),
+                            callees_states,
+                            logs,
+                            gas_used,
+                        }
+                    }
+                };
+
+                debug!("receipt: {:?}", receipt);
+
+                receipt
+            }
+
+            /// Returns the ahead-of-time gas estimation of function `func_name` exported by the contract
+            /// deployed under `addr` (see `svm_compiler::gas::estimate_program_gas`).
+            ///
+            /// A `GasEstimate::Fixed` estimation is an upper-bound for the gas a transaction executing
+            /// the function consumes (excluding the instantiation of the contract dependencies,
+            /// see `DEP_INSTANTIATE_GAS`), so the node may reject transactions whose gas limit can't cover it.
+            pub fn contract_export_gas(
+                addr: &Address,
+                func_name: &str,
+            ) -> Result<svm_compiler::gas::GasEstimate, ContractExecError> {
+                debug!("runtime `contract_export_gas` (addr={:?})", addr);
+
+                let contract = {
+                    let mut env = $env_gen();
+                    contract_load(addr, &mut env)?
+                };
+
+                let program = contract_compile(&contract.wasm, addr)?;
+
+                program
+                    .export_gas(func_name)
+                    .ok_or_else(|| ContractExecError::FuncNotFound(func_name.to_string()))
+            }
+
+            /// The transaction value is transferred from the sender to the executed contract.
+            /// The node is expected to validate the sender balance (as it does for the gas fee) prior execution.
+            fn value_transfers(tx: &Transaction) -> Vec<Transfer> {
+                if tx.value == Balance(0) {
+                    return Vec::new();
+                }
+
+                vec![Transfer {
+                    from: tx.sender.clone(),
+                    to: tx.contract.clone(),
+                    amount: tx.value,
+                }]
+            }
+
+            /// Deploys `contract` under account address `addr`, and returns the deploy receipt.
+            ///
+            /// When the contract has a constructor (see `Contract#ctor`), it runs first on behalf of the contract author,
+            /// limited by the contract gas limit (see `Contract#gas_limit`) and the runtime gas limit (see `Opts#gas_limit`).
+            /// The storage committed by the constructor is the contract initial `State`.
+            /// `import_object` should be created for `addr` with `State::empty()`.
+            ///
+            /// The contract is stored only if the constructor succeeded, and `addr` hasn't been taken already.
+            pub fn contract_deploy(
+                contract: &Contract,
+                addr: &Address,
+                import_object: &wasmer_runtime::ImportObject,
+            ) -> DeployReceipt {
+                debug!("runtime `contract_deploy` (addr={:?})", addr);
+
+                // the ctor must not run (and touch the storage of `addr`) when `addr` is taken
+                let (res, gas_used) = match ensure_address_free(addr) {
+                    Err(e) => (Err(e), 0),
+                    Ok(()) => match contract.ctor {
+                        None => (Ok((State::empty(), Vec::new(), Vec::new(), Vec::new())), 0),
+                        Some(ref ctor) => {
+                            let tx = Transaction {
+                                contract: addr.clone(),
+                                sender: contract.author.clone(),
+                                gas_limit: contract.gas_limit,
+                                gas_price: contract.gas_price,
+                                value: Balance(0),
+                                func_name: ctor.func_name.clone(),
+                                func_args: ctor.func_args.clone(),
+                            };
+
+                            do_contract_exec(&tx, import_object, Some(contract))
+                        }
+                    },
+                };
+
+                let res = res.and_then(|(state, callees_states, logs, _results)| {
+                    contract_store(contract, addr).map_err(|e| match e {
+                        ContractStoreError::AddressTaken(addr) => {
+                            ContractExecError::AddressTaken(addr)
+                        }
+                        ContractStoreError::StoreFailed(e) | ContractStoreError::LoadFailed(e) => {
+                            ContractExecError::StorageFailed(e)
+                        }
+                    })?;
+
+                    Ok((state, callees_states, logs))
+                });
+
+                let receipt = match res {
+                    Err(e) => DeployReceipt {
+                        success: false,
+                        error: Some(e),
+                        addr: addr.clone(),
                         new_state: None,
+                        callees_states: Vec::new(),
+                        logs: Vec::new(),
+                        gas_used,
                     },
-                    Ok((state, results)) => Receipt {
+                    Ok((state, callees_states, logs)) => DeployReceipt {
                         success: true,
                         error: None,
-                        tx,
-                        results,
+                        addr: addr.clone(),
                         new_state: Some(state),
+                        callees_states,
+                        logs,
+                        gas_used,
                     },
                 };
 
-                debug!("receipt: {:?}", receipt);
+                debug!("deploy receipt: {:?}", receipt);
 
                 receipt
             }
 
+            /// Fails when a contract has already been deployed at `addr`
+            fn ensure_address_free(addr: &Address) -> Result<(), ContractExecError> {
+                let env = $env_gen();
+
+                match env.get_store().load_code_hash(addr) {
+                    Ok(None) => Ok(()),
+                    Ok(Some(_)) => Err(ContractExecError::AddressTaken(addr.clone())),
+                    Err(e) => Err(ContractExecError::StorageFailed(e)),
+                }
+            }
+
             pub fn import_object_create(
                 addr: Address,
                 state: State,
@@ -104,15 +386,17 @@ macro_rules! include_svm_runtime {
                 opts: $crate::opts::Opts,
             ) -> wasmer_runtime::ImportObject {
                 use svm_runtime::ctx_data_wrapper::SvmCtxDataWrapper;
-                use wasmer_runtime::{func, ImportObject};
+                use wasmer_runtime::ImportObject;
 
                 debug!(
                     "runtime `import_object_create` address={:?}, state={:?}, opts={:?}",
                     addr, state, opts
                 );
 
+                // the storage of a contract running for the first time starts with no pages,
+                // and grows to the contract pages count when instantiated (see `instantiate`)
                 let wrapped_pages_storage_gen =
-                    move || $pages_storage_gen(addr.clone(), state.clone(), opts.max_pages);
+                    move || $pages_storage_gen(addr.clone(), state.clone(), 0);
 
                 let wrapped_data = SvmCtxDataWrapper::new(node_data);
 
@@ -126,6 +410,15 @@ macro_rules! include_svm_runtime {
 
                 let mut import_object = ImportObject::new_with_data(state_gen);
 
+                import_object.register("svm", vmcalls_namespace());
+
+                import_object
+            }
+
+            /// The `svm` vmcalls imported by the contracts (under the `svm` import module)
+            fn vmcalls_namespace() -> wasmer_runtime_core::import::Namespace {
+                use wasmer_runtime::func;
+
                 let mut ns = wasmer_runtime_core::import::Namespace::new();
 
                 // storage vmcalls
@@ -147,64 +440,480 @@ macro_rules! include_svm_runtime {
                 ns.insert("reg_read_be_i64", func!(vmcalls::reg_read_be_i64));
                 ns.insert("reg_write_be_i64", func!(vmcalls::reg_write_be_i64));
 
-                import_object.register("svm", ns);
+                // collections vmcalls
+                ns.insert("array_len", func!(vmcalls::array_len));
+                ns.insert("array_get_to_reg", func!(vmcalls::array_get_to_reg));
+                ns.insert("array_set_from_reg", func!(vmcalls::array_set_from_reg));
+                ns.insert("array_push_from_reg", func!(vmcalls::array_push_from_reg));
+                ns.insert("array_pop_to_reg", func!(vmcalls::array_pop_to_reg));
+                ns.insert(
+                    "array_insert_from_reg",
+                    func!(vmcalls::array_insert_from_reg),
+                );
+                ns.insert("array_remove_to_reg", func!(vmcalls::array_remove_to_reg));
+                ns.insert("list_len", func!(vmcalls::list_len));
+                ns.insert("list_get_to_reg", func!(vmcalls::list_get_to_reg));
+                ns.insert(
+                    "list_push_front_from_reg",
+                    func!(vmcalls::list_push_front_from_reg),
+                );
+                ns.insert(
+                    "list_push_back_from_reg",
+                    func!(vmcalls::list_push_back_from_reg),
+                );
+                ns.insert(
+                    "list_pop_front_to_reg",
+                    func!(vmcalls::list_pop_front_to_reg),
+                );
+                ns.insert("list_pop_back_to_reg", func!(vmcalls::list_pop_back_to_reg));
+                ns.insert("hash_len", func!(vmcalls::hash_len));
+                ns.insert("hash_get_to_reg", func!(vmcalls::hash_get_to_reg));
+                ns.insert("hash_insert_from_reg", func!(vmcalls::hash_insert_from_reg));
+                ns.insert("hash_remove", func!(vmcalls::hash_remove));
+                ns.insert("set_len", func!(vmcalls::set_len));
+                ns.insert("set_contains", func!(vmcalls::set_contains));
+                ns.insert("set_insert_from_reg", func!(vmcalls::set_insert_from_reg));
+                ns.insert("set_remove", func!(vmcalls::set_remove));
+                ns.insert("sorted_set_len", func!(vmcalls::sorted_set_len));
+                ns.insert(
+                    "sorted_set_get_to_reg",
+                    func!(vmcalls::sorted_set_get_to_reg),
+                );
+                ns.insert("sorted_set_contains", func!(vmcalls::sorted_set_contains));
+                ns.insert(
+                    "sorted_set_insert_from_reg",
+                    func!(vmcalls::sorted_set_insert_from_reg),
+                );
+                ns.insert("sorted_set_remove", func!(vmcalls::sorted_set_remove));
+                ns.insert("string_len", func!(vmcalls::string_len));
+                ns.insert("string_read_to_mem", func!(vmcalls::string_read_to_mem));
+                ns.insert(
+                    "string_write_from_mem",
+                    func!(vmcalls::string_write_from_mem),
+                );
+                ns.insert(
+                    "string_append_from_mem",
+                    func!(vmcalls::string_append_from_mem),
+                );
+
+                // events vmcalls
+                ns.insert("emit_event_from_mem", func!(vmcalls::emit_event_from_mem));
+                ns.insert("emit_event_from_reg", func!(vmcalls::emit_event_from_reg));
 
-                import_object
+                // admins vmcalls
+                ns.insert("is_admin", func!(vmcalls::is_admin));
+
+                // balance vmcalls
+                ns.insert("get_value", func!(vmcalls::get_value));
+                ns.insert("get_self_balance", func!(vmcalls::get_self_balance));
+
+                // env vmcalls
+                ns.insert("get_sender_to_reg", func!(vmcalls::get_sender_to_reg));
+                ns.insert("get_sender_to_mem", func!(vmcalls::get_sender_to_mem));
+                ns.insert(
+                    "get_self_address_to_reg",
+                    func!(vmcalls::get_self_address_to_reg),
+                );
+                ns.insert(
+                    "get_self_address_to_mem",
+                    func!(vmcalls::get_self_address_to_mem),
+                );
+                ns.insert("get_layer_id", func!(vmcalls::get_layer_id));
+                ns.insert("get_layer_timestamp", func!(vmcalls::get_layer_timestamp));
+                ns.insert("get_beacon_to_reg", func!(vmcalls::get_beacon_to_reg));
+                ns.insert("get_beacon_to_mem", func!(vmcalls::get_beacon_to_mem));
+
+                // contracts vmcalls
+                ns.insert("contract_call", func!(contract_call));
+
+                ns
             }
 
-            #[inline(always)]
+            /// Calls function `func_name` exported by the contract whose address is in register `160:addr_reg_idx`.
+            /// Returns the number of the called function results, or `-1` when the call failed.
+            /// A failure of the called contract storage (or of the underlying key-value store) isn't
+            /// reported to the caller but traps, failing the whole transaction.
+            ///
+            /// The called contract runs with its own `wasmer` instance (importing only the `svm` vmcalls),
+            /// and consumes gas out of the calling contract remaining gas.
+            /// Its new state is recorded by the caller, and adopted only if the whole transaction succeeds.
+            ///
+            /// The called contract pages are committed to the key-value store right after the call returns
+            /// (so later calls within the transaction can load the recorded state). This is intended:
+            /// the pages and the state entry are keyed by their hashes, so they are unreachable unless
+            /// the transaction states are adopted. Entries of failed transactions are removed by
+            /// `svm_storage::prune::prune_pages`.
+            ///
+            /// * `ctx`             - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+            /// * `addr_reg_idx`    - The index of the `SvmReg160` register holding the called contract address
+            /// * `func_name_ptr`   - Pointer to the called function name (in memory `0`)
+            /// * `func_name_len`   - The length of the called function name (in bytes)
+            /// * `args_reg_idx`    - The index of the first `SvmReg64` register holding the call args (Big-Endian)
+            /// * `args_count`      - The number of call args (each arg is held by a consecutive `SvmReg64` register)
+            /// * `results_reg_idx` - The index of the first `SvmReg64` register to write the call results into (Big-Endian)
+            fn contract_call(
+                ctx: &mut wasmer_runtime::Ctx,
+                addr_reg_idx: i32,
+                func_name_ptr: i32,
+                func_name_len: i32,
+                args_reg_idx: i32,
+                args_count: i32,
+                results_reg_idx: i32,
+            ) -> Result<i32, ContractExecError> {
+                debug!("runtime `contract_call`");
+
+                let res = do_contract_call(
+                    ctx,
+                    addr_reg_idx,
+                    func_name_ptr,
+                    func_name_len,
+                    args_reg_idx,
+                    args_count,
+                    results_reg_idx,
+                );
+
+                match res {
+                    Err(e @ ContractExecError::StorageFailed(..)) => {
+                        error!("contract call storage failed: {:?}", e);
+                        Err(e)
+                    }
+                    Err(e) => {
+                        error!("contract call failed: {:?}", e);
+                        Ok(-1)
+                    }
+                    Ok(results_count) => Ok(results_count as i32),
+                }
+            }
+
+            fn do_contract_call(
+                ctx: &mut wasmer_runtime::Ctx,
+                addr_reg_idx: i32,
+                func_name_ptr: i32,
+                func_name_len: i32,
+                args_reg_idx: i32,
+                args_count: i32,
+                results_reg_idx: i32,
+            ) -> Result<usize, ContractExecError> {
+                use byteorder::{BigEndian, ByteOrder};
+                use wasmer_runtime::Value;
+
+                let callee = {
+                    let reg = $crate::wasmer_data_reg!(ctx.data, 160, addr_reg_idx, $PC);
+                    Address::from(&reg.view()[..])
+                };
+
+                let func_name = {
+                    let cells = $crate::wasmer_ctx_mem_cells!(ctx, 0, func_name_ptr, func_name_len);
+                    let bytes = cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
+
+                    String::from_utf8_lossy(&bytes).to_string()
+                };
+
+                let args = (0..args_count)
+                    .map(|i| {
+                        let reg = $crate::wasmer_data_reg!(ctx.data, 64, args_reg_idx + i, $PC);
+                        BigEndian::read_u64(&reg.getn(8))
+                    })
+                    .collect::<Vec<u64>>();
+
+                debug!(
+                    "calling `{}` of contract {:?} (args={:?})",
+                    func_name, callee, args
+                );
+
+                let caller = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+
+                if caller.call_stack.len() >= $crate::ctx::MAX_CALL_DEPTH {
+                    return Err(ContractExecError::CallDepthExceeded);
+                }
+
+                if caller.call_stack.contains(&callee) {
+                    return Err(ContractExecError::ReentrantCall(callee));
+                }
+
+                let state = resolve_callee_state(caller, &callee)?;
+                let import_object =
+                    import_object_create(callee.clone(), state, caller.node_data, caller.opts);
+
+                // the environment (opening the contracts store) is dropped before the called contract runs,
+                // since the called contract may call other contracts (each opening the store again)
+                let mut instance = {
+                    let mut env = $env_gen();
+                    let contract = contract_load(&callee, &mut env)?;
+                    let program = contract_compile(&contract.wasm, &callee)?;
+
+                    instantiate(&contract, &callee, &program.module, &import_object, &env)?
+                };
+
+                {
+                    let callee_ctx = get_instance_svm_ctx_mut(&mut instance);
+
+                    callee_ctx.call_stack = caller.call_stack.clone();
+                    callee_ctx.call_stack.push(callee.clone());
+                    callee_ctx.callees_states = caller.callees_states.clone();
+                    callee_ctx.env.sender = caller.env.contract.clone();
+                    callee_ctx.env.contract = callee.clone();
+                }
+
+                let gas_left = svm_compiler::gas::get_gas_left_ctx(ctx);
+                let res = set_instance_gas_left(&mut instance, gas_left)
+                    .and_then(|_| call_exported_func(&instance, &func_name, &args));
+
+                // the called contract gas consumption is charged from the caller
+                let (res, gas_left) = match res {
+                    Err(ContractExecError::OutOfGas) => (Err(ContractExecError::OutOfGas), 0),
+                    res => (res, svm_compiler::gas::get_gas_left(&instance)),
+                };
+                svm_compiler::gas::set_gas_left_ctx(ctx, gas_left);
+
+                let results = res?;
+
+                let callee_ctx = get_instance_svm_ctx_mut(&mut instance);
+                let new_state = callee_ctx
+                    .storage
+                    .commit()
+                    .map_err(ContractExecError::StorageFailed)?;
+
+                caller.callees_states =
+                    std::mem::replace(&mut callee_ctx.callees_states, Vec::new());
+                caller.set_callee_state(callee, new_state);
+                caller.logs.append(&mut callee_ctx.logs);
+
+                for (i, result) in results.iter().enumerate() {
+                    let value = match *result {
+                        Value::I32(v) => v as i64,
+                        Value::I64(v) => v,
+                        // floats are rejected at compilation
+                        _ => unreachable!(),
+                    };
+
+                    let reg_idx = results_reg_idx + i as i32;
+                    let reg = $crate::wasmer_data_reg!(ctx.data, 64, reg_idx, $PC);
+
+                    let mut buf = [0; 8];
+                    BigEndian::write_i64(&mut buf, value);
+                    reg.set(&buf);
+                }
+
+                Ok(results.len())
+            }
+
+            /// A called contract state is the state recorded by a previous call (within the same transaction).
+            /// Otherwise, we ask the node to resolve it (see `Opts#state_resolver`).
+            fn resolve_callee_state(
+                caller: &$crate::ctx::SvmCtx<$PC>,
+                callee: &Address,
+            ) -> Result<State, ContractExecError> {
+                if let Some(state) = caller.get_callee_state(callee) {
+                    return Ok(state.clone());
+                }
+
+                let state_ptr = match caller.opts.state_resolver {
+                    None => std::ptr::null(),
+                    Some(resolver) => unsafe {
+                        resolver(caller.node_data, callee.as_ptr() as *const std::ffi::c_void)
+                    },
+                };
+
+                if state_ptr.is_null() {
+                    error!("state not found for called contract {:?}", callee);
+                    Err(ContractExecError::CalleeStateNotFound(callee.clone()))
+                } else {
+                    Ok(State::from(state_ptr))
+                }
+            }
+
+            /// Returns the execution result along with the consumed gas.
+            ///
+            /// * `contract` - the contract to run. When `None` it's loaded from the contract store
+            ///                (it's `Some` for a contract being deployed, see `contract_deploy`).
             fn do_contract_exec(
                 tx: &Transaction,
                 import_object: &wasmer_runtime::ImportObject,
-            ) -> Result<(State, Vec<wasmer_runtime::Value>), ContractExecError> {
+                contract: Option<&Contract>,
+            ) -> (
+                Result<
+                    (
+                        State,
+                        Vec<(Address, State)>,
+                        Vec<$crate::event::Event>,
+                        Vec<wasmer_runtime::Value>,
+                    ),
+                    ContractExecError,
+                >,
+                u64,
+            ) {
+                let mut instance = match contract_instantiate(tx, import_object, contract) {
+                    Err(e) => return (Err(e), 0),
+                    Ok(instance) => instance,
+                };
+
+                {
+                    let ctx = get_instance_svm_ctx_mut(&mut instance);
+
+                    ctx.call_stack = vec![tx.contract.clone()];
+                    ctx.value = tx.value;
+                    ctx.env.sender = tx.sender.clone();
+                    ctx.env.contract = tx.contract.clone();
+                }
+
+                let gas_limit = instance_gas_limit(tx, &mut instance);
+                let res = set_instance_gas_left(&mut instance, gas_limit)
+                    .and_then(|_| instance_exec(tx, &mut instance));
+
+                match res {
+                    Err(ContractExecError::OutOfGas) => {
+                        (Err(ContractExecError::OutOfGas), gas_limit)
+                    }
+                    res => (res, gas_limit - svm_compiler::gas::get_gas_left(&instance)),
+                }
+            }
+
+            fn contract_instantiate(
+                tx: &Transaction,
+                import_object: &wasmer_runtime::ImportObject,
+                contract: Option<&Contract>,
+            ) -> Result<wasmer_runtime::Instance, ContractExecError> {
                 let mut env = $env_gen();
 
-                let contract = contract_load(tx, &mut env)?;
-                let module = contract_compile(&contract, &tx.contract)?;
-                let mut instance = instantiate(&contract, &tx.contract, &module, import_object)?;
-                let args = prepare_args_and_memory(tx, &mut instance);
-                let func = get_exported_func(&instance, &tx.func_name)?;
+                let loaded;
+                let contract = match contract {
+                    Some(contract) => contract,
+                    None => {
+                        loaded = contract_load(&tx.contract, &mut env)?;
+                        &loaded
+                    }
+                };
+
+                let program = contract_compile(&contract.wasm, &tx.contract)?;
+
+                instantiate(contract, &tx.contract, &program.module, import_object, &env)
+            }
+
+            fn instance_exec(
+                tx: &Transaction,
+                instance: &mut wasmer_runtime::Instance,
+            ) -> Result<
+                (
+                    State,
+                    Vec<(Address, State)>,
+                    Vec<$crate::event::Event>,
+                    Vec<wasmer_runtime::Value>,
+                ),
+                ContractExecError,
+            > {
+                let args = prepare_args_and_memory(tx, instance)?;
+                let func = get_exported_func(instance, &tx.func_name)?;
 
                 match func.call(&args) {
-                    Err(e) => Err(ContractExecError::ExecFailed),
+                    Err(e) => Err(call_error(e)),
                     Ok(results) => {
-                        let storage = get_instance_svm_storage_mut(&mut instance);
-                        let state = storage.commit();
-                        Ok((state, results))
+                        let ctx = get_instance_svm_ctx_mut(instance);
+                        let state = ctx
+                            .storage
+                            .commit()
+                            .map_err(ContractExecError::StorageFailed)?;
+                        let callees_states = std::mem::replace(&mut ctx.callees_states, Vec::new());
+                        let logs = std::mem::replace(&mut ctx.logs, Vec::new());
+
+                        Ok((state, callees_states, logs, results))
                     }
                 }
             }
 
+            /// Calls an exported function on behalf of another contract.
+            /// The `args` are casted to the function params types (`i32` or `i64`).
+            fn call_exported_func(
+                instance: &wasmer_runtime::Instance,
+                func_name: &str,
+                args: &[u64],
+            ) -> Result<Vec<wasmer_runtime::Value>, ContractExecError> {
+                use wasmer_runtime::Value;
+                use wasmer_runtime_core::types::Type;
+
+                let func = get_exported_func(instance, func_name)?;
+                let params = func.signature().params();
+
+                if params.len() != args.len() {
+                    error!(
+                        "function `{}` expects {} args (got {})",
+                        func_name,
+                        params.len(),
+                        args.len()
+                    );
+                    return Err(ContractExecError::ExecFailed);
+                }
+
+                let args = params
+                    .iter()
+                    .zip(args.iter())
+                    .map(|(ty, arg)| match ty {
+                        Type::I32 => Value::I32(*arg as i32),
+                        _ => Value::I64(*arg as i64),
+                    })
+                    .collect::<Vec<Value>>();
+
+                func.call(&args).map_err(call_error)
+            }
+
+            fn call_error(e: wasmer_runtime::error::CallError) -> ContractExecError {
+                use svm_compiler::gas::OutOfGasError;
+                use wasmer_runtime::error::{CallError, RuntimeError};
+
+                match e {
+                    CallError::Runtime(RuntimeError::Error { ref data })
+                        if data.downcast_ref::<OutOfGasError>().is_some() =>
+                    {
+                        error!("out of gas");
+                        ContractExecError::OutOfGas
+                    }
+                    CallError::Runtime(RuntimeError::Error { ref data })
+                        if data.downcast_ref::<ContractExecError>().is_some() =>
+                    {
+                        // a vmcall has failed the execution
+                        let e = data.downcast_ref::<ContractExecError>().unwrap();
+                        error!("vmcall failed: {:?}", e);
+                        e.clone()
+                    }
+                    _ => ContractExecError::ExecFailed,
+                }
+            }
+
             fn contract_load(
-                tx: &Transaction,
+                addr: &Address,
                 env: &mut $ENV,
             ) -> Result<Contract, ContractExecError> {
-                info!("runtime `contract_load`");
+                info!("runtime `contract_load` (addr={:?})", addr);
 
                 let store = env.get_store();
 
-                match store.load(&tx.contract) {
-                    None => Err(ContractExecError::NotFound(tx.contract.clone())),
-                    Some(contract) => Ok(contract),
+                match store.load(addr) {
+                    Err(e) => Err(ContractExecError::LoadFailed(e)),
+                    Ok(None) => Err(ContractExecError::NotFound(addr.clone())),
+                    Ok(Some(contract)) => Ok(contract),
                 }
             }
 
             fn contract_compile(
-                contract: &Contract,
+                wasm: &[u8],
                 addr: &Address,
-            ) -> Result<wasmer_runtime::Module, ContractExecError> {
+            ) -> Result<svm_compiler::CompiledProgram, ContractExecError> {
                 info!("runtime `contract_compile` (addr={:?})", addr);
 
-                let compile = svm_compiler::compile_program(&contract.wasm);
+                let hash =
+                    <<$ENV as ContractEnv>::Types as ContractEnvTypes>::CodeHasher::hash(wasm);
+
+                let compile =
+                    MODULE_CACHE.with(|cache| cache.borrow_mut().get_or_compile(&hash, wasm));
 
                 match compile {
                     Err(e) => {
                         error!("wasmer module compilation failed (addr={:?})", addr);
                         Err(ContractExecError::CompilationFailed(addr.clone()))
                     }
-                    Ok(module) => {
+                    Ok(program) => {
                         info!("wasmer module compile succeeded");
-                        Ok(module)
+                        Ok(program)
                     }
                 }
             }
@@ -214,14 +923,128 @@ macro_rules! include_svm_runtime {
                 addr: &Address,
                 module: &wasmer_runtime::Module,
                 import_object: &wasmer_runtime::ImportObject,
+                env: &$ENV,
             ) -> Result<wasmer_runtime::Instance, ContractExecError> {
                 info!("runtime `instantiate` (wasmer module instantiate)");
 
-                let instantiate = module.instantiate(import_object);
+                let mut libs = deps_instantiate(contract, addr, env)?;
+
+                let instantiate = if libs.is_empty() {
+                    module.instantiate(import_object)
+                } else {
+                    module.instantiate(&deps_import_object(import_object, &libs))
+                };
 
                 match instantiate {
                     Err(e) => Err(ContractExecError::InstantiationFailed(addr.clone())),
-                    Ok(instance) => Ok(instance),
+                    Ok(mut instance) => {
+                        for lib in libs.iter_mut() {
+                            // safe, since the libraries instances are owned by the instance data
+                            unsafe { svm_compiler::gas::share_gas_left(&mut instance, lib) };
+                        }
+
+                        let ctx = get_instance_svm_ctx_mut(&mut instance);
+                        ctx.admins = contract.admins.clone();
+                        ctx.libs = libs;
+
+                        // the pages allocated at deploy time (no gas is charged for them)
+                        ctx.storage
+                            .grow(u32::from(contract.pages_count))
+                            .map_err(ContractExecError::StorageOutOfRange)?;
+
+                        Ok(instance)
+                    }
+                }
+            }
+
+            /// Instantiates the libraries the contract depends on (see `Contract#deps`).
+            /// Libraries import nothing (validated at deploy time), so they don't require an import object.
+            fn deps_instantiate(
+                contract: &Contract,
+                addr: &Address,
+                env: &$ENV,
+            ) -> Result<Vec<wasmer_runtime::Instance>, ContractExecError> {
+                contract
+                    .deps
+                    .iter()
+                    .map(|dep| {
+                        let lib = env
+                            .load_dependency(dep)
+                            .map_err(ContractExecError::LoadFailed)?
+                            .ok_or_else(|| {
+                                error!("dependency {:?} not found (addr={:?})", dep, addr);
+                                ContractExecError::DependencyNotFound(dep.clone())
+                            })?;
+
+                        let program = contract_compile(&lib, addr)?;
+
+                        program
+                            .module
+                            .instantiate(&wasmer_runtime::ImportObject::new())
+                            .map_err(|_| ContractExecError::InstantiationFailed(addr.clone()))
+                    })
+                    .collect()
+            }
+
+            /// Links the libraries into the contract import object.
+            /// The functions exported by the `i`-th library are imported by the contract from module `dep{i}`.
+            fn deps_import_object(
+                import_object: &wasmer_runtime::ImportObject,
+                libs: &[wasmer_runtime::Instance],
+            ) -> wasmer_runtime::ImportObject {
+                use wasmer_runtime::Export;
+                use wasmer_runtime_core::import::Namespace;
+
+                let mut import_object = import_object.clone_ref();
+
+                for (i, lib) in libs.iter().enumerate() {
+                    let mut ns = Namespace::new();
+
+                    for (name, export) in lib.exports() {
+                        if let Export::Function { .. } = export {
+                            ns.insert(name, export);
+                        }
+                    }
+
+                    import_object.register(format!("dep{}", i), ns);
+                }
+
+                import_object
+            }
+
+            /// The instance gas limit is the transaction gas limit capped by the runtime gas limit (see `Opts`)
+            fn instance_gas_limit(
+                tx: &Transaction,
+                instance: &mut wasmer_runtime::Instance,
+            ) -> u64 {
+                let wasmer_ctx: &mut wasmer_runtime::Ctx = instance.context_mut();
+                let ctx = $crate::cast_wasmer_data_to_svm_ctx!(wasmer_ctx.data, $PC);
+                let gas_limit = std::cmp::min(tx.gas_limit, ctx.opts.gas_limit);
+
+                debug!("runtime `instance_gas_limit` (gas_limit={})", gas_limit);
+
+                gas_limit
+            }
+
+            /// Sets the remaining gas of the instance (shared with its libraries instances, see `instantiate`)
+            /// to `gas` minus the gas charged for instantiating the libraries (see `DEP_INSTANTIATE_GAS`).
+            /// Returns `ContractExecError::OutOfGas` when `gas` doesn't cover the libraries instantiation.
+            fn set_instance_gas_left(
+                instance: &mut wasmer_runtime::Instance,
+                gas: u64,
+            ) -> Result<(), ContractExecError> {
+                let libs_count = get_instance_svm_ctx_mut(instance).libs.len() as u64;
+                let libs_gas = libs_count * $crate::ctx::DEP_INSTANTIATE_GAS;
+
+                match gas.checked_sub(libs_gas) {
+                    None => {
+                        svm_compiler::gas::set_gas_left(instance, 0);
+                        Err(ContractExecError::OutOfGas)
+                    }
+                    Some(gas_left) => {
+                        svm_compiler::gas::set_gas_left(instance, gas_left);
+                        Ok(())
+                    }
                 }
             }
 
@@ -246,8 +1069,8 @@ macro_rules! include_svm_runtime {
             fn prepare_args_and_memory(
                 tx: &Transaction,
                 instance: &mut wasmer_runtime::Instance,
-            ) -> Vec<wasmer_runtime::Value> {
-                use svm_contract::wasm::{WasmArgValue, WasmIntType};
+            ) -> Result<Vec<wasmer_runtime::Value>, ContractExecError> {
+                use svm_contract::wasm::WasmArgValue;
                 use wasmer_runtime::Value;
 
                 debug!("runtime `prepare_args_and_memory`");
@@ -258,42 +1081,80 @@ macro_rules! include_svm_runtime {
                 let mut wasmer_args = Vec::with_capacity(tx.func_args.len());
 
                 for arg in tx.func_args.iter() {
-                    let wasmer_arg = match arg {
-                        WasmArgValue::I32(v) => Value::I32(*v as i32),
-                        WasmArgValue::I64(v) => Value::I64(*v as i64),
-                        WasmArgValue::Fixed(ty, buf) => {
+                    match arg {
+                        WasmArgValue::I32(v) => wasmer_args.push(Value::I32(*v as i32)),
+                        WasmArgValue::I64(v) => wasmer_args.push(Value::I64(*v as i64)),
+                        WasmArgValue::Fixed(offset_type, buf) => {
                             let buf_mem_start = mem_offset;
 
-                            let view = memory.view();
+                            mem_offset = copy_arg_to_memory(memory, mem_offset, buf)?;
 
-                            for byte in buf.into_iter() {
-                                view[mem_offset].set(*byte);
-                                mem_offset += 1;
-                            }
-
-                            match ty {
-                                WasmIntType::I32 => Value::I32(buf_mem_start as i32),
-                                WasmIntType::I64 => Value::I64(buf_mem_start as i64),
-                            }
+                            wasmer_args.push(wasmer_int_arg(offset_type, buf_mem_start));
                         }
-                        WasmArgValue::Slice(..) => unimplemented!(),
-                    };
+                        WasmArgValue::Slice(offset_type, length_type, buf) => {
+                            let buf_mem_start = mem_offset;
+
+                            mem_offset = copy_arg_to_memory(memory, mem_offset, buf)?;
 
-                    wasmer_args.push(wasmer_arg);
+                            // a slice arg is passed to the function as two args: `(offset, length)`
+                            wasmer_args.push(wasmer_int_arg(offset_type, buf_mem_start));
+                            wasmer_args.push(wasmer_int_arg(length_type, buf.len()));
+                        }
+                    }
                 }
 
                 debug!("wasmer args={:?}", wasmer_args);
 
-                wasmer_args
+                Ok(wasmer_args)
+            }
+
+            /// Copies `buf` into `memory` starting at `mem_offset`. Returns the offset right after it.
+            /// Fails when `buf` doesn't fit the memory (the memory isn't grown for the args).
+            fn copy_arg_to_memory(
+                memory: &wasmer_runtime::Memory,
+                mem_offset: usize,
+                buf: &[u8],
+            ) -> Result<usize, ContractExecError> {
+                let view = memory.view::<u8>();
+                let mem_end = mem_offset + buf.len();
+
+                if mem_end > view.len() {
+                    error!(
+                        "function args require {} bytes (memory has {})",
+                        mem_end,
+                        view.len()
+                    );
+                    return Err(ContractExecError::ArgsTooLarge(mem_end));
+                }
+
+                for (cell, byte) in view[mem_offset..mem_end].iter().zip(buf.iter()) {
+                    cell.set(*byte);
+                }
+
+                Ok(mem_end)
+            }
+
+            #[inline(always)]
+            fn wasmer_int_arg(
+                int_type: &svm_contract::wasm::WasmIntType,
+                value: usize,
+            ) -> wasmer_runtime::Value {
+                use svm_contract::wasm::WasmIntType;
+                use wasmer_runtime::Value;
+
+                match int_type {
+                    WasmIntType::I32 => Value::I32(value as i32),
+                    WasmIntType::I64 => Value::I64(value as i64),
+                }
             }
 
             #[inline(always)]
-            fn get_instance_svm_storage_mut(
+            fn get_instance_svm_ctx_mut(
                 instance: &mut wasmer_runtime::Instance,
-            ) -> &mut svm_storage::PageSliceCache<$PC> {
+            ) -> &mut $crate::ctx::SvmCtx<$PC> {
                 let wasmer_ctx: &mut wasmer_runtime::Ctx = instance.context_mut();
 
-                $crate::wasmer_data_storage!(wasmer_ctx.data, $PC)
+                $crate::cast_wasmer_data_to_svm_ctx!(wasmer_ctx.data, $PC)
             }
         }
     };
@@ -1,7 +1,8 @@
 mod error;
+mod leveldb;
 mod macros;
 mod receipt;
 mod rocksdb;
 
 pub use error::ContractExecError;
-pub use receipt::Receipt;
+pub use receipt::{DeployReceipt, Receipt, Transfer};
@@ -1,10 +1,25 @@
+use crate::event::Event;
 use crate::runtime::ContractExecError;
 
-use svm_common::State;
+use svm_common::{Address, Balance, State};
 use svm_contract::transaction::Transaction;
 
 use wasmer_runtime::Value;
 
+/// A balance change the node applies when a transaction succeeds:
+/// `amount` coins are moved from account `from` to account `to`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+    /// the debited account address
+    pub from: Address,
+
+    /// the credited account address
+    pub to: Address,
+
+    /// the transferred amount (in coins)
+    pub amount: Balance,
+}
+
 /// Runtime transaction execution receipt
 #[derive(Debug)]
 pub struct Receipt {
@@ -20,6 +35,83 @@ pub struct Receipt {
     /// the new contract `State` if execution succedded
     pub new_state: Option<State>,
 
+    /// the new states of the contracts called (directly or indirectly) by the executed contract.
+    /// empty if execution failed
+    pub callees_states: Vec<(Address, State)>,
+
+    /// the events emitted by the executed contract (and the contracts it called).
+    /// empty if execution failed
+    pub logs: Vec<Event>,
+
     /// returned values
     pub results: Vec<Value>,
+
+    /// the balance changes the node should apply (all of them, atomically along with the new states).
+    /// empty if execution failed
+    pub transfers: Vec<Transfer>,
+
+    /// the gas consumed by the execution (the whole gas limit when running out of gas)
+    pub gas_used: u64,
+}
+
+impl Receipt {
+    /// Returns the new states of the contracts modified by the transaction (the callees first,
+    /// then the executed contract). Meant for updating the global state (see `svm_storage::global_state`).
+    /// Empty if execution failed.
+    pub fn changed_states(&self) -> Vec<(Address, State)> {
+        match self.new_state {
+            Some(ref state) => {
+                let mut states = self.callees_states.clone();
+                states.push((self.tx.contract.clone(), state.clone()));
+
+                states
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Runtime contract deploy receipt
+#[derive(Debug)]
+pub struct DeployReceipt {
+    /// whether the deploy succedded or not (the contract is stored only if it did)
+    pub success: bool,
+
+    /// the constructor execution error in case the deploy failed
+    pub error: Option<ContractExecError>,
+
+    /// the deployed contract account address
+    pub addr: Address,
+
+    /// the contract initial `State` if the deploy succedded.
+    /// `State::empty()` for a contract having no constructor
+    pub new_state: Option<State>,
+
+    /// the new states of the contracts called (directly or indirectly) by the constructor.
+    /// empty if the deploy failed
+    pub callees_states: Vec<(Address, State)>,
+
+    /// the events emitted by the constructor (and the contracts it called).
+    /// empty if the deploy failed
+    pub logs: Vec<Event>,
+
+    /// the gas consumed by the constructor execution (bounded by the contract gas limit, see `Contract#gas_limit`).
+    /// the author pays it at the contract gas price (see `Contract#gas_price`)
+    pub gas_used: u64,
+}
+
+impl DeployReceipt {
+    /// Returns the new states of the contracts modified by the deploy (the callees first,
+    /// then the deployed contract). Empty if the deploy failed.
+    pub fn changed_states(&self) -> Vec<(Address, State)> {
+        match self.new_state {
+            Some(ref state) => {
+                let mut states = self.callees_states.clone();
+                states.push((self.addr.clone(), state.clone()));
+
+                states
+            }
+            None => Vec::new(),
+        }
+    }
 }
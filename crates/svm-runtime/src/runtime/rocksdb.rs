@@ -1,19 +1,45 @@
 /// Generates pages-storage instance of `RocksPages`
+///
+/// A `rocksdb` database can't be opened twice. Since a contract calling another contract
+/// requires a pages-storage per contract, the opened `RocksStore` is shared (while in use).
+///
+/// Panics when the database can't be opened (the runtime can't run contracts without their storage).
 #[macro_export]
 macro_rules! gen_rocksdb_pages_storage {
-    ($addr: expr, $state: expr, $max_pages: expr, $contract_storage_path: expr) => {{
+    ($addr: expr, $state: expr, $pages_count: expr, $contract_storage_path: expr) => {{
         use std::cell::RefCell;
-        use std::path::Path;
-        use std::rc::Rc;
+        use std::collections::HashMap;
+        use std::path::{Path, PathBuf};
+        use std::rc::{Rc, Weak};
 
         use svm_kv::rocksdb::RocksStore;
         use svm_storage::rocksdb::RocksPages;
 
+        thread_local! {
+            static OPENED_STORES: RefCell<HashMap<PathBuf, Weak<RefCell<RocksStore>>>> =
+                RefCell::new(HashMap::new());
+        }
+
         let path = Path::new($contract_storage_path);
-        let kv = RocksStore::new(path);
-        let kv = Rc::new(RefCell::new(kv));
 
-        RocksPages::new($addr, kv, $state, $max_pages as u32)
+        let kv = OPENED_STORES.with(|stores| {
+            let mut stores = stores.borrow_mut();
+
+            match stores.get(path).and_then(|kv| kv.upgrade()) {
+                Some(kv) => kv,
+                None => {
+                    let kv = match RocksStore::new(path) {
+                        Ok(kv) => Rc::new(RefCell::new(kv)),
+                        Err(e) => panic!("failed to open contracts storage: {:?}", e),
+                    };
+                    stores.insert(path.to_path_buf(), Rc::downgrade(&kv));
+
+                    kv
+                }
+            }
+        });
+
+        RocksPages::new($addr, kv, $state, $pages_count as u32)
     }};
 }
 
@@ -27,33 +53,150 @@ macro_rules! gen_rocksdb_page_cache {
     }};
 }
 
-/// Generates an environment instance of type `RocksEnv`
+/// Generates an environment instance of type `RocksEnv`.
+/// Panics when the code database can't be opened.
 #[macro_export]
 macro_rules! gen_rocksdb_env {
     ($code_db_path: expr) => {{
         use std::path::Path;
         use svm_contract::rocksdb::{RocksContractStore, RocksEnv};
 
-        use svm_contract::wasm::{
-            WasmContractJsonDeserializer as D, WasmContractJsonSerializer as S,
-        };
+        use svm_contract::wasm::{WasmContractDeserializer as D, WasmContractSerializer as S};
 
         let path = Path::new($code_db_path);
-        let store = RocksContractStore::<S, D>::new(path);
+        let store = match RocksContractStore::<S, D>::new(path) {
+            Ok(store) => store,
+            Err(e) => panic!("failed to open contracts code database: {:?}", e),
+        };
+
+        RocksEnv::new(store)
+    }};
+}
+
+/// Returns a `RocksStore` handle to the column family `$cf` of the database under `$db_path`.
+///
+/// The database holds two column families:
+/// * `storage` - the contracts pages and pages-states (tuned for point lookups by hash).
+/// * `code`    - the contracts code (compressed).
+///
+/// The opened database is shared (while in use) by all the handles of the current thread.
+/// Panics when the database can't be opened.
+#[macro_export]
+macro_rules! gen_rocksdb_column_family {
+    ($db_path: expr, $cf: expr) => {{
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+        use std::path::{Path, PathBuf};
+
+        use svm_kv::rocksdb::{
+            RocksCompression, RocksStore, RocksStoreBuilder, RocksTuning, WeakRocksStore,
+        };
+
+        thread_local! {
+            static OPENED_DBS: RefCell<HashMap<PathBuf, WeakRocksStore>> =
+                RefCell::new(HashMap::new());
+        }
+
+        let path = Path::new($db_path);
+
+        let db: RocksStore = OPENED_DBS.with(|dbs| {
+            let mut dbs = dbs.borrow_mut();
+
+            match dbs.get(path).and_then(|db| db.upgrade()) {
+                Some(db) => db,
+                None => {
+                    let storage_tuning = RocksTuning {
+                        bloom_filter_bits: Some(10),
+                        ..RocksTuning::default()
+                    };
+
+                    let code_tuning = RocksTuning {
+                        compression: Some(RocksCompression::Lz4),
+                        ..RocksTuning::default()
+                    };
+
+                    let db = RocksStoreBuilder::new(path)
+                        .with_column_family("storage", storage_tuning)
+                        .with_column_family("code", code_tuning)
+                        .build()
+                        .unwrap_or_else(|e| panic!("failed to open database: {:?}", e));
+
+                    dbs.insert(path.to_path_buf(), db.downgrade());
+
+                    db
+                }
+            }
+        });
+
+        db.column_family($cf).unwrap()
+    }};
+}
+
+/// Generates pages-storage instance of `RocksPages` over the `storage` column family
+/// of the database under `$db_path` (see `gen_rocksdb_column_family!`)
+#[macro_export]
+macro_rules! gen_rocksdb_cf_pages_storage {
+    ($addr: expr, $state: expr, $pages_count: expr, $db_path: expr) => {{
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        use svm_storage::rocksdb::RocksPages;
+
+        let kv = $crate::gen_rocksdb_column_family!($db_path, "storage");
+
+        RocksPages::new(
+            $addr,
+            Rc::new(RefCell::new(kv)),
+            $state,
+            $pages_count as u32,
+        )
+    }};
+}
+
+/// Generates an environment instance of type `RocksEnv` over the `code` column family
+/// of the database under `$db_path` (see `gen_rocksdb_column_family!`)
+#[macro_export]
+macro_rules! gen_rocksdb_cf_env {
+    ($db_path: expr) => {{
+        use svm_contract::rocksdb::{RocksContractStore, RocksEnv};
+
+        use svm_contract::wasm::{WasmContractDeserializer as D, WasmContractSerializer as S};
+
+        let kv = $crate::gen_rocksdb_column_family!($db_path, "code");
+        let store = RocksContractStore::<S, D>::from_store(kv);
 
         RocksEnv::new(store)
     }};
 }
 
 /// Injects `rocksdb` backed implementation of `svm` runtime.
+///
+/// * `include_svm_rocksdb_runtime!(db_path)` - contracts storage and code live in one database
+///   (under distinct column families).
+/// * `include_svm_rocksdb_runtime!(contract_storage_path, code_db_path)` - contracts storage
+///   and code live in two databases.
 #[macro_export]
 macro_rules! include_svm_rocksdb_runtime {
+    ($db_path: expr) => {
+        $crate::include_svm_runtime!(
+            |addr, state, pages_count| $crate::gen_rocksdb_cf_pages_storage!(
+                addr,
+                state,
+                pages_count,
+                $db_path
+            ),
+            |pages_storage, max_pages| $crate::gen_rocksdb_page_cache!(pages_storage, max_pages),
+            svm_storage::rocksdb::RocksMerklePageCache,
+            svm_contract::rocksdb::RocksEnv,
+            || $crate::gen_rocksdb_cf_env!($db_path)
+        );
+    };
     ($contract_storage_path: expr, $code_db_path: expr) => {
         $crate::include_svm_runtime!(
-            |addr, state, max_pages| $crate::gen_rocksdb_pages_storage!(
+            |addr, state, pages_count| $crate::gen_rocksdb_pages_storage!(
                 addr,
                 state,
-                max_pages,
+                pages_count,
                 $contract_storage_path
             ),
             |pages_storage, max_pages| $crate::gen_rocksdb_page_cache!(pages_storage, max_pages),
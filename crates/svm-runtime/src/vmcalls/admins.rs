@@ -0,0 +1,25 @@
+/// When called, injects the code of the `svm` admins vmcalls.
+/// The `vmcalls` are functions imported into each running `svm` instance.
+#[macro_export]
+macro_rules! include_svm_admins_vmcalls {
+    ($PC: path) => {
+        /// Returns `1` if the address held by register `160:addr_reg_idx` is an admin
+        /// of the running contract, and `0` otherwise.
+        /// Contracts should use it for gating their privileged functions.
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `addr_reg_idx` - The index of the `SvmReg160` register holding the queried address
+        pub fn is_admin(ctx: &mut wasmer_runtime::Ctx, addr_reg_idx: i32) -> i32 {
+            let reg = $crate::wasmer_data_reg!(ctx.data, 160, addr_reg_idx, $PC);
+            let addr = svm_common::Address::from(&reg.view()[..]);
+
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+
+            if svm_ctx.admins.contains(&addr) {
+                1
+            } else {
+                0
+            }
+        }
+    };
+}
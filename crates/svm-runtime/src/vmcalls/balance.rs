@@ -0,0 +1,69 @@
+/// When called, injects the code of the `svm` balance vmcalls.
+/// The `vmcalls` are functions imported into each running `svm` instance.
+///
+/// The vmcalls fail the execution when the destination register has less than 128 bits.
+#[macro_export]
+macro_rules! include_svm_balance_vmcalls {
+    ($PC: path) => {
+        /// Copies the value transferred to the running contract by the transaction sender
+        /// into register `reg_bits:reg_idx` (16 bytes, Little-Endian). Contracts called by other contracts get zero.
+        ///
+        /// * `ctx`      - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `reg_bits` - The type of the register (must be at least 128 bits)
+        /// * `reg_idx`  - The index of the register to copy the value into
+        pub fn get_value(
+            ctx: &mut wasmer_runtime::Ctx,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            log::debug!("`get_value` register=`{}:{}`", reg_bits, reg_idx);
+
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let value = svm_ctx.value;
+
+            balance_to_reg(ctx, value, reg_bits, reg_idx)
+        }
+
+        /// Copies the balance of the running contract into register `reg_bits:reg_idx` (16 bytes, Little-Endian).
+        /// The balance includes the value transferred by the transaction sender (see `SvmCtx#get_self_balance`).
+        ///
+        /// * `ctx`      - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `reg_bits` - The type of the register (must be at least 128 bits)
+        /// * `reg_idx`  - The index of the register to copy the balance into
+        pub fn get_self_balance(
+            ctx: &mut wasmer_runtime::Ctx,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            log::debug!("`get_self_balance` register=`{}:{}`", reg_bits, reg_idx);
+
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let balance = svm_ctx.get_self_balance();
+
+            balance_to_reg(ctx, balance, reg_bits, reg_idx)
+        }
+
+        fn balance_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            balance: svm_common::Balance,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use byteorder::{ByteOrder, LittleEndian};
+
+            if reg_bits < 128 {
+                return Err($crate::runtime::ContractExecError::RegisterTooSmall(
+                    reg_bits,
+                ));
+            }
+
+            let mut buf = [0; 16];
+            LittleEndian::write_u128(&mut buf, balance.0);
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            reg.set(&buf);
+
+            Ok(())
+        }
+    };
+}
@@ -0,0 +1,863 @@
+/// When called, injects the code of the `svm` storage collections vmcalls.
+/// The `vmcalls` are functions imported into each running `svm` instance.
+///
+/// Each collection occupies a whole storage page (see `svm_storage::collections`).
+/// Items, keys and values are copied from / to `svm wasmer` registers (strings from / to `wasmer` memory).
+///
+/// The vmcalls fail the execution when the collection operation fails (e.g an index out of bounds
+/// or a full collection), or when the item size doesn't fit the register copied from / to. The vmcalls modifying a collection fail the execution also when the page index
+/// is out of range (see `svm_collection_write`).
+#[macro_export]
+macro_rules! include_svm_collections_vmcalls {
+    ($PC: path) => {
+        /// Validates that a collection item (or key, or value) of `size` bytes fits a register of `reg_bits` bits
+        fn collection_item_size(
+            size: i32,
+            reg_bits: i32,
+        ) -> Result<usize, $crate::runtime::ContractExecError> {
+            if size <= 0 {
+                return Err($crate::runtime::ContractExecError::InvalidItemSize(size));
+            }
+
+            if size > reg_bits / 8 {
+                return Err($crate::runtime::ContractExecError::RegisterTooSmall(
+                    reg_bits,
+                ));
+            }
+
+            Ok(size as usize)
+        }
+
+        /// Returns the number of items of the `Array` stored at page `page_idx`
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the array
+        /// * `item_size` - The size of each array item (in bytes)
+        pub fn array_len(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                Array::new(page, item_size as usize).map(|array| array.len() as i32)
+            })
+        }
+
+        /// Copies the item indexed `idx` of the `Array` stored at page `page_idx` into register `dst_reg`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the array
+        /// * `item_size`    - The size of each array item (in bytes)
+        /// * `idx`          - The item index
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn array_get_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            idx: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item =
+                $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                    let array = Array::new(page, item_size)?;
+                    array.get(idx as u32).map(|item| item.to_vec())
+                })?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+            reg.set(&item);
+
+            Ok(())
+        }
+
+        /// Overrides the item indexed `idx` of the `Array` stored at page `page_idx`
+        /// with the first `item_size` bytes of register `src_reg`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the array
+        /// * `item_size`    - The size of each array item (in bytes)
+        /// * `idx`          - The item index
+        /// * `src_reg_bits` - The type of the register (determined by its #bits) we want to copy the item from
+        /// * `src_reg_idx`  - The source register index
+        pub fn array_set_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            idx: i32,
+            src_reg_bits: i32,
+            src_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            let item_size = collection_item_size(item_size, src_reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut array = Array::new(page, item_size)?;
+                array.set(idx as u32, &item)
+            })
+        }
+
+        /// Appends the first `item_size` bytes of register `src_reg` to the `Array` stored at page `page_idx`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the array
+        /// * `item_size`    - The size of each array item (in bytes)
+        /// * `src_reg_bits` - The type of the register (determined by its #bits) we want to copy the item from
+        /// * `src_reg_idx`  - The source register index
+        pub fn array_push_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            src_reg_bits: i32,
+            src_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            let item_size = collection_item_size(item_size, src_reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut array = Array::new(page, item_size)?;
+                array.push(&item)
+            })
+        }
+
+        /// Removes the last item of the `Array` stored at page `page_idx` and copies it into register `dst_reg`.
+        /// Returns `1` when an item has been removed and `0` when the array is empty.
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the array
+        /// * `item_size`    - The size of each array item (in bytes)
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn array_pop_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item = $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                Array::new(page, item_size).map(|mut array| array.pop())
+            })?;
+
+            match item {
+                None => Ok(0),
+                Some(item) => {
+                    let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+                    reg.set(&item);
+                    Ok(1)
+                }
+            }
+        }
+
+        /// Inserts the first `item_size` bytes of register `src_reg` at index `idx`
+        /// of the `Array` stored at page `page_idx` (shifting the items after it)
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the array
+        /// * `item_size`    - The size of each array item (in bytes)
+        /// * `idx`          - The index to insert at
+        /// * `src_reg_bits` - The type of the register (determined by its #bits) we want to copy the item from
+        /// * `src_reg_idx`  - The source register index
+        pub fn array_insert_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            idx: i32,
+            src_reg_bits: i32,
+            src_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            let item_size = collection_item_size(item_size, src_reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut array = Array::new(page, item_size)?;
+                array.insert(idx as u32, &item)
+            })
+        }
+
+        /// Removes the item indexed `idx` of the `Array` stored at page `page_idx` (shifting the items after it)
+        /// and copies it into register `dst_reg`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the array
+        /// * `item_size`    - The size of each array item (in bytes)
+        /// * `idx`          - The index of the item to remove
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn array_remove_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            idx: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Array;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item = $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut array = Array::new(page, item_size)?;
+                array.remove(idx as u32)
+            })?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+            reg.set(&item);
+
+            Ok(())
+        }
+
+        /// Returns the number of items of the `List` stored at page `page_idx`
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the list
+        /// * `item_size` - The size of each list item (in bytes)
+        pub fn list_len(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::List;
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                List::new(page, item_size as usize).map(|list| list.len() as i32)
+            })
+        }
+
+        /// Copies the item indexed `idx` (the first item is at index `0`) of the `List` stored at page `page_idx`
+        /// into register `dst_reg`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the list
+        /// * `item_size`    - The size of each list item (in bytes)
+        /// * `idx`          - The item index
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn list_get_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            idx: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::List;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item =
+                $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                    let list = List::new(page, item_size)?;
+                    list.get(idx as u32).map(|item| item.to_vec())
+                })?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+            reg.set(&item);
+
+            Ok(())
+        }
+
+        /// Prepends the first `item_size` bytes of register `src_reg` to the `List` stored at page `page_idx`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the list
+        /// * `item_size`    - The size of each list item (in bytes)
+        /// * `src_reg_bits` - The type of the register (determined by its #bits) we want to copy the item from
+        /// * `src_reg_idx`  - The source register index
+        pub fn list_push_front_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            src_reg_bits: i32,
+            src_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::List;
+
+            let item_size = collection_item_size(item_size, src_reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut list = List::new(page, item_size)?;
+                list.push_front(&item)
+            })
+        }
+
+        /// Appends the first `item_size` bytes of register `src_reg` to the `List` stored at page `page_idx`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the list
+        /// * `item_size`    - The size of each list item (in bytes)
+        /// * `src_reg_bits` - The type of the register (determined by its #bits) we want to copy the item from
+        /// * `src_reg_idx`  - The source register index
+        pub fn list_push_back_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            src_reg_bits: i32,
+            src_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::List;
+
+            let item_size = collection_item_size(item_size, src_reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut list = List::new(page, item_size)?;
+                list.push_back(&item)
+            })
+        }
+
+        /// Removes the first item of the `List` stored at page `page_idx` and copies it into register `dst_reg`.
+        /// Returns `1` when an item has been removed and `0` when the list is empty.
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the list
+        /// * `item_size`    - The size of each list item (in bytes)
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn list_pop_front_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::List;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item = $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                List::new(page, item_size).map(|mut list| list.pop_front())
+            })?;
+
+            match item {
+                None => Ok(0),
+                Some(item) => {
+                    let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+                    reg.set(&item);
+                    Ok(1)
+                }
+            }
+        }
+
+        /// Removes the last item of the `List` stored at page `page_idx` and copies it into register `dst_reg`.
+        /// Returns `1` when an item has been removed and `0` when the list is empty.
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the list
+        /// * `item_size`    - The size of each list item (in bytes)
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn list_pop_back_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::List;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item = $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                List::new(page, item_size).map(|mut list| list.pop_back())
+            })?;
+
+            match item {
+                None => Ok(0),
+                Some(item) => {
+                    let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+                    reg.set(&item);
+                    Ok(1)
+                }
+            }
+        }
+
+        /// Returns the number of entries of the `Hash` stored at page `page_idx`
+        ///
+        /// * `ctx`        - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`   - The page holding the hash
+        /// * `key_size`   - The size of each key (in bytes)
+        /// * `value_size` - The size of each value (in bytes)
+        pub fn hash_len(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            key_size: i32,
+            value_size: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Hash;
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                Hash::new(page, key_size as usize, value_size as usize)
+                    .map(|hash| hash.len() as i32)
+            })
+        }
+
+        /// Copies the value stored under the key held by register `key_reg` of the `Hash` stored at page `page_idx`
+        /// into register `dst_reg`. Returns `1` when the key has been found and `0` otherwise.
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the hash
+        /// * `key_size`     - The size of each key (in bytes)
+        /// * `value_size`   - The size of each value (in bytes)
+        /// * `key_reg_bits` - The type of the register (determined by its #bits) holding the key
+        /// * `key_reg_idx`  - The index of the register holding the key
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the value to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn hash_get_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            key_size: i32,
+            value_size: i32,
+            key_reg_bits: i32,
+            key_reg_idx: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Hash;
+
+            let key_size = collection_item_size(key_size, key_reg_bits)?;
+            let value_size = collection_item_size(value_size, dst_reg_bits)?;
+
+            let key_reg = $crate::wasmer_data_reg!(ctx.data, key_reg_bits, key_reg_idx, $PC);
+            let key = key_reg.getn(key_size);
+
+            let value =
+                $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                    let hash = Hash::new(page, key_size, value_size)?;
+                    hash.get(&key).map(|value| value.map(|v| v.to_vec()))
+                })?;
+
+            match value {
+                None => Ok(0),
+                Some(value) => {
+                    let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+                    reg.set(&value);
+                    Ok(1)
+                }
+            }
+        }
+
+        /// Stores the value held by register `value_reg` under the key held by register `key_reg`
+        /// of the `Hash` stored at page `page_idx`. Returns `1` when a new entry has been added and `0` otherwise.
+        ///
+        /// * `ctx`            - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`       - The page holding the hash
+        /// * `key_size`       - The size of each key (in bytes)
+        /// * `value_size`     - The size of each value (in bytes)
+        /// * `key_reg_bits`   - The type of the register (determined by its #bits) holding the key
+        /// * `key_reg_idx`    - The index of the register holding the key
+        /// * `value_reg_bits` - The type of the register (determined by its #bits) holding the value
+        /// * `value_reg_idx`  - The index of the register holding the value
+        pub fn hash_insert_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            key_size: i32,
+            value_size: i32,
+            key_reg_bits: i32,
+            key_reg_idx: i32,
+            value_reg_bits: i32,
+            value_reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Hash;
+
+            let key_size = collection_item_size(key_size, key_reg_bits)?;
+            let value_size = collection_item_size(value_size, value_reg_bits)?;
+
+            let key_reg = $crate::wasmer_data_reg!(ctx.data, key_reg_bits, key_reg_idx, $PC);
+            let key = key_reg.getn(key_size);
+
+            let value_reg = $crate::wasmer_data_reg!(ctx.data, value_reg_bits, value_reg_idx, $PC);
+            let value = value_reg.getn(value_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut hash = Hash::new(page, key_size, value_size)?;
+                hash.insert(&key, &value).map(|is_new| is_new as i32)
+            })
+        }
+
+        /// Removes the entry of the key held by register `key_reg` from the `Hash` stored at page `page_idx`.
+        /// Returns `1` when there was such entry and `0` otherwise.
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the hash
+        /// * `key_size`     - The size of each key (in bytes)
+        /// * `value_size`   - The size of each value (in bytes)
+        /// * `key_reg_bits` - The type of the register (determined by its #bits) holding the key
+        /// * `key_reg_idx`  - The index of the register holding the key
+        pub fn hash_remove(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            key_size: i32,
+            value_size: i32,
+            key_reg_bits: i32,
+            key_reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Hash;
+
+            let key_size = collection_item_size(key_size, key_reg_bits)?;
+
+            let key_reg = $crate::wasmer_data_reg!(ctx.data, key_reg_bits, key_reg_idx, $PC);
+            let key = key_reg.getn(key_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut hash = Hash::new(page, key_size, value_size as usize)?;
+                hash.remove(&key).map(|removed| removed as i32)
+            })
+        }
+
+        /// Returns the number of items of the `Set` stored at page `page_idx`
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the set
+        /// * `item_size` - The size of each set item (in bytes)
+        pub fn set_len(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Set;
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                Set::new(page, item_size as usize).map(|set| set.len() as i32)
+            })
+        }
+
+        /// Returns `1` when the item held by register `reg` is in the `Set` stored at page `page_idx`
+        /// and `0` otherwise.
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the set
+        /// * `item_size` - The size of each set item (in bytes)
+        /// * `reg_bits`  - The type of the register (determined by its #bits) holding the item
+        /// * `reg_idx`   - The index of the register holding the item
+        pub fn set_contains(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Set;
+
+            let item_size = collection_item_size(item_size, reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                let set = Set::new(page, item_size)?;
+                set.contains(&item).map(|found| found as i32)
+            })
+        }
+
+        /// Adds the item held by register `reg` to the `Set` stored at page `page_idx`.
+        /// Returns `1` when the item wasn't in the set and `0` otherwise.
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the set
+        /// * `item_size` - The size of each set item (in bytes)
+        /// * `reg_bits`  - The type of the register (determined by its #bits) holding the item
+        /// * `reg_idx`   - The index of the register holding the item
+        pub fn set_insert_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Set;
+
+            let item_size = collection_item_size(item_size, reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut set = Set::new(page, item_size)?;
+                set.insert(&item).map(|is_new| is_new as i32)
+            })
+        }
+
+        /// Removes the item held by register `reg` from the `Set` stored at page `page_idx`.
+        /// Returns `1` when the item was in the set and `0` otherwise.
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the set
+        /// * `item_size` - The size of each set item (in bytes)
+        /// * `reg_bits`  - The type of the register (determined by its #bits) holding the item
+        /// * `reg_idx`   - The index of the register holding the item
+        pub fn set_remove(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Set;
+
+            let item_size = collection_item_size(item_size, reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut set = Set::new(page, item_size)?;
+                set.remove(&item).map(|removed| removed as i32)
+            })
+        }
+
+        /// Returns the number of items of the `SortedSet` stored at page `page_idx`
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the sorted set
+        /// * `item_size` - The size of each sorted set item (in bytes)
+        pub fn sorted_set_len(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::SortedSet;
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                SortedSet::new(page, item_size as usize).map(|set| set.len() as i32)
+            })
+        }
+
+        /// Copies the item ranked `idx` (the smallest item is ranked `0`) of the `SortedSet` stored at page `page_idx`
+        /// into register `dst_reg`
+        ///
+        /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`     - The page holding the sorted set
+        /// * `item_size`    - The size of each sorted set item (in bytes)
+        /// * `idx`          - The item rank
+        /// * `dst_reg_bits` - The type of the register (determined by its #bits) we want to copy the item to
+        /// * `dst_reg_idx`  - The destination register index
+        pub fn sorted_set_get_to_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            idx: i32,
+            dst_reg_bits: i32,
+            dst_reg_idx: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::SortedSet;
+
+            let item_size = collection_item_size(item_size, dst_reg_bits)?;
+
+            let item =
+                $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                    let set = SortedSet::new(page, item_size)?;
+                    set.get(idx as u32).map(|item| item.to_vec())
+                })?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, dst_reg_bits, dst_reg_idx, $PC);
+            reg.set(&item);
+
+            Ok(())
+        }
+
+        /// Returns `1` when the item held by register `reg` is in the `SortedSet` stored at page `page_idx`
+        /// and `0` otherwise.
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the sorted set
+        /// * `item_size` - The size of each sorted set item (in bytes)
+        /// * `reg_bits`  - The type of the register (determined by its #bits) holding the item
+        /// * `reg_idx`   - The index of the register holding the item
+        pub fn sorted_set_contains(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::SortedSet;
+
+            let item_size = collection_item_size(item_size, reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                let set = SortedSet::new(page, item_size)?;
+                set.contains(&item).map(|found| found as i32)
+            })
+        }
+
+        /// Adds the item held by register `reg` to the `SortedSet` stored at page `page_idx`.
+        /// Returns `1` when the item wasn't in the sorted set and `0` otherwise.
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the sorted set
+        /// * `item_size` - The size of each sorted set item (in bytes)
+        /// * `reg_bits`  - The type of the register (determined by its #bits) holding the item
+        /// * `reg_idx`   - The index of the register holding the item
+        pub fn sorted_set_insert_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::SortedSet;
+
+            let item_size = collection_item_size(item_size, reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut set = SortedSet::new(page, item_size)?;
+                set.insert(&item).map(|is_new| is_new as i32)
+            })
+        }
+
+        /// Removes the item held by register `reg` from the `SortedSet` stored at page `page_idx`.
+        /// Returns `1` when the item was in the sorted set and `0` otherwise.
+        ///
+        /// * `ctx`       - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`  - The page holding the sorted set
+        /// * `item_size` - The size of each sorted set item (in bytes)
+        /// * `reg_bits`  - The type of the register (determined by its #bits) holding the item
+        /// * `reg_idx`   - The index of the register holding the item
+        pub fn sorted_set_remove(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            item_size: i32,
+            reg_bits: i32,
+            reg_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::SortedSet;
+
+            let item_size = collection_item_size(item_size, reg_bits)?;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, reg_bits, reg_idx, $PC);
+            let item = reg.getn(item_size);
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut set = SortedSet::new(page, item_size)?;
+                set.remove(&item).map(|removed| removed as i32)
+            })
+        }
+
+        /// Returns the number of bytes of the `Str` stored at page `page_idx`
+        ///
+        /// * `ctx`      - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx` - The page holding the string
+        pub fn string_len(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Str;
+
+            $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                Str::new(page).map(|s| s.len() as i32)
+            })
+        }
+
+        /// Copies the bytes of the `Str` stored at page `page_idx` into `wasmer` memory cells
+        /// `dst_mem_ptr, dst_mem_ptr + 1, .. , dst_mem_ptr + len` (exclusive).
+        /// Returns the number of copied bytes (`len`).
+        ///
+        /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`    - The page holding the string
+        /// * `dst_mem_idx` - The destination memory index we want to copy to
+        /// * `dst_mem_ptr` - The destination memory address to start copying the string into
+        pub fn string_read_to_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            dst_mem_idx: i32,
+            dst_mem_ptr: i32,
+        ) -> Result<i32, $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Str;
+
+            let bytes =
+                $crate::svm_collection_read!(ctx.data, page_idx, $PC, |page: &mut [u8]| {
+                    Str::new(page).map(|s| s.get().to_vec())
+                })?;
+
+            $crate::wasmer_ctx_mem_cells_write!(ctx, dst_mem_idx, dst_mem_ptr, bytes);
+
+            Ok(bytes.len() as i32)
+        }
+
+        /// Overrides the `Str` stored at page `page_idx` with `wasmer` memory cells
+        /// `src_mem_ptr, src_mem_ptr + 1, .. , src_mem_ptr + len` (exclusive)
+        ///
+        /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`    - The page holding the string
+        /// * `src_mem_idx` - The source memory index we want to copy from
+        /// * `src_mem_ptr` - The source memory address to start copying the string from
+        /// * `len`         - The number of bytes to copy
+        pub fn string_write_from_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            src_mem_idx: i32,
+            src_mem_ptr: i32,
+            len: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Str;
+
+            let cells = $crate::wasmer_ctx_mem_cells!(ctx, src_mem_idx, src_mem_ptr, len);
+            let bytes = cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut s = Str::new(page)?;
+                s.set(&bytes)
+            })
+        }
+
+        /// Appends `wasmer` memory cells `src_mem_ptr, src_mem_ptr + 1, .. , src_mem_ptr + len` (exclusive)
+        /// to the end of the `Str` stored at page `page_idx`
+        ///
+        /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `page_idx`    - The page holding the string
+        /// * `src_mem_idx` - The source memory index we want to copy from
+        /// * `src_mem_ptr` - The source memory address to start copying the appended bytes from
+        /// * `len`         - The number of bytes to append
+        pub fn string_append_from_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            page_idx: i32,
+            src_mem_idx: i32,
+            src_mem_ptr: i32,
+            len: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use svm_storage::collections::Str;
+
+            let cells = $crate::wasmer_ctx_mem_cells!(ctx, src_mem_idx, src_mem_ptr, len);
+            let bytes = cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
+
+            $crate::svm_collection_write!(ctx, page_idx, $PC, |page: &mut [u8]| {
+                let mut s = Str::new(page)?;
+                s.append(&bytes)
+            })
+        }
+    };
+}
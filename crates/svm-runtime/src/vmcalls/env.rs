@@ -0,0 +1,114 @@
+/// When called, injects the code of the `svm` env vmcalls.
+/// The `vmcalls` are functions imported into each running `svm` instance.
+///
+/// The env vmcalls expose the execution environment of the running contract (see `ExecEnv`).
+#[macro_export]
+macro_rules! include_svm_env_vmcalls {
+    ($PC: path) => {
+        /// Copies the address of the account calling the running contract into register `160:reg_idx`.
+        /// It's the transaction sender, or the calling contract (for contracts called by other contracts).
+        ///
+        /// * `ctx`     - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `reg_idx` - The index of the `SvmReg160` register to copy the sender address into
+        pub fn get_sender_to_reg(ctx: &mut wasmer_runtime::Ctx, reg_idx: i32) {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let sender = svm_ctx.env.sender.clone();
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, 160, reg_idx, $PC);
+            reg.set(sender.as_slice());
+        }
+
+        /// Copies the address of the account calling the running contract into `wasmer` memory cells
+        /// `dst_mem_ptr, dst_mem_ptr + 1, .. , dst_mem_ptr + Address::len()` (exclusive).
+        ///
+        /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `dst_mem_idx` - The destination memory index we want to copy to
+        /// * `dst_mem_ptr` - The destination memory address to start copying the sender address into
+        pub fn get_sender_to_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            dst_mem_idx: i32,
+            dst_mem_ptr: i32,
+        ) {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let sender = svm_ctx.env.sender.clone();
+
+            $crate::wasmer_ctx_mem_cells_write!(ctx, dst_mem_idx, dst_mem_ptr, sender.as_slice());
+        }
+
+        /// Copies the address of the running contract into register `160:reg_idx`.
+        ///
+        /// * `ctx`     - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `reg_idx` - The index of the `SvmReg160` register to copy the contract address into
+        pub fn get_self_address_to_reg(ctx: &mut wasmer_runtime::Ctx, reg_idx: i32) {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let addr = svm_ctx.env.contract.clone();
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, 160, reg_idx, $PC);
+            reg.set(addr.as_slice());
+        }
+
+        /// Copies the address of the running contract into `wasmer` memory cells
+        /// `dst_mem_ptr, dst_mem_ptr + 1, .. , dst_mem_ptr + Address::len()` (exclusive).
+        ///
+        /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `dst_mem_idx` - The destination memory index we want to copy to
+        /// * `dst_mem_ptr` - The destination memory address to start copying the contract address into
+        pub fn get_self_address_to_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            dst_mem_idx: i32,
+            dst_mem_ptr: i32,
+        ) {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let addr = svm_ctx.env.contract.clone();
+
+            $crate::wasmer_ctx_mem_cells_write!(ctx, dst_mem_idx, dst_mem_ptr, addr.as_slice());
+        }
+
+        /// Returns the id of the layer the transaction is executed in
+        ///
+        /// * `ctx` - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        pub fn get_layer_id(ctx: &mut wasmer_runtime::Ctx) -> i64 {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+
+            svm_ctx.env.layer.id as i64
+        }
+
+        /// Returns the timestamp of the layer the transaction is executed in
+        ///
+        /// * `ctx` - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        pub fn get_layer_timestamp(ctx: &mut wasmer_runtime::Ctx) -> i64 {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+
+            svm_ctx.env.layer.timestamp as i64
+        }
+
+        /// Copies the layer randomness beacon (32 bytes) into register `256:reg_idx`.
+        ///
+        /// * `ctx`     - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `reg_idx` - The index of the `SvmReg256` register to copy the beacon into
+        pub fn get_beacon_to_reg(ctx: &mut wasmer_runtime::Ctx, reg_idx: i32) {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let beacon = svm_ctx.env.layer.beacon;
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, 256, reg_idx, $PC);
+            reg.set(&beacon);
+        }
+
+        /// Copies the layer randomness beacon into `wasmer` memory cells
+        /// `dst_mem_ptr, dst_mem_ptr + 1, .. , dst_mem_ptr + 32` (exclusive).
+        ///
+        /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `dst_mem_idx` - The destination memory index we want to copy to
+        /// * `dst_mem_ptr` - The destination memory address to start copying the beacon into
+        pub fn get_beacon_to_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            dst_mem_idx: i32,
+            dst_mem_ptr: i32,
+        ) {
+            let svm_ctx = $crate::cast_wasmer_data_to_svm_ctx!(ctx.data, $PC);
+            let beacon = svm_ctx.env.layer.beacon;
+
+            $crate::wasmer_ctx_mem_cells_write!(ctx, dst_mem_idx, dst_mem_ptr, beacon);
+        }
+    };
+}
@@ -0,0 +1,69 @@
+/// When called, injects the code of the `svm` events vmcalls.
+/// The `vmcalls` are functions imported into each running `svm` instance.
+#[macro_export]
+macro_rules! include_svm_event_vmcalls {
+    ($PC: path) => {
+        /// Emits an event whose payload data is copied from `wasmer` memory cells under addresses:
+        /// `src_mem_ptr, src_mem_ptr + 1, .. , src_mem_ptr + len (exclusive)`
+        ///
+        /// * `ctx`           - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `topic_reg_idx` - The index of the `SvmReg256` register holding the event topic
+        /// * `src_mem_idx`   - The memory index we start to copy from
+        /// * `src_mem_ptr`   - Memory address to start copying from
+        /// * `len`           - #memory cells to copy. Must be within the memory bounds
+        /// * `expiration`    - The number of layers the event should be kept for
+        ///
+        /// Returns an error when the memory cells range is out of the memory bounds.
+        pub fn emit_event_from_mem(
+            ctx: &mut wasmer_runtime::Ctx,
+            topic_reg_idx: i32,
+            src_mem_idx: i32,
+            src_mem_ptr: i32,
+            len: i32,
+            expiration: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use $crate::runtime::ContractExecError;
+
+            let mem_len = ctx.memory(src_mem_idx as u32).view::<u8>().len() as i64;
+
+            if len < 0 || src_mem_ptr < 0 || i64::from(src_mem_ptr) + i64::from(len) > mem_len {
+                return Err(ContractExecError::InvalidEventLength(len));
+            }
+
+            let cells = $crate::wasmer_ctx_mem_cells!(ctx, src_mem_idx, src_mem_ptr, len);
+            let data = cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
+
+            $crate::svm_emit_event!(ctx, topic_reg_idx, data, expiration, $PC)
+        }
+
+        /// Emits an event whose payload data is copied from `svm wasmer` register
+        ///
+        /// * `ctx`           - `wasmer` context (holds a `data` field. we use `SvmCtx`)
+        /// * `topic_reg_idx` - The index of the `SvmReg256` register holding the event topic
+        /// * `src_reg_bits`  - The type of the register (determined by its #bits) we want to copy data from
+        /// * `src_reg_idx`   - Source register to start copying from
+        /// * `len`           - #register bytes to copy. Must not exceed the register capacity
+        /// * `expiration`    - The number of layers the event should be kept for
+        ///
+        /// Returns an error when `len` is negative or exceeds the register capacity.
+        pub fn emit_event_from_reg(
+            ctx: &mut wasmer_runtime::Ctx,
+            topic_reg_idx: i32,
+            src_reg_bits: i32,
+            src_reg_idx: i32,
+            len: i32,
+            expiration: i32,
+        ) -> Result<(), $crate::runtime::ContractExecError> {
+            use $crate::runtime::ContractExecError;
+
+            if len < 0 || len > src_reg_bits / 8 {
+                return Err(ContractExecError::InvalidEventLength(len));
+            }
+
+            let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
+            let data = reg.getn(len as usize);
+
+            $crate::svm_emit_event!(ctx, topic_reg_idx, data, expiration, $PC)
+        }
+    };
+}
@@ -1,6 +1,13 @@
+mod admins;
+mod balance;
+mod collections;
+mod env;
+mod event;
 mod register;
 mod storage;
 
+use crate::ctx::{EVENT_DATA_BYTE_GAS, EVENT_GAS};
+
 /// When called, injects the code of the `svm vmcalls`.
 /// The `vmcalls` are functions imported into each running `svm` instance.
 #[macro_export]
@@ -9,6 +16,61 @@ macro_rules! include_svm_vmcalls {
         mod vmcalls {
             $crate::include_svm_storage_vmcalls!($PC);
             $crate::include_svm_register_vmcalls!($PC);
+            $crate::include_svm_event_vmcalls!($PC);
+            $crate::include_svm_collections_vmcalls!($PC);
+            $crate::include_svm_admins_vmcalls!($PC);
+            $crate::include_svm_balance_vmcalls!($PC);
+            $crate::include_svm_env_vmcalls!($PC);
         }
     };
 }
+
+/// Returns the fixed gas charged by the runtime for calling the imported function `module.field`
+/// (on top of the `call` opcode metered by the calling code).
+/// Used for the ahead-of-time gas estimation of the contracts (see `svm_compiler::gas::estimate_program_gas`).
+///
+/// Returns `None` when the gas isn't fixed: for the vmcalls writing to the contract storage (may grow it,
+/// see `PAGE_GROW_GAS`), for `emit_event_from_mem` (charged per data byte), for `contract_call`,
+/// and for functions imported from the `node` or from the contract dependencies.
+pub fn import_gas(module: &str, field: &str) -> Option<u64> {
+    if module != "svm" {
+        return None;
+    }
+
+    match field {
+        // the largest register holds `64 bytes`
+        "emit_event_from_reg" => Some(EVENT_GAS + 64 * EVENT_DATA_BYTE_GAS),
+        "mem_to_reg_copy"
+        | "reg_to_mem_copy"
+        | "storage_read_to_reg"
+        | "storage_read_to_mem"
+        | "reg_replace_byte"
+        | "reg_read_be_i64"
+        | "reg_write_be_i64"
+        | "array_len"
+        | "array_get_to_reg"
+        | "list_len"
+        | "list_get_to_reg"
+        | "hash_len"
+        | "hash_get_to_reg"
+        | "set_len"
+        | "set_contains"
+        | "sorted_set_len"
+        | "sorted_set_get_to_reg"
+        | "sorted_set_contains"
+        | "string_len"
+        | "string_read_to_mem"
+        | "is_admin"
+        | "get_value"
+        | "get_self_balance"
+        | "get_sender_to_reg"
+        | "get_sender_to_mem"
+        | "get_self_address_to_reg"
+        | "get_self_address_to_mem"
+        | "get_layer_id"
+        | "get_layer_timestamp"
+        | "get_beacon_to_reg"
+        | "get_beacon_to_mem" => Some(0),
+        _ => None,
+    }
+}
@@ -118,7 +118,8 @@ macro_rules! include_svm_storage_vmcalls {
             $crate::wasmer_ctx_mem_cells_write!(ctx, dst_mem_idx, dst_mem_ptr, slice);
         }
 
-        /// Writes into `svm` storage, a page-slice copied from `wasmer` memory
+        /// Writes into `svm` storage, a page-slice copied from `wasmer` memory.
+        /// Fails the execution when the page-slice is out of range (see `svm_storage_write`).
         ///
         /// * `ctx`         - `wasmer` context (holds a `data` field. we use `SvmCtx`)
         /// * `src_mem_idx` - The memory index we start to copy from
@@ -135,22 +136,24 @@ macro_rules! include_svm_storage_vmcalls {
             dst_page: i32,
             dst_slice: i32,
             dst_offset: i32,
-        ) {
+        ) -> Result<(), $crate::runtime::ContractExecError> {
             let cells = $crate::wasmer_ctx_mem_cells!(ctx, src_mem_idx, src_mem_ptr, len);
             let data = cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
-            let storage = $crate::wasmer_data_storage!(ctx.data, $PC);
 
-            let slice = $crate::svm_write_page_slice!(
-                storage,
-                dst_page as u32,
-                dst_slice as u32,
-                dst_offset as u32,
-                len as u32,
-                &data
-            );
+            $crate::svm_storage_write!(ctx, $PC, |storage| {
+                $crate::svm_write_page_slice!(
+                    storage,
+                    dst_page as u32,
+                    dst_slice as u32,
+                    dst_offset as u32,
+                    len as u32,
+                    &data
+                )
+            })
         }
 
-        /// Writes into `svm` storage, a page-slice copied from `svm wasmer` register
+        /// Writes into `svm` storage, a page-slice copied from `svm wasmer` register.
+        /// Fails the execution when the page-slice is out of range (see `svm_storage_write`).
         ///
         /// * `ctx`          - `wasmer` context (holds a `data` field. we use `SvmCtx`)
         /// * `src_reg_bits` - The type of the register (determined by its #bits) we want to copy data from
@@ -167,19 +170,20 @@ macro_rules! include_svm_storage_vmcalls {
             dst_page: i32,
             dst_slice: i32,
             dst_offset: i32,
-        ) {
+        ) -> Result<(), $crate::runtime::ContractExecError> {
             let reg = $crate::wasmer_data_reg!(ctx.data, src_reg_bits, src_reg_idx, $PC);
-            let storage = $crate::wasmer_data_storage!(ctx.data, $PC);
             let data = reg.getn(len as usize);
 
-            let slice = $crate::svm_write_page_slice!(
-                storage,
-                dst_page as u32,
-                dst_slice as u32,
-                dst_offset as u32,
-                len as u32,
-                &data
-            );
+            $crate::svm_storage_write!(ctx, $PC, |storage| {
+                $crate::svm_write_page_slice!(
+                    storage,
+                    dst_page as u32,
+                    dst_slice as u32,
+                    dst_offset as u32,
+                    len as u32,
+                    &data
+                )
+            })
         }
     };
 }
@@ -0,0 +1,94 @@
+#![cfg(feature = "leveldb")]
+
+use svm_common::{Address, State};
+use svm_contract::build::{WireContractBuilder, WireTxBuilder};
+use svm_contract::wasm::WasmArgValue as Value;
+
+use svm_storage::page::{PageIndex, PageSliceLayout, SliceIndex};
+use svm_storage::PageSliceCache;
+
+// Injects `svm` runtime backed by `leveldb` into the current file.
+svm_runtime::include_svm_leveldb_runtime!(
+    "tests-leveldb-contract-storage",
+    "tests-leveldb-contract-code"
+);
+
+#[test]
+fn leveldb_contract_exec_valid_transaction() {
+    let wasm = wabt::wat2wasm(include_str!("wasm/runtime-1.wast")).unwrap();
+
+    // the database outlives a single run, so each run deploys the contract under a new address
+    let nonce = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos() as u64;
+
+    let bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Contract #1")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&wasm[..])
+        .with_nonce(nonce)
+        .build();
+
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    let bytes = WireTxBuilder::new()
+        .with_version(0)
+        .with_contract(addr.clone())
+        .with_sender(Address::from(0x11_22_33_44))
+        .with_func_name("reg_set_and_persist")
+        .with_func_args(&[
+            Value::I64(0x10_20_30_40_50_60_70_80),
+            Value::I32(64),
+            Value::I32(0),
+            Value::I32(0),
+            Value::I32(0),
+            Value::I32(0),
+        ])
+        .build();
+
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let opts = svm_runtime::opts::Opts {
+        max_pages: 10,
+        max_pages_slices: 100,
+        gas_limit: 1_000_000,
+        state_resolver: None,
+        balance_resolver: None,
+        layer: svm_runtime::opts::Layer::default(),
+    };
+
+    let import_object =
+        runtime::import_object_create(tx.contract.clone(), State::from(0), std::ptr::null(), opts);
+
+    let receipt = runtime::contract_exec(tx, &import_object);
+    assert_eq!(true, receipt.success);
+    assert_eq!(None, receipt.error);
+
+    let new_state = receipt.new_state.unwrap();
+
+    let pages_storage = svm_runtime::gen_leveldb_pages_storage!(
+        addr,
+        new_state,
+        10,
+        "tests-leveldb-contract-storage"
+    );
+    let page_cache = svm_runtime::gen_leveldb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(0),
+        page_idx: PageIndex(0),
+        offset: 0,
+        len: 8,
+    };
+
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(
+        &[0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80],
+        &slice[..]
+    );
+}
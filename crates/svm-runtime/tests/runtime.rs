@@ -1,13 +1,31 @@
-use svm_common::{Address, State};
+use svm_common::{Address, Balance, State};
 use svm_contract::build::{WireContractBuilder, WireTxBuilder};
-use svm_contract::wasm::WasmArgValue as Value;
+use svm_contract::transaction::Transaction;
+use svm_contract::types::Dependency;
+use svm_contract::wasm::{WasmArgValue as Value, WasmIntType};
+
+use svm_runtime::runtime::ContractExecError;
 
 use svm_storage::page::{PageIndex, PageSliceLayout, SliceIndex};
 use svm_storage::PageSliceCache;
 
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
 // Injects `svm` runtime backed by `rocksdb` into the current file.
 svm_runtime::include_svm_rocksdb_runtime!("tests-contract-storage", "tests-contract-code");
 
+/// The tests databases outlive a single run, and some tests deploy the same code (by the same author).
+/// So each deployed contract is given a unique nonce (the contract address is derived from it).
+fn unique_nonce() -> u64 {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+
+    now.as_nanos() as u64 + COUNTER.fetch_add(1, Ordering::SeqCst)
+}
+
 macro_rules! build_raw_contract {
     ($version: expr, $name: expr, $author: expr, $file: expr) => {{
         let wasm = load_wasm_file!($file);
@@ -17,6 +35,7 @@ macro_rules! build_raw_contract {
             .with_name($name)
             .with_author(Address::from($author))
             .with_code(&wasm[..])
+            .with_nonce(unique_nonce())
             .build()
     }};
 }
@@ -35,9 +54,24 @@ macro_rules! build_raw_tx {
 
 macro_rules! exec_tx {
     ($tx: expr, $state: expr) => {{
+        exec_tx!($tx, $state, None)
+    }};
+    ($tx: expr, $state: expr, $state_resolver: expr) => {{
+        exec_tx!(
+            $tx,
+            $state,
+            $state_resolver,
+            svm_runtime::opts::Layer::default()
+        )
+    }};
+    ($tx: expr, $state: expr, $state_resolver: expr, $layer: expr) => {{
         let opts = svm_runtime::opts::Opts {
             max_pages: 10,
             max_pages_slices: 100,
+            gas_limit: 1_000_000,
+            state_resolver: $state_resolver,
+            balance_resolver: None,
+            layer: $layer,
         };
 
         let import_object =
@@ -47,6 +81,54 @@ macro_rules! exec_tx {
     }};
 }
 
+macro_rules! deploy_contract {
+    ($name: expr, $file: expr) => {{
+        let bytes = build_raw_contract!(2, $name, 0x10_20_30_40, $file);
+        let contract = runtime::contract_build(&bytes).unwrap();
+        let addr = runtime::contract_compute_address(&contract);
+        runtime::contract_store(&contract, &addr).unwrap();
+
+        addr
+    }};
+}
+
+macro_rules! call_inc_tx {
+    ($caller: expr, $callee: expr, $value: expr) => {{
+        Transaction {
+            contract: $caller,
+            sender: Address::from(0x11_22_33_44),
+            gas_limit: 1_000_000,
+            gas_price: 1,
+            value: Balance(0),
+            func_name: "call_inc".to_string(),
+            func_args: vec![
+                Value::Fixed(WasmIntType::I32, $callee.as_slice().to_vec()),
+                Value::I64($value),
+            ],
+        }
+    }};
+}
+
+static ZERO_STATE: [u8; 32] = [0; 32];
+
+/// The called contracts run for the first time
+unsafe extern "C" fn zero_state_resolver(
+    _node_data: *const c_void,
+    _addr: *const c_void,
+) -> *const c_void {
+    ZERO_STATE.as_ptr() as *const c_void
+}
+
+static MISSING_STATE: [u8; 32] = [0xFF; 32];
+
+/// The called contracts have a state which isn't found under the contracts storage
+unsafe extern "C" fn missing_state_resolver(
+    _node_data: *const c_void,
+    _addr: *const c_void,
+) -> *const c_void {
+    MISSING_STATE.as_ptr() as *const c_void
+}
+
 macro_rules! load_wasm_file {
     ($file: expr) => {{
         let wasm = include_str!($file);
@@ -66,7 +148,63 @@ fn deploy_wasm_contract() {
 
     let contract = runtime::contract_build(&bytes).unwrap();
     let addr = runtime::contract_compute_address(&contract);
-    runtime::contract_store(&contract, &addr);
+    runtime::contract_store(&contract, &addr).unwrap();
+}
+
+macro_rules! build_wat_contract {
+    ($wat: expr) => {{
+        let wasm = wabt::wat2wasm($wat).unwrap();
+
+        let bytes = WireContractBuilder::new()
+            .with_version(0)
+            .with_name("Contract")
+            .with_author(Address::from(0x10_20_30_40))
+            .with_code(&wasm[..])
+            .build();
+
+        runtime::contract_build(&bytes)
+    }};
+}
+
+#[test]
+fn deploy_contract_with_unknown_svm_import() {
+    let res = build_wat_contract!(
+        r#"(module
+             (func (import "svm" "no_such_vmcall"))
+             (func (export "run")))"#
+    );
+
+    assert_eq!(
+        "Unsupported import: `svm.no_such_vmcall`",
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn deploy_contract_with_node_import() {
+    let res = build_wat_contract!(
+        r#"(module
+             (func (import "node" "vmcall_get_counter") (result i32))
+             (func (export "run")))"#
+    );
+
+    assert!(res.is_ok());
+}
+
+#[test]
+fn deploy_contract_using_floats() {
+    let res = build_wat_contract!(
+        r#"(module
+             (func (export "run") (param i32) (result i32)
+               get_local 0
+               f32.convert_u/i32
+               i32.trunc_u/f32))"#
+    );
+
+    assert_eq!(
+        "Unsupported opcode (function: `0`)",
+        format!("{:?}", res.unwrap_err())
+    );
 }
 
 #[test]
@@ -79,14 +217,14 @@ fn contract_exec_non_existing_contract() {
 fn contract_exec_valid_transaction() {
     // 1) deploying the contract
     let bytes = build_raw_contract!(
-        0,                     // protocol version
+        2,                     // protocol version
         "Contract #1",         // contract name
         0x10_20_30_40,         // author address
         "wasm/runtime-1.wast"  // file holding the wasm code
     );
     let contract = runtime::contract_build(&bytes).unwrap();
     let addr = runtime::contract_compute_address(&contract);
-    runtime::contract_store(&contract, &addr);
+    runtime::contract_store(&contract, &addr).unwrap();
 
     // 2) executing a transaction `reg_set_and_persist`
     // setting register `64:0` the value `1000`.
@@ -135,6 +273,912 @@ fn contract_exec_valid_transaction() {
     );
 }
 
+#[test]
+fn contract_exec_with_value() {
+    use svm_runtime::runtime::Transfer;
+
+    let bytes = build_raw_contract!(2, "Value", 0x10_20_30_40, "wasm/value.wast");
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    macro_rules! value_tx {
+        ($func_name: expr) => {{
+            let bytes = WireTxBuilder::new()
+                .with_version(2)
+                .with_contract(addr.clone())
+                .with_sender(Address::from(0x11_22_33_44))
+                .with_gas_limit(1_000_000)
+                .with_gas_price(1)
+                .with_value(Balance(1_000))
+                .with_func_name($func_name)
+                .with_func_args(&[])
+                .build();
+
+            runtime::transaction_build(&bytes).unwrap()
+        }};
+    }
+
+    let receipt = exec_tx!(value_tx!("keep_value"), State::empty());
+    assert_eq!(true, receipt.success);
+    assert_eq!(
+        vec![Transfer {
+            from: Address::from(0x11_22_33_44),
+            to: addr.clone(),
+            amount: Balance(1_000),
+        }],
+        receipt.transfers
+    );
+
+    let new_state = receipt.new_state.unwrap();
+    let pages_storage =
+        svm_runtime::gen_rocksdb_pages_storage!(addr, new_state, 10, "tests-contract-storage");
+    let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(0),
+        page_idx: PageIndex(0),
+        offset: 0,
+        len: 16,
+    };
+
+    // the value (Little-Endian) has been persisted by the contract
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(Balance(1_000), Balance::from(slice.as_ptr()));
+
+    // no value is transferred when the transaction fails
+    let receipt = exec_tx!(value_tx!("no_such_func"), State::empty());
+    assert_eq!(false, receipt.success);
+    assert!(receipt.transfers.is_empty());
+}
+
+#[test]
+fn contract_exec_env() {
+    use svm_runtime::opts::Layer;
+
+    let bytes = build_raw_contract!(2, "Env", 0x10_20_30_40, "wasm/keep_env.wast");
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    let layer = Layer {
+        id: 7,
+        timestamp: 1_000,
+        beacon: [0xAB; 32],
+    };
+
+    let bytes = build_raw_tx!(0, addr.clone(), 0x11_22_33_44, "keep_env", &[]);
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::empty(), None, layer);
+    assert_eq!(true, receipt.success);
+
+    let new_state = receipt.new_state.unwrap();
+    let pages_storage =
+        svm_runtime::gen_rocksdb_pages_storage!(addr, new_state, 10, "tests-contract-storage");
+    let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(0),
+        page_idx: PageIndex(0),
+        offset: 0,
+        len: 40,
+    };
+
+    // the sender address and the contract address have been persisted by the contract
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(Address::from(0x11_22_33_44).as_slice(), &slice[0..20]);
+    assert_eq!(addr.as_slice(), &slice[20..40]);
+
+    let bytes = build_raw_tx!(0, addr.clone(), 0x11_22_33_44, "layer_id", &[]);
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::empty(), None, layer);
+    assert_eq!(true, receipt.success);
+    assert_eq!(vec![wasmer_runtime::Value::I64(7)], receipt.results);
+}
+
+#[test]
+fn global_state_updated_from_receipt() {
+    use svm_contract::default::DefaultCodeHasher;
+    use svm_contract::traits::ContractCodeHasher;
+    use svm_kv::memory::MemKVStore;
+    use svm_storage::global_state::GlobalState;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let bytes = build_raw_contract!(2, "Contract #1", 0x10_20_30_40, "wasm/runtime-1.wast");
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    let kv = Rc::new(RefCell::new(MemKVStore::new()));
+    let mut gs = GlobalState::new(kv, State::empty()).unwrap();
+    gs.add_account(addr.clone(), DefaultCodeHasher::hash(&contract.wasm))
+        .unwrap();
+    let root1 = gs.commit().unwrap();
+
+    let bytes = build_raw_tx!(
+        0,
+        addr.clone(),
+        0x11_22_33_44,
+        "reg_set_and_persist",
+        &[
+            Value::I64(0x10_20_30_40_50_60_70_80),
+            Value::I32(64),
+            Value::I32(0),
+            Value::I32(0),
+            Value::I32(0),
+            Value::I32(0)
+        ]
+    );
+    let tx = runtime::transaction_build(&bytes).unwrap();
+    let receipt = exec_tx!(tx, State::from(0));
+
+    for (addr, state) in receipt.changed_states() {
+        gs.set_state(&addr, state).unwrap();
+    }
+
+    let root2 = gs.commit().unwrap();
+    assert_ne!(root1, root2);
+    assert_eq!(
+        receipt.new_state,
+        gs.get_account(&addr).unwrap().map(|acc| acc.state)
+    );
+
+    // the account is proven against the new global root
+    assert!(gs.prove(&addr).unwrap().verify(&root2, &addr));
+}
+
+#[test]
+fn contract_export_gas_estimation() {
+    use svm_compiler::gas::GasEstimate;
+
+    let addr = deploy_contract!("Env", "wasm/env.wast");
+
+    // the `env` vmcalls have no gas of their own (only the `call` opcode is charged)
+    let estimate = runtime::contract_export_gas(&addr, "do_get_layer_id");
+    assert_eq!(Ok(GasEstimate::Fixed(10)), estimate);
+
+    // `get_local` + `call`
+    let estimate = runtime::contract_export_gas(&addr, "do_get_sender_to_reg");
+    assert_eq!(Ok(GasEstimate::Fixed(1 + 10)), estimate);
+
+    let estimate = runtime::contract_export_gas(&addr, "no_such_func");
+    assert_eq!(
+        Err(ContractExecError::FuncNotFound("no_such_func".to_string())),
+        estimate
+    );
+
+    let addr = deploy_contract!("Infinite Loop", "wasm/infinite_loop.wast");
+
+    let estimate = runtime::contract_export_gas(&addr, "run");
+    assert_eq!(Ok(GasEstimate::HasLoops), estimate);
+}
+
+#[test]
+fn contract_exec_out_of_gas() {
+    let bytes = build_raw_contract!(
+        2,                         // protocol version
+        "Contract #2",             // contract name
+        0x10_20_30_40,             // author address
+        "wasm/infinite_loop.wast"  // file holding the wasm code
+    );
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    let bytes = WireTxBuilder::new()
+        .with_version(1)
+        .with_contract(addr)
+        .with_sender(Address::from(0x11_22_33_44))
+        .with_gas_limit(5_000)
+        .with_gas_price(1)
+        .with_func_name("run")
+        .with_func_args(&[])
+        .build();
+
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(false, receipt.success);
+    assert_eq!(Some(ContractExecError::OutOfGas), receipt.error);
+    assert_eq!(5_000, receipt.gas_used);
+    assert_eq!(None, receipt.new_state);
+}
+
+#[test]
+fn contract_exec_calling_another_contract() {
+    let callee = deploy_contract!("Callee", "wasm/callee.wast");
+    let caller = deploy_contract!("Caller", "wasm/caller.wast");
+
+    let tx = call_inc_tx!(caller, callee, 10);
+
+    let receipt = exec_tx!(tx, State::from(0), Some(zero_state_resolver));
+    assert_eq!(true, receipt.success);
+    assert_eq!(vec![wasmer_runtime::Value::I64(11)], receipt.results);
+    assert_eq!(1, receipt.callees_states.len());
+
+    let (addr, state) = receipt.callees_states[0].clone();
+    assert_eq!(callee, addr);
+    assert_ne!(State::from(0), state);
+
+    let pages_storage =
+        svm_runtime::gen_rocksdb_pages_storage!(addr, state, 10, "tests-contract-storage");
+    let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(0),
+        page_idx: PageIndex(0),
+        offset: 0,
+        len: 8,
+    };
+
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(&[0, 0, 0, 0, 0, 0, 0, 11], &slice[..]);
+}
+
+#[test]
+fn contract_exec_nested_calls() {
+    // `caller -> relay -> callee`. Each call opens the contracts store while the calling contracts are running
+    let callee = deploy_contract!("Callee", "wasm/callee.wast");
+
+    let callee_bytes = callee
+        .as_slice()
+        .iter()
+        .map(|byte| format!("\\{:02x}", byte))
+        .collect::<String>();
+    let relay_wast = include_str!("wasm/relay.wast").replace("{callee}", &callee_bytes);
+    let relay_wasm = wabt::wat2wasm(&relay_wast).unwrap();
+
+    let bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Relay")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&relay_wasm[..])
+        .with_nonce(unique_nonce())
+        .build();
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let relay = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &relay).unwrap();
+
+    let caller = deploy_contract!("Caller", "wasm/caller.wast");
+
+    let tx = call_inc_tx!(caller, relay, 10);
+
+    let receipt = exec_tx!(tx, State::from(0), Some(zero_state_resolver));
+    assert_eq!(true, receipt.success);
+    assert_eq!(vec![wasmer_runtime::Value::I64(11)], receipt.results);
+
+    // both the relay and the callee new states are recorded
+    let addrs = receipt
+        .callees_states
+        .iter()
+        .map(|(addr, _state)| addr.clone())
+        .collect::<Vec<Address>>();
+    assert_eq!(2, addrs.len());
+    assert!(addrs.contains(&relay));
+    assert!(addrs.contains(&callee));
+}
+
+#[test]
+fn contract_exec_calling_contract_with_unresolved_state() {
+    let callee = deploy_contract!("Callee", "wasm/callee.wast");
+    let caller = deploy_contract!("Caller", "wasm/caller.wast");
+
+    let tx = call_inc_tx!(caller, callee, 10);
+
+    // without a state resolver the call fails (and the caller reads the zeroed results register)
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(true, receipt.success);
+    assert_eq!(vec![wasmer_runtime::Value::I64(0)], receipt.results);
+    assert!(receipt.callees_states.is_empty());
+}
+
+#[test]
+fn contract_exec_calling_contract_with_failing_storage() {
+    let callee = deploy_contract!("Callee", "wasm/callee.wast");
+    let caller = deploy_contract!("Caller", "wasm/caller.wast");
+
+    let tx = call_inc_tx!(caller, callee, 10);
+
+    // the callee storage failure isn't reported to the caller (as `-1`) but fails the transaction
+    let receipt = exec_tx!(tx, State::from(0), Some(missing_state_resolver));
+    assert_eq!(false, receipt.success);
+
+    match receipt.error {
+        Some(ContractExecError::StorageFailed(..)) => (),
+        e => panic!("expected `StorageFailed` error (got: {:?})", e),
+    }
+
+    assert_eq!(None, receipt.new_state);
+    assert!(receipt.callees_states.is_empty());
+}
+
+#[test]
+fn contract_exec_with_slice_arg() {
+    let addr = deploy_contract!("Slice", "wasm/slice_arg.wast");
+
+    let bytes = build_raw_tx!(
+        0,               // protocol version
+        addr.clone(),    // contract address
+        0x11_22_33_44,   // sender address
+        "persist_slice", // `func_name` to execute
+        // `func_args` (the slice is passed as `(offset: i32, length: i64)`)
+        &[Value::Slice(
+            WasmIntType::I32,
+            WasmIntType::I64,
+            b"Hello World".to_vec()
+        )]
+    );
+
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(true, receipt.success);
+    assert_eq!(vec![wasmer_runtime::Value::I64(11)], receipt.results);
+
+    let new_state = receipt.new_state.unwrap();
+
+    let pages_storage =
+        svm_runtime::gen_rocksdb_pages_storage!(addr, new_state, 10, "tests-contract-storage");
+    let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(0),
+        page_idx: PageIndex(0),
+        offset: 0,
+        len: 11,
+    };
+
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(b"Hello World", &slice[..]);
+}
+
+#[test]
+fn contract_exec_with_slice_arg_exceeding_memory() {
+    let addr = deploy_contract!("Slice", "wasm/slice_arg.wast");
+
+    // the contract memory has a single page (64KB)
+    let bytes = build_raw_tx!(
+        0,
+        addr.clone(),
+        0x11_22_33_44,
+        "persist_slice",
+        &[Value::Slice(
+            WasmIntType::I32,
+            WasmIntType::I64,
+            vec![0xFF; 65536 + 1]
+        )]
+    );
+
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(false, receipt.success);
+    assert_eq!(
+        Some(ContractExecError::ArgsTooLarge(65536 + 1)),
+        receipt.error
+    );
+}
+
+macro_rules! reg_set_and_persist_tx {
+    ($addr: expr, $page_idx: expr) => {{
+        let bytes = build_raw_tx!(
+            0,
+            $addr,
+            0x11_22_33_44,
+            "reg_set_and_persist",
+            &[
+                Value::I64(0x10_20_30_40_50_60_70_80),
+                Value::I32(64),
+                Value::I32(0),
+                Value::I32($page_idx),
+                Value::I32(0),
+                Value::I32(0)
+            ]
+        );
+
+        runtime::transaction_build(&bytes).unwrap()
+    }};
+}
+
+#[test]
+fn contract_exec_storage_grows_beyond_deploy_pages_count() {
+    let wasm = load_wasm_file!("wasm/runtime-1.wast");
+    let bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Pages Count")
+        .with_author(Address::from(0x20_30_40_50))
+        .with_pages_count(3)
+        .with_code(&wasm[..])
+        .with_nonce(unique_nonce())
+        .build();
+
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    macro_rules! exec_and_count_pages {
+        ($page_idx: expr) => {{
+            let tx = reg_set_and_persist_tx!(addr.clone(), $page_idx);
+            let receipt = exec_tx!(tx, State::from(0));
+            assert_eq!(true, receipt.success);
+
+            let new_state = receipt.new_state.unwrap();
+            let pages_storage = svm_runtime::gen_rocksdb_pages_storage!(
+                addr.clone(),
+                new_state,
+                0,
+                "tests-contract-storage"
+            );
+            let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+            let storage = PageSliceCache::new(page_cache, 100);
+
+            (storage.pages_count(), receipt.gas_used)
+        }};
+    }
+
+    // the pages allocated at deploy time are recorded by the state (and aren't charged)
+    let (pages_count, gas_used) = exec_and_count_pages!(0);
+    assert_eq!(3, pages_count);
+
+    // growing the storage by 3 pages
+    let (pages_count, grow_gas_used) = exec_and_count_pages!(5);
+    assert_eq!(6, pages_count);
+    assert_eq!(
+        gas_used + 3 * svm_runtime::ctx::PAGE_GROW_GAS,
+        grow_gas_used
+    );
+}
+
+#[test]
+fn contract_exec_storage_out_of_range() {
+    use svm_storage::StorageError;
+
+    let addr = deploy_contract!("Out Of Range", "wasm/runtime-1.wast");
+
+    // `max_pages` is `10` (see `exec_tx!`)
+    let tx = reg_set_and_persist_tx!(addr, 10);
+    let receipt = exec_tx!(tx, State::from(0));
+
+    let expected = StorageError::PageIndexOutOfRange {
+        page_idx: PageIndex(10),
+        max_pages: 10,
+    };
+
+    assert_eq!(false, receipt.success);
+    assert_eq!(
+        Some(ContractExecError::StorageOutOfRange(expected)),
+        receipt.error
+    );
+    assert_eq!(None, receipt.new_state);
+}
+
+macro_rules! deploy_contract_with_deps {
+    ($name: expr, $file: expr, $deps: expr) => {{
+        let wasm = load_wasm_file!($file);
+
+        let bytes = WireContractBuilder::new()
+            .with_version(2)
+            .with_name($name)
+            .with_author(Address::from(0x10_20_30_40))
+            .with_deps($deps)
+            .with_code(&wasm[..])
+            .with_nonce(unique_nonce())
+            .build();
+
+        runtime::contract_build(&bytes).map(|contract| {
+            let addr = runtime::contract_compute_address(&contract);
+            runtime::contract_store(&contract, &addr).unwrap();
+
+            addr
+        })
+    }};
+}
+
+#[test]
+fn deploy_contract_with_missing_dependency() {
+    let dep = Dependency::Address(Address::from(0x50_60_70_80));
+    let res = deploy_contract_with_deps!("Uses Lib", "wasm/uses_lib.wast", &[dep.clone()]);
+
+    assert_eq!(
+        format!("Dependency not found: `{:?}`", dep),
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn deploy_contract_with_importing_dependency() {
+    // libraries may not import anything (`callee` imports `svm` vmcalls)
+    let lib = deploy_contract!("Callee", "wasm/callee.wast");
+    let dep = Dependency::Address(lib);
+    let res = deploy_contract_with_deps!("Uses Lib", "wasm/uses_lib.wast", &[dep.clone()]);
+
+    assert_eq!(
+        format!("Invalid dependency: `{:?}`", dep),
+        format!("{:?}", res.unwrap_err())
+    );
+}
+
+#[test]
+fn contract_exec_calling_dependency() {
+    let lib = deploy_contract!("Math Lib", "wasm/math_lib.wast");
+    let deps = [Dependency::Address(lib)];
+    let addr = deploy_contract_with_deps!("Uses Lib", "wasm/uses_lib.wast", &deps).unwrap();
+
+    let bytes = build_raw_tx!(
+        0,                // protocol version
+        addr.clone(),     // contract address
+        0x11_22_33_44,    // sender address
+        "add_ten",        // `func_name` to execute
+        &[Value::I64(5)]  // `func_args`
+    );
+
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(true, receipt.success);
+    assert_eq!(vec![wasmer_runtime::Value::I64(15)], receipt.results);
+
+    // the library instantiation is charged
+    assert!(receipt.gas_used > svm_runtime::ctx::DEP_INSTANTIATE_GAS);
+}
+
+#[test]
+fn contract_exec_dependency_instantiation_out_of_gas() {
+    let lib = deploy_contract!("Math Lib", "wasm/math_lib.wast");
+    let deps = [Dependency::Address(lib)];
+    let addr = deploy_contract_with_deps!("Uses Lib", "wasm/uses_lib.wast", &deps).unwrap();
+
+    let gas_limit = svm_runtime::ctx::DEP_INSTANTIATE_GAS - 1;
+
+    let bytes = WireTxBuilder::new()
+        .with_version(1)
+        .with_contract(addr)
+        .with_sender(Address::from(0x11_22_33_44))
+        .with_gas_limit(gas_limit)
+        .with_gas_price(1)
+        .with_func_name("add_ten")
+        .with_func_args(&[Value::I64(5)])
+        .build();
+
+    let tx = runtime::transaction_build(&bytes).unwrap();
+
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(false, receipt.success);
+    assert_eq!(Some(ContractExecError::OutOfGas), receipt.error);
+    assert_eq!(gas_limit, receipt.gas_used);
+}
+
+#[test]
+fn contract_exec_reuses_compiled_module() {
+    runtime::module_cache_configure(10, None);
+
+    let addr = deploy_contract!("Slice", "wasm/slice_arg.wast");
+    let args = [Value::Slice(
+        WasmIntType::I32,
+        WasmIntType::I64,
+        vec![1, 2, 3],
+    )];
+
+    for _ in 0..3 {
+        let bytes = build_raw_tx!(0, addr.clone(), 0x11_22_33_44, "persist_slice", &args);
+        let tx = runtime::transaction_build(&bytes).unwrap();
+
+        let receipt = exec_tx!(tx, State::from(0));
+        assert_eq!(true, receipt.success);
+    }
+
+    let stats = runtime::module_cache_stats();
+    assert_eq!(1, stats.misses);
+    assert_eq!(2, stats.hits);
+}
+
+#[test]
+fn contract_upgrade_keeps_storage() {
+    use svm_contract::build::WireUpgradeBuilder;
+    use svm_contract::error::ContractUpgradeError;
+
+    let admin = Address::from(0x50_60_70_80);
+    let wasm = load_wasm_file!("wasm/runtime-1.wast");
+    let bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Upgradable")
+        .with_author(Address::from(0x30_40_50_60))
+        .with_admins(&[admin.clone()])
+        .with_code(&wasm[..])
+        .with_nonce(unique_nonce())
+        .build();
+
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    // persisting `0x10_20_30_40_50_60_70_80` to (page=`0`, slice=`0`, offset=`0`)
+    let tx = reg_set_and_persist_tx!(addr.clone(), 0);
+    let receipt = exec_tx!(tx, State::from(0));
+    assert_eq!(true, receipt.success);
+    let state = receipt.new_state.unwrap();
+
+    macro_rules! build_upgrade {
+        ($sender: expr) => {{
+            let wasm = load_wasm_file!("wasm/runtime-2.wast");
+
+            let bytes = WireUpgradeBuilder::new()
+                .with_version(0)
+                .with_contract(addr.clone())
+                .with_sender($sender)
+                .with_code(&wasm[..])
+                .build();
+
+            runtime::upgrade_build(&bytes).unwrap()
+        }};
+    }
+
+    // only an admin may upgrade the contract
+    let upgrade = build_upgrade!(Address::from(0x11_22_33_44));
+    match runtime::contract_upgrade(&upgrade) {
+        Err(ContractUpgradeError::NotAdmin(sender)) => {
+            assert_eq!(Address::from(0x11_22_33_44), sender)
+        }
+        _ => unreachable!(),
+    }
+
+    let upgrade = build_upgrade!(admin);
+    runtime::contract_upgrade(&upgrade).unwrap();
+
+    // the previous code functions don't exist anymore
+    let tx = reg_set_and_persist_tx!(addr.clone(), 0);
+    let receipt = exec_tx!(tx, state.clone());
+    assert_eq!(
+        Some(ContractExecError::FuncNotFound(
+            "reg_set_and_persist".to_string()
+        )),
+        receipt.error
+    );
+
+    // the new code runs over the storage of the previous code
+    let bytes = build_raw_tx!(0, addr.clone(), 0x11_22_33_44, "copy_slice", &[]);
+    let tx = runtime::transaction_build(&bytes).unwrap();
+    let receipt = exec_tx!(tx, state);
+    assert_eq!(true, receipt.success);
+
+    let new_state = receipt.new_state.unwrap();
+    let pages_storage =
+        svm_runtime::gen_rocksdb_pages_storage!(addr, new_state, 10, "tests-contract-storage");
+    let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(1),
+        page_idx: PageIndex(0),
+        offset: 8,
+        len: 8,
+    };
+
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(
+        &[0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80],
+        &slice[..]
+    );
+}
+
+macro_rules! deploy_with_ctor {
+    ($name: expr, $author: expr, $func_name: expr, $func_args: expr) => {{
+        let wasm = load_wasm_file!("wasm/runtime-1.wast");
+
+        let bytes = WireContractBuilder::new()
+            .with_version(2)
+            .with_name($name)
+            .with_author(Address::from($author))
+            .with_code(&wasm[..])
+            .with_ctor($func_name, $func_args)
+            .with_nonce(unique_nonce())
+            .build();
+
+        let contract = runtime::contract_build(&bytes).unwrap();
+        let addr = runtime::contract_compute_address(&contract);
+
+        let opts = svm_runtime::opts::Opts {
+            max_pages: 10,
+            max_pages_slices: 100,
+            gas_limit: 1_000_000,
+            state_resolver: None,
+            balance_resolver: None,
+            layer: svm_runtime::opts::Layer::default(),
+        };
+
+        let import_object =
+            runtime::import_object_create(addr.clone(), State::empty(), std::ptr::null(), opts);
+
+        runtime::contract_deploy(&contract, &addr, &import_object)
+    }};
+}
+
+#[test]
+fn deploy_contract_running_ctor() {
+    let receipt = deploy_with_ctor!(
+        "With Ctor",
+        0x40_50_60_70,
+        "reg_set_and_persist",
+        &[
+            Value::I64(0x10_20_30_40_50_60_70_80),
+            Value::I32(64),
+            Value::I32(0),
+            Value::I32(0),
+            Value::I32(0),
+            Value::I32(0)
+        ]
+    );
+
+    assert_eq!(true, receipt.success);
+    assert_eq!(None, receipt.error);
+    assert!(receipt.gas_used > 0);
+
+    // the storage written by the constructor is the contract initial state
+    let state = receipt.new_state.unwrap();
+    assert_ne!(State::empty(), state);
+
+    let addr = receipt.addr;
+    let pages_storage = svm_runtime::gen_rocksdb_pages_storage!(
+        addr.clone(),
+        state.clone(),
+        10,
+        "tests-contract-storage"
+    );
+    let page_cache = svm_runtime::gen_rocksdb_page_cache!(pages_storage, 10);
+    let mut storage = PageSliceCache::new(page_cache, 100);
+
+    let slice_pos = PageSliceLayout {
+        slice_idx: SliceIndex(0),
+        page_idx: PageIndex(0),
+        offset: 0,
+        len: 8,
+    };
+
+    let slice = storage.read_page_slice(&slice_pos).unwrap();
+    assert_eq!(
+        &[0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80],
+        &slice[..]
+    );
+
+    // the contract has been stored
+    let tx = reg_set_and_persist_tx!(addr, 1);
+    let receipt = exec_tx!(tx, state);
+    assert_eq!(true, receipt.success);
+}
+
+#[test]
+fn deploy_contract_with_ctor_at_taken_address() {
+    let wasm = load_wasm_file!("wasm/runtime-1.wast");
+
+    let bytes = WireContractBuilder::new()
+        .with_version(2)
+        .with_name("Ctor Deployed Twice")
+        .with_author(Address::from(0x60_70_80_90))
+        .with_code(&wasm[..])
+        .with_ctor("no_such_func", &[])
+        .with_nonce(unique_nonce())
+        .build();
+
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    let opts = svm_runtime::opts::Opts {
+        max_pages: 10,
+        max_pages_slices: 100,
+        gas_limit: 1_000_000,
+        state_resolver: None,
+        balance_resolver: None,
+        layer: svm_runtime::opts::Layer::default(),
+    };
+
+    let import_object =
+        runtime::import_object_create(addr.clone(), State::empty(), std::ptr::null(), opts);
+
+    // the ctor isn't run (it would have failed with `FuncNotFound`)
+    let receipt = runtime::contract_deploy(&contract, &addr, &import_object);
+    assert_eq!(false, receipt.success);
+    assert_eq!(Some(ContractExecError::AddressTaken(addr)), receipt.error);
+    assert_eq!(0, receipt.gas_used);
+}
+
+#[test]
+fn deploy_contract_with_failing_ctor() {
+    let receipt = deploy_with_ctor!("Bad Ctor", 0x50_60_70_80, "no_such_func", &[]);
+
+    assert_eq!(false, receipt.success);
+    assert_eq!(
+        Some(ContractExecError::FuncNotFound("no_such_func".to_string())),
+        receipt.error
+    );
+    assert_eq!(None, receipt.new_state);
+
+    // the contract hasn't been stored
+    let addr = receipt.addr;
+    let tx = reg_set_and_persist_tx!(addr.clone(), 0);
+    let receipt = exec_tx!(tx, State::empty());
+    assert_eq!(Some(ContractExecError::NotFound(addr)), receipt.error);
+}
+
+#[test]
+fn deploy_contract_with_ctor_out_of_gas() {
+    let wasm = load_wasm_file!("wasm/infinite_loop.wast");
+
+    let bytes = WireContractBuilder::new()
+        .with_version(3)
+        .with_name("Looping Ctor")
+        .with_author(Address::from(0x10_20_30_40))
+        .with_code(&wasm[..])
+        .with_ctor("run", &[])
+        .with_nonce(unique_nonce())
+        .with_gas_limit(5_000)
+        .with_gas_price(1)
+        .build();
+
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+
+    let opts = svm_runtime::opts::Opts {
+        max_pages: 10,
+        max_pages_slices: 100,
+        gas_limit: 1_000_000,
+        state_resolver: None,
+        balance_resolver: None,
+        layer: svm_runtime::opts::Layer::default(),
+    };
+
+    let import_object =
+        runtime::import_object_create(addr.clone(), State::empty(), std::ptr::null(), opts);
+
+    // the constructor is limited by the contract gas limit (and not by the runtime one)
+    let receipt = runtime::contract_deploy(&contract, &addr, &import_object);
+    assert_eq!(false, receipt.success);
+    assert_eq!(Some(ContractExecError::OutOfGas), receipt.error);
+    assert_eq!(5_000, receipt.gas_used);
+    assert_eq!(None, receipt.new_state);
+
+    // the contract hasn't been stored
+    let tx = reg_set_and_persist_tx!(addr.clone(), 0);
+    let receipt = exec_tx!(tx, State::empty());
+    assert_eq!(Some(ContractExecError::NotFound(addr)), receipt.error);
+}
+
+#[test]
+fn deploy_contract_address_taken() {
+    let bytes = build_raw_contract!(2, "Deployed Twice", 0x10_20_30_40, "wasm/runtime-1.wast");
+    let contract = runtime::contract_build(&bytes).unwrap();
+    let addr = runtime::contract_compute_address(&contract);
+    runtime::contract_store(&contract, &addr).unwrap();
+
+    // the same contract (having the same nonce) is derived the same address
+    let res = runtime::contract_store(&contract, &addr);
+    assert!(format!("{:?}", res.unwrap_err()).ends_with("is already taken"));
+
+    let opts = svm_runtime::opts::Opts {
+        max_pages: 10,
+        max_pages_slices: 100,
+        gas_limit: 1_000_000,
+        state_resolver: None,
+        balance_resolver: None,
+        layer: svm_runtime::opts::Layer::default(),
+    };
+
+    let import_object =
+        runtime::import_object_create(addr.clone(), State::empty(), std::ptr::null(), opts);
+
+    let receipt = runtime::contract_deploy(&contract, &addr, &import_object);
+    assert_eq!(false, receipt.success);
+    assert_eq!(Some(ContractExecError::AddressTaken(addr)), receipt.error);
+}
+
 #[test]
 #[ignore]
 fn contract_exec_invalid_state() {
@@ -1,6 +1,9 @@
 use std::cell::Cell;
 
+use svm_common::Address;
+
 use svm_runtime::ctx_data_wrapper::SvmCtxDataWrapper;
+use svm_runtime::event::Event;
 
 use svm_storage::memory::MemMerklePageCache;
 
@@ -23,6 +26,15 @@ macro_rules! wasmer_compile_module_file {
     }};
 }
 
+macro_rules! instantiate {
+    ($module: expr, $import_object: expr) => {{
+        let mut instance = $module.instantiate($import_object).unwrap();
+        svm_compiler::gas::set_gas_left(&mut instance, 1_000_000);
+
+        instance
+    }};
+}
+
 macro_rules! test_create_svm_state_gen {
     () => {{
         let node_data = SvmCtxDataWrapper::new(std::ptr::null());
@@ -53,6 +65,10 @@ macro_rules! test_create_svm_state_gen {
         let opts = svm_runtime::opts::Opts {
             max_pages: max_pages as usize,
             max_pages_slices: max_pages_slices as usize,
+            gas_limit: 1_000_000,
+            state_resolver: None,
+            balance_resolver: None,
+            layer: svm_runtime::opts::Layer::default(),
         };
 
         svm_runtime::lazy_create_svm_state_gen!(
@@ -87,7 +103,7 @@ fn vmcalls_mem_to_reg_copy() {
         },
     };
 
-    let instance = module.instantiate(&import_object).unwrap();
+    let instance = instantiate!(module, &import_object);
 
     // initializing memory #0 cells `200..203` with values `10, 20, 30` respectively
     svm_runtime::wasmer_ctx_mem_cells_write!(instance.context(), 0, 200, &[10, 20, 30]);
@@ -116,7 +132,7 @@ fn vmcalls_reg_to_mem_copy() {
         },
     };
 
-    let instance = module.instantiate(&import_object).unwrap();
+    let instance = instantiate!(module, &import_object);
 
     // initializing reg `2` (of type `64 bits`) with values `10, 20, 30` respectively
     let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 2, MemMerklePageCache);
@@ -147,7 +163,7 @@ fn vmcalls_storage_read_an_empty_page_slice_to_reg() {
         },
     };
 
-    let instance = module.instantiate(&import_object).unwrap();
+    let instance = instantiate!(module, &import_object);
 
     // we first initialize register `2` with some garbage data which should be overriden
     // after calling the exported `do_copy_to_reg` function
@@ -176,13 +192,15 @@ fn vmcalls_storage_read_non_empty_page_slice_to_reg() {
         },
     };
 
-    let mut instance = module.instantiate(&import_object).unwrap();
+    let mut instance = instantiate!(module, &import_object);
     let storage =
         svm_runtime::wasmer_data_storage!(instance.context_mut().data, MemMerklePageCache);
     let layout = svm_runtime::svm_page_slice_layout!(1, 10, 100, 3);
 
     // we write `[10, 20, 30]` into storage slice `10` (page `1`, cells: `100..103`)
-    storage.write_page_slice(&layout, &vec![10, 20, 30]);
+    storage
+        .write_page_slice(&layout, &vec![10, 20, 30])
+        .unwrap();
 
     // we first initialize register `2` (of type `64 bits`) with some garbage data which should be overriden
     // after calling the exported `do_copy_to_reg` function
@@ -210,7 +228,7 @@ fn vmcalls_storage_read_an_empty_page_slice_to_mem() {
         },
     };
 
-    let instance = module.instantiate(&import_object).unwrap();
+    let instance = instantiate!(module, &import_object);
 
     // we fill memory #0, cells `200..203` with garbage data
     svm_runtime::wasmer_ctx_mem_cells_write!(instance.context(), 0, 200, &[255, 255, 255]);
@@ -237,13 +255,15 @@ fn vmcalls_storage_read_non_empty_page_slice_to_mem() {
         },
     };
 
-    let mut instance = module.instantiate(&import_object).unwrap();
+    let mut instance = instantiate!(module, &import_object);
     let storage =
         svm_runtime::wasmer_data_storage!(instance.context_mut().data, MemMerklePageCache);
     let layout = svm_runtime::svm_page_slice_layout!(1, 10, 100, 3);
 
     // we write `[10, 20, 30]` into storage slice `10` (page `1`, cells `100..103`)
-    storage.write_page_slice(&layout, &vec![10, 20, 30]);
+    storage
+        .write_page_slice(&layout, &vec![10, 20, 30])
+        .unwrap();
 
     let do_copy: Func<(i32, i32, i32, i32, i32)> = instance.func("do_copy_to_mem").unwrap();
 
@@ -266,7 +286,7 @@ fn vmcalls_storage_write_from_mem() {
         },
     };
 
-    let mut instance = module.instantiate(&import_object).unwrap();
+    let mut instance = instantiate!(module, &import_object);
     let storage =
         svm_runtime::wasmer_data_storage!(instance.context_mut().data, MemMerklePageCache);
 
@@ -296,7 +316,7 @@ fn vmcalls_storage_write_from_reg() {
         },
     };
 
-    let mut instance = module.instantiate(&import_object).unwrap();
+    let mut instance = instantiate!(module, &import_object);
     let storage =
         svm_runtime::wasmer_data_storage!(instance.context_mut().data, MemMerklePageCache);
 
@@ -316,6 +336,53 @@ fn vmcalls_storage_write_from_reg() {
     assert_eq!(Some(vec![10, 20, 30]), storage.read_page_slice(&layout));
 }
 
+#[test]
+fn vmcalls_storage_write_out_of_range() {
+    use svm_runtime::runtime::ContractExecError;
+    use svm_storage::page::PageIndex;
+    use svm_storage::StorageError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/storage_write_from_reg.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "storage_write_from_reg" => func!(vmcalls::storage_write_from_reg),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+    let do_write: Func<(i32, i32, i32, i32, i32)> = instance.func("do_write_from_reg").unwrap();
+
+    // `max_pages` is `5` (see `test_create_svm_state_gen!`)
+    let expected = ContractExecError::StorageOutOfRange(StorageError::PageIndexOutOfRange {
+        page_idx: PageIndex(5),
+        max_pages: 5,
+    });
+
+    match do_write.call(5, 3, 5, 10, 200) {
+        Err(RuntimeError::Error { data }) => {
+            assert_eq!(Some(&expected), data.downcast_ref::<ContractExecError>());
+        }
+        _ => unreachable!(),
+    }
+
+    // a negative page index is the last page index
+    let expected = ContractExecError::StorageOutOfRange(StorageError::PageIndexOutOfRange {
+        page_idx: PageIndex(std::u32::MAX),
+        max_pages: 5,
+    });
+
+    match do_write.call(5, 3, -1, 10, 200) {
+        Err(RuntimeError::Error { data }) => {
+            assert_eq!(Some(&expected), data.downcast_ref::<ContractExecError>());
+        }
+        _ => unreachable!(),
+    }
+}
+
 #[test]
 fn vmcalls_reg_replace_byte_read_write_be_i64() {
     let module = wasmer_compile_module_file!("wasm/reg_replace_read_write_be_i64.wast");
@@ -332,7 +399,7 @@ fn vmcalls_reg_replace_byte_read_write_be_i64() {
         },
     };
 
-    let instance = module.instantiate(&import_object).unwrap();
+    let instance = instantiate!(module, &import_object);
 
     // we first initialize register `64:5` with `[254, 255, 0, 0, 0, 0, 0, 0]`
     let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 5, MemMerklePageCache);
@@ -351,3 +418,701 @@ fn vmcalls_reg_replace_byte_read_write_be_i64() {
     assert!(replace.call(5, 20, 7).is_ok());
     assert_eq!(vec![0, 0, 0, 0, 0, 1, 10, 20], reg.view());
 }
+
+#[test]
+fn vmcalls_emit_event_from_mem() {
+    let module = wasmer_compile_module_file!("wasm/emit_event_from_mem.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "emit_event_from_mem" => func!(vmcalls::emit_event_from_mem),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+    ctx.env.contract = Address::from(0x12_34_56_78);
+
+    // we initialize the topic register `256:1` with `[1, 2, 3, 0, ... 0]`
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 256, 1, MemMerklePageCache);
+    reg.set(&[1, 2, 3]);
+
+    svm_runtime::wasmer_ctx_mem_cells_write!(instance.context(), 0, 200, &[10, 20, 30]);
+
+    let do_emit: Func<(i32, i32, i32, i32)> = instance.func("do_emit_from_mem").unwrap();
+
+    // we emit an event with memory cells `200..203` as its data, to be kept for `5` layers
+    assert!(do_emit.call(1, 200, 3, 5).is_ok());
+
+    let mut topic = vec![0; 32];
+    topic[..3].copy_from_slice(&[1, 2, 3]);
+
+    let expected = Event {
+        addr: Address::from(0x12_34_56_78),
+        topic,
+        data: vec![10, 20, 30],
+        expiration: 5,
+    };
+
+    assert_eq!(vec![expected], ctx.logs);
+}
+
+#[test]
+fn vmcalls_emit_event_from_reg() {
+    let module = wasmer_compile_module_file!("wasm/emit_event_from_reg.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "emit_event_from_reg" => func!(vmcalls::emit_event_from_reg),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+    ctx.env.contract = Address::from(0x12_34_56_78);
+
+    // we initialize register `64:5` with `[10, 20, 30, 0, 0, 0, 0, 0]`
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 5, MemMerklePageCache);
+    reg.set(&[10, 20, 30]);
+
+    let do_emit: Func<(i32, i32, i32, i32)> = instance.func("do_emit_from_reg").unwrap();
+
+    // we emit two events with register `64:5` first 2 bytes as their data (topic register `256:0` is empty)
+    assert!(do_emit.call(0, 5, 2, 1).is_ok());
+    assert!(do_emit.call(0, 5, 2, 10).is_ok());
+
+    assert_eq!(2, ctx.logs.len());
+    assert_eq!(vec![0; 32], ctx.logs[0].topic);
+    assert_eq!(vec![10, 20], ctx.logs[0].data);
+    assert_eq!(1, ctx.logs[0].expiration);
+    assert_eq!(10, ctx.logs[1].expiration);
+}
+
+#[test]
+fn vmcalls_emit_event_invalid_len_or_expiration() {
+    use svm_runtime::runtime::ContractExecError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/emit_event_from_reg.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "emit_event_from_reg" => func!(vmcalls::emit_event_from_reg),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+
+    let do_emit: Func<(i32, i32, i32, i32)> = instance.func("do_emit_from_reg").unwrap();
+
+    // a `64 bits` register holds at most `8 bytes`, and the expiration can't be negative
+    let cases = [
+        ((0, 5, 9, 1), ContractExecError::InvalidEventLength(9)),
+        ((0, 5, -1, 1), ContractExecError::InvalidEventLength(-1)),
+        ((0, 5, 2, -1), ContractExecError::InvalidEventExpiration(-1)),
+    ];
+
+    for ((topic_reg_idx, reg_idx, len, expiration), expected) in cases.iter() {
+        match do_emit.call(*topic_reg_idx, *reg_idx, *len, *expiration) {
+            Err(RuntimeError::Error { data }) => {
+                assert_eq!(Some(expected), data.downcast_ref::<ContractExecError>());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    assert!(ctx.logs.is_empty());
+}
+
+#[test]
+fn vmcalls_emit_event_from_mem_out_of_bounds() {
+    use svm_runtime::runtime::ContractExecError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/emit_event_from_mem.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "emit_event_from_mem" => func!(vmcalls::emit_event_from_mem),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+    let do_emit: Func<(i32, i32, i32, i32)> = instance.func("do_emit_from_mem").unwrap();
+
+    // the memory has a single page (`65536 bytes`)
+    match do_emit.call(1, 65536 - 2, 3, 5) {
+        Err(RuntimeError::Error { data }) => {
+            assert_eq!(
+                Some(&ContractExecError::InvalidEventLength(3)),
+                data.downcast_ref::<ContractExecError>()
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn vmcalls_emit_event_charges_gas_per_data_byte() {
+    use svm_runtime::ctx::{EVENT_DATA_BYTE_GAS, EVENT_GAS};
+    use svm_runtime::runtime::ContractExecError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/emit_event_from_reg.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "emit_event_from_reg" => func!(vmcalls::emit_event_from_reg),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+
+    let do_emit: Func<(i32, i32, i32, i32)> = instance.func("do_emit_from_reg").unwrap();
+
+    let gas_before = svm_compiler::gas::get_gas_left(&instance);
+    assert!(do_emit.call(0, 5, 8, 1).is_ok());
+    let gas_used = gas_before - svm_compiler::gas::get_gas_left(&instance);
+
+    // the exported function itself is metered too
+    assert!(gas_used >= EVENT_GAS + 8 * EVENT_DATA_BYTE_GAS);
+
+    // not enough gas left for emitting another event
+    svm_compiler::gas::set_gas_left(&mut instance, EVENT_GAS);
+    let do_emit: Func<(i32, i32, i32, i32)> = instance.func("do_emit_from_reg").unwrap();
+
+    match do_emit.call(0, 5, 8, 1) {
+        Err(RuntimeError::Error { data }) => {
+            assert_eq!(
+                Some(&ContractExecError::OutOfGas),
+                data.downcast_ref::<ContractExecError>()
+            );
+        }
+        _ => unreachable!(),
+    }
+
+    assert_eq!(1, ctx.logs.len());
+}
+
+#[test]
+fn vmcalls_is_admin() {
+    let module = wasmer_compile_module_file!("wasm/is_admin.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "is_admin" => func!(vmcalls::is_admin),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+    ctx.admins.push(Address::from(0x10_20_30_40));
+
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 160, 0, MemMerklePageCache);
+    reg.set(Address::from(0x10_20_30_40).as_slice());
+
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 160, 1, MemMerklePageCache);
+    reg.set(Address::from(0x50_60_70_80).as_slice());
+
+    let do_is_admin: Func<i32, i32> = instance.func("do_is_admin").unwrap();
+
+    assert_eq!(1, do_is_admin.call(0).unwrap());
+    assert_eq!(0, do_is_admin.call(1).unwrap());
+}
+
+#[test]
+fn vmcalls_get_value_and_self_balance() {
+    use svm_common::Balance;
+
+    unsafe extern "C" fn balance_resolver(
+        _node_data: *const std::ffi::c_void,
+        _addr: *const std::ffi::c_void,
+    ) -> *const std::ffi::c_void {
+        // `1000` (Little-Endian)
+        static BALANCE: [u8; 16] = [0xE8, 0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+        BALANCE.as_ptr() as _
+    }
+
+    let module = wasmer_compile_module_file!("wasm/balance.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "get_value" => func!(vmcalls::get_value),
+            "get_self_balance" => func!(vmcalls::get_self_balance),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+    ctx.value = Balance(100);
+    ctx.call_stack.push(Address::from(0x10_20_30_40));
+
+    let do_get_value: Func<(i32, i32)> = instance.func("do_get_value").unwrap();
+    let do_get_self_balance: Func<(i32, i32)> = instance.func("do_get_self_balance").unwrap();
+
+    macro_rules! reg_balance {
+        ($reg_idx: expr) => {{
+            let reg =
+                svm_runtime::wasmer_ctx_reg!(instance.context(), 256, $reg_idx, MemMerklePageCache);
+            Balance::from(reg.getn(16).as_ptr())
+        }};
+    }
+
+    assert!(do_get_value.call(256, 0).is_ok());
+    assert_eq!(Balance(100), reg_balance!(0));
+
+    // without a balance resolver, the contract has only the value sent with the transaction
+    assert!(do_get_self_balance.call(256, 1).is_ok());
+    assert_eq!(Balance(100), reg_balance!(1));
+
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context().data, MemMerklePageCache);
+    ctx.opts.balance_resolver = Some(balance_resolver);
+
+    assert!(do_get_self_balance.call(256, 2).is_ok());
+    assert_eq!(Balance(1100), reg_balance!(2));
+}
+
+#[test]
+fn vmcalls_get_value_into_small_register() {
+    use svm_runtime::runtime::ContractExecError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/balance.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "get_value" => func!(vmcalls::get_value),
+            "get_self_balance" => func!(vmcalls::get_self_balance),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+    let do_get_value: Func<(i32, i32)> = instance.func("do_get_value").unwrap();
+
+    match do_get_value.call(64, 0) {
+        Err(RuntimeError::Error { data }) => {
+            assert_eq!(
+                Some(&ContractExecError::RegisterTooSmall(64)),
+                data.downcast_ref::<ContractExecError>()
+            );
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn vmcalls_get_env() {
+    use svm_runtime::opts::Layer;
+
+    let module = wasmer_compile_module_file!("wasm/env.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "get_sender_to_reg" => func!(vmcalls::get_sender_to_reg),
+            "get_sender_to_mem" => func!(vmcalls::get_sender_to_mem),
+            "get_self_address_to_reg" => func!(vmcalls::get_self_address_to_reg),
+            "get_self_address_to_mem" => func!(vmcalls::get_self_address_to_mem),
+            "get_layer_id" => func!(vmcalls::get_layer_id),
+            "get_layer_timestamp" => func!(vmcalls::get_layer_timestamp),
+            "get_beacon_to_reg" => func!(vmcalls::get_beacon_to_reg),
+            "get_beacon_to_mem" => func!(vmcalls::get_beacon_to_mem),
+        },
+    };
+
+    let mut instance = instantiate!(module, &import_object);
+    let ctx =
+        svm_runtime::cast_wasmer_data_to_svm_ctx!(instance.context_mut().data, MemMerklePageCache);
+    ctx.env.sender = Address::from(0x11_22_33_44);
+    ctx.env.contract = Address::from(0x10_20_30_40);
+    ctx.env.layer = Layer {
+        id: 7,
+        timestamp: 1_000,
+        beacon: [0xAB; 32],
+    };
+
+    let do_get_sender_to_reg: Func<i32> = instance.func("do_get_sender_to_reg").unwrap();
+    let do_get_sender_to_mem: Func<i32> = instance.func("do_get_sender_to_mem").unwrap();
+    let do_get_self_address_to_reg: Func<i32> =
+        instance.func("do_get_self_address_to_reg").unwrap();
+    let do_get_self_address_to_mem: Func<i32> =
+        instance.func("do_get_self_address_to_mem").unwrap();
+    let do_get_layer_id: Func<(), i64> = instance.func("do_get_layer_id").unwrap();
+    let do_get_layer_timestamp: Func<(), i64> = instance.func("do_get_layer_timestamp").unwrap();
+    let do_get_beacon_to_reg: Func<i32> = instance.func("do_get_beacon_to_reg").unwrap();
+    let do_get_beacon_to_mem: Func<i32> = instance.func("do_get_beacon_to_mem").unwrap();
+
+    macro_rules! mem_bytes {
+        ($mem_ptr: expr, $len: expr) => {{
+            let cells = svm_runtime::wasmer_ctx_mem_cells!(instance.context(), 0, $mem_ptr, $len);
+            cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>()
+        }};
+    }
+
+    // sender
+    assert!(do_get_sender_to_reg.call(0).is_ok());
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 160, 0, MemMerklePageCache);
+    assert_eq!(Address::from(0x11_22_33_44).as_slice(), &reg.view()[..]);
+
+    assert!(do_get_sender_to_mem.call(100).is_ok());
+    assert_eq!(
+        Address::from(0x11_22_33_44).as_slice(),
+        &mem_bytes!(100, 20)[..]
+    );
+
+    // self address
+    assert!(do_get_self_address_to_reg.call(1).is_ok());
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 160, 1, MemMerklePageCache);
+    assert_eq!(Address::from(0x10_20_30_40).as_slice(), &reg.view()[..]);
+
+    assert!(do_get_self_address_to_mem.call(200).is_ok());
+    assert_eq!(
+        Address::from(0x10_20_30_40).as_slice(),
+        &mem_bytes!(200, 20)[..]
+    );
+
+    // layer
+    assert_eq!(7, do_get_layer_id.call().unwrap());
+    assert_eq!(1_000, do_get_layer_timestamp.call().unwrap());
+
+    assert!(do_get_beacon_to_reg.call(0).is_ok());
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 256, 0, MemMerklePageCache);
+    assert_eq!(vec![0xAB; 32], reg.view());
+
+    assert!(do_get_beacon_to_mem.call(300).is_ok());
+    assert_eq!(vec![0xAB; 32], mem_bytes!(300, 32));
+}
+
+#[test]
+fn vmcalls_array_push_get_pop() {
+    let module = wasmer_compile_module_file!("wasm/array.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "array_len" => func!(vmcalls::array_len),
+            "array_get_to_reg" => func!(vmcalls::array_get_to_reg),
+            "array_push_from_reg" => func!(vmcalls::array_push_from_reg),
+            "array_pop_to_reg" => func!(vmcalls::array_pop_to_reg),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+
+    let len: Func<(), i32> = instance.func("len").unwrap();
+    let push: Func<i32> = instance.func("push").unwrap();
+    let get: Func<(i32, i32)> = instance.func("get").unwrap();
+    let pop: Func<i32, i32> = instance.func("pop").unwrap();
+
+    assert_eq!(0, len.call().unwrap());
+
+    // pushing the contents of registers `64:0` and `64:1`
+    let reg0 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 0, MemMerklePageCache);
+    reg0.set(&[10, 20, 30]);
+    let reg1 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 1, MemMerklePageCache);
+    reg1.set(&[40, 50, 60]);
+
+    assert!(push.call(0).is_ok());
+    assert!(push.call(1).is_ok());
+    assert_eq!(2, len.call().unwrap());
+
+    // copying item `0` into register `64:2`
+    assert!(get.call(0, 2).is_ok());
+    let reg2 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 2, MemMerklePageCache);
+    assert_eq!(vec![10, 20, 30, 0, 0, 0, 0, 0], reg2.view());
+
+    // popping into register `64:3`
+    assert_eq!(1, pop.call(3).unwrap());
+    let reg3 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 3, MemMerklePageCache);
+    assert_eq!(vec![40, 50, 60, 0, 0, 0, 0, 0], reg3.view());
+
+    assert_eq!(1, pop.call(3).unwrap());
+    assert_eq!(0, pop.call(3).unwrap());
+    assert_eq!(0, len.call().unwrap());
+}
+
+#[test]
+fn vmcalls_list_push_and_pop_at_both_ends() {
+    let module = wasmer_compile_module_file!("wasm/list.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "list_len" => func!(vmcalls::list_len),
+            "list_get_to_reg" => func!(vmcalls::list_get_to_reg),
+            "list_push_front_from_reg" => func!(vmcalls::list_push_front_from_reg),
+            "list_push_back_from_reg" => func!(vmcalls::list_push_back_from_reg),
+            "list_pop_front_to_reg" => func!(vmcalls::list_pop_front_to_reg),
+            "list_pop_back_to_reg" => func!(vmcalls::list_pop_back_to_reg),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+
+    let len: Func<(), i32> = instance.func("len").unwrap();
+    let push_front: Func<i32> = instance.func("push_front").unwrap();
+    let push_back: Func<i32> = instance.func("push_back").unwrap();
+    let get: Func<(i32, i32)> = instance.func("get").unwrap();
+    let pop_front: Func<i32, i32> = instance.func("pop_front").unwrap();
+    let pop_back: Func<i32, i32> = instance.func("pop_back").unwrap();
+
+    // registers `64:0` and `64:1` hold the pushed items
+    let reg0 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 0, MemMerklePageCache);
+    reg0.set(&[10, 20, 30]);
+    let reg1 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 1, MemMerklePageCache);
+    reg1.set(&[40, 50, 60]);
+
+    assert!(push_back.call(1).is_ok());
+    assert!(push_front.call(0).is_ok());
+    assert_eq!(2, len.call().unwrap());
+
+    // copying the first item into register `64:2`
+    assert!(get.call(0, 2).is_ok());
+    let reg2 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 2, MemMerklePageCache);
+    assert_eq!(vec![10, 20, 30, 0, 0, 0, 0, 0], reg2.view());
+
+    // popping the last item into register `64:3`
+    assert_eq!(1, pop_back.call(3).unwrap());
+    let reg3 = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 3, MemMerklePageCache);
+    assert_eq!(vec![40, 50, 60, 0, 0, 0, 0, 0], reg3.view());
+
+    // popping the first item into register `64:3`
+    assert_eq!(1, pop_front.call(3).unwrap());
+    assert_eq!(vec![10, 20, 30, 0, 0, 0, 0, 0], reg3.view());
+
+    assert_eq!(0, pop_front.call(3).unwrap());
+    assert_eq!(0, pop_back.call(3).unwrap());
+    assert_eq!(0, len.call().unwrap());
+}
+
+#[test]
+fn vmcalls_list_invalid_item_size() {
+    use svm_runtime::runtime::ContractExecError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/list.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "list_len" => func!(vmcalls::list_len),
+            "list_get_to_reg" => func!(vmcalls::list_get_to_reg),
+            "list_push_front_from_reg" => func!(vmcalls::list_push_front_from_reg),
+            "list_push_back_from_reg" => func!(vmcalls::list_push_back_from_reg),
+            "list_pop_front_to_reg" => func!(vmcalls::list_pop_front_to_reg),
+            "list_pop_back_to_reg" => func!(vmcalls::list_pop_back_to_reg),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+    let push_back_sized: Func<(i32, i32)> = instance.func("push_back_sized").unwrap();
+
+    // an item of `9 bytes` doesn't fit a `64 bits` register, and an item must have at least one byte
+    let cases = [
+        (9, ContractExecError::RegisterTooSmall(64)),
+        (0, ContractExecError::InvalidItemSize(0)),
+        (-1, ContractExecError::InvalidItemSize(-1)),
+    ];
+
+    for (item_size, expected) in cases.iter() {
+        match push_back_sized.call(*item_size, 0) {
+            Err(RuntimeError::Error { data }) => {
+                assert_eq!(Some(expected), data.downcast_ref::<ContractExecError>());
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+fn vmcalls_array_get_out_of_bounds() {
+    use svm_runtime::runtime::ContractExecError;
+    use svm_storage::collections::CollectionError;
+    use wasmer_runtime::error::RuntimeError;
+
+    let module = wasmer_compile_module_file!("wasm/array.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "array_len" => func!(vmcalls::array_len),
+            "array_get_to_reg" => func!(vmcalls::array_get_to_reg),
+            "array_push_from_reg" => func!(vmcalls::array_push_from_reg),
+            "array_pop_to_reg" => func!(vmcalls::array_pop_to_reg),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+    let get: Func<(i32, i32)> = instance.func("get").unwrap();
+
+    // the array is empty
+    let expected = ContractExecError::CollectionFailed(CollectionError::IndexOutOfBounds(5));
+
+    match get.call(5, 0) {
+        Err(RuntimeError::Error { data }) => {
+            assert_eq!(Some(&expected), data.downcast_ref::<ContractExecError>());
+        }
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn vmcalls_hash_insert_get_remove() {
+    let module = wasmer_compile_module_file!("wasm/hash.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "hash_len" => func!(vmcalls::hash_len),
+            "hash_get_to_reg" => func!(vmcalls::hash_get_to_reg),
+            "hash_insert_from_reg" => func!(vmcalls::hash_insert_from_reg),
+            "hash_remove" => func!(vmcalls::hash_remove),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+
+    let len: Func<(), i32> = instance.func("len").unwrap();
+    let insert: Func<(i32, i32), i32> = instance.func("insert").unwrap();
+    let get: Func<(i32, i32), i32> = instance.func("get").unwrap();
+    let remove: Func<i32, i32> = instance.func("remove").unwrap();
+
+    // key register `32:0` and value register `64:0`
+    let key = svm_runtime::wasmer_ctx_reg!(instance.context(), 32, 0, MemMerklePageCache);
+    key.set(&[1, 2, 3, 4]);
+    let value = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 0, MemMerklePageCache);
+    value.set(&[10, 20, 30, 40, 50, 60, 70, 80]);
+
+    assert_eq!(0, get.call(0, 1).unwrap());
+
+    assert_eq!(1, insert.call(0, 0).unwrap());
+    assert_eq!(0, insert.call(0, 0).unwrap());
+    assert_eq!(1, len.call().unwrap());
+
+    // copying the value into register `64:1`
+    assert_eq!(1, get.call(0, 1).unwrap());
+    let reg = svm_runtime::wasmer_ctx_reg!(instance.context(), 64, 1, MemMerklePageCache);
+    assert_eq!(vec![10, 20, 30, 40, 50, 60, 70, 80], reg.view());
+
+    assert_eq!(1, remove.call(0).unwrap());
+    assert_eq!(0, remove.call(0).unwrap());
+    assert_eq!(0, get.call(0, 1).unwrap());
+    assert_eq!(0, len.call().unwrap());
+}
+
+#[test]
+fn vmcalls_sorted_set_insert_get_remove() {
+    let module = wasmer_compile_module_file!("wasm/sorted_set.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "sorted_set_len" => func!(vmcalls::sorted_set_len),
+            "sorted_set_get_to_reg" => func!(vmcalls::sorted_set_get_to_reg),
+            "sorted_set_contains" => func!(vmcalls::sorted_set_contains),
+            "sorted_set_insert_from_reg" => func!(vmcalls::sorted_set_insert_from_reg),
+            "sorted_set_remove" => func!(vmcalls::sorted_set_remove),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+
+    let len: Func<(), i32> = instance.func("len").unwrap();
+    let get: Func<(i32, i32)> = instance.func("get").unwrap();
+    let contains: Func<i32, i32> = instance.func("contains").unwrap();
+    let insert: Func<i32, i32> = instance.func("insert").unwrap();
+    let remove: Func<i32, i32> = instance.func("remove").unwrap();
+
+    // items registers `32:0` and `32:1`
+    let reg0 = svm_runtime::wasmer_ctx_reg!(instance.context(), 32, 0, MemMerklePageCache);
+    reg0.set(&[0, 0, 0, 20]);
+    let reg1 = svm_runtime::wasmer_ctx_reg!(instance.context(), 32, 1, MemMerklePageCache);
+    reg1.set(&[0, 0, 0, 10]);
+
+    assert_eq!(1, insert.call(0).unwrap());
+    assert_eq!(1, insert.call(1).unwrap());
+    assert_eq!(0, insert.call(0).unwrap());
+    assert_eq!(2, len.call().unwrap());
+    assert_eq!(1, contains.call(1).unwrap());
+
+    // the smallest item is copied into register `32:2`
+    assert!(get.call(0, 2).is_ok());
+    let reg2 = svm_runtime::wasmer_ctx_reg!(instance.context(), 32, 2, MemMerklePageCache);
+    assert_eq!(vec![0, 0, 0, 10], reg2.view());
+
+    assert_eq!(1, remove.call(1).unwrap());
+    assert_eq!(0, remove.call(1).unwrap());
+    assert_eq!(0, contains.call(1).unwrap());
+    assert_eq!(1, len.call().unwrap());
+}
+
+#[test]
+fn vmcalls_string_write_append_read() {
+    let module = wasmer_compile_module_file!("wasm/string.wast");
+
+    let import_object = imports! {
+        test_create_svm_state_gen!(),
+
+        "svm" => {
+            "string_len" => func!(vmcalls::string_len),
+            "string_read_to_mem" => func!(vmcalls::string_read_to_mem),
+            "string_write_from_mem" => func!(vmcalls::string_write_from_mem),
+            "string_append_from_mem" => func!(vmcalls::string_append_from_mem),
+        },
+    };
+
+    let instance = instantiate!(module, &import_object);
+
+    let len: Func<(), i32> = instance.func("len").unwrap();
+    let read: Func<i32, i32> = instance.func("read").unwrap();
+    let write: Func<(i32, i32)> = instance.func("write").unwrap();
+    let append: Func<(i32, i32)> = instance.func("append").unwrap();
+
+    assert_eq!(0, len.call().unwrap());
+
+    svm_runtime::wasmer_ctx_mem_cells_write!(instance.context(), 0, 0, b"Hello");
+    svm_runtime::wasmer_ctx_mem_cells_write!(instance.context(), 0, 10, b", World");
+
+    assert!(write.call(0, 5).is_ok());
+    assert!(append.call(10, 7).is_ok());
+    assert_eq!(12, len.call().unwrap());
+
+    // copying the string into memory cells `100, 101, ..., 111`
+    assert_eq!(12, read.call(100).unwrap());
+    let cells = svm_runtime::wasmer_ctx_mem_cells!(instance.context(), 0, 100, 12);
+    let bytes = cells.iter().map(|cell| cell.get()).collect::<Vec<u8>>();
+    assert_eq!(b"Hello, World", &bytes[..]);
+}
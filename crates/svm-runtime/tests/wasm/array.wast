@@ -0,0 +1,37 @@
+(module
+  ;; import `svm` vmcalls
+  (func $array_len (import "svm" "array_len") (param i32 i32) (result i32))
+  (func $array_get_to_reg (import "svm" "array_get_to_reg") (param i32 i32 i32 i32 i32))
+  (func $array_push_from_reg (import "svm" "array_push_from_reg") (param i32 i32 i32 i32))
+  (func $array_pop_to_reg (import "svm" "array_pop_to_reg") (param i32 i32 i32 i32) (result i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; the array is stored at page `1`, each item is `8 bytes`
+
+  (func (export "len") (result i32)
+        i32.const 1 ;; page_idx
+        i32.const 8 ;; item_size
+        call $array_len)
+
+  (func (export "push") (param i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        i32.const 64 ;; src_reg_bits
+        get_local 0  ;; src_reg_idx
+        call $array_push_from_reg)
+
+  (func (export "get") (param i32 i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        get_local 0  ;; idx
+        i32.const 64 ;; dst_reg_bits
+        get_local 1  ;; dst_reg_idx
+        call $array_get_to_reg)
+
+  (func (export "pop") (param i32) (result i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        i32.const 64 ;; dst_reg_bits
+        get_local 0  ;; dst_reg_idx
+        call $array_pop_to_reg))
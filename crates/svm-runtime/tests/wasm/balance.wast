@@ -0,0 +1,17 @@
+(module
+  ;; import `svm` vmcalls
+  (func $get_value (import "svm" "get_value") (param i32 i32))
+  (func $get_self_balance (import "svm" "get_self_balance") (param i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported functions to be called
+  (func (export "do_get_value") (param i32 i32)
+        get_local 0 ;; reg_bits
+        get_local 1 ;; reg_idx
+        call $get_value)
+
+  (func (export "do_get_self_balance") (param i32 i32)
+        get_local 0 ;; reg_bits
+        get_local 1 ;; reg_idx
+        call $get_self_balance))
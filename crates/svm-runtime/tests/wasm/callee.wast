@@ -0,0 +1,28 @@
+(module
+  ;; import `svm` vmcalls
+  (func $reg_write_be_i64 (import "svm" "reg_write_be_i64") (param i64 i32 i32))
+  (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32 i32))
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported function to be called by another contract
+  (func (export "inc") (param i64) (result i64)
+        ;; register `64:0` = value + 1
+        get_local 0
+        i64.const 1
+        i64.add
+        i32.const 64  ;; reg_bits
+        i32.const 0   ;; reg_idx
+        call $reg_write_be_i64
+
+        ;; persist register `64:0` to storage (page=`0`, slice=`0`, offset=`0`)
+        i32.const 64  ;; src_reg_bits
+        i32.const 0   ;; src_reg_idx
+        i32.const 8   ;; len
+        i32.const 0   ;; dst_page
+        i32.const 0   ;; dst_slice
+        i32.const 0   ;; dst_offset
+        call $storage_write_from_reg
+
+        get_local 0
+        i64.const 1
+        i64.add))
@@ -0,0 +1,40 @@
+(module
+  ;; import `svm` vmcalls
+  (func $mem_to_reg_copy (import "svm" "mem_to_reg_copy") (param i32 i32 i32 i32 i32))
+  (func $reg_write_be_i64 (import "svm" "reg_write_be_i64") (param i64 i32 i32))
+  (func $reg_read_be_i64 (import "svm" "reg_read_be_i64") (param i32 i32) (result i64))
+  (func $contract_call (import "svm" "contract_call") (param i32 i32 i32 i32 i32 i32) (result i32))
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; the called function name
+  (data (i32.const 100) "inc")
+
+  ;; calls `inc` of the contract whose address is at memory `0` (starting at `addr_ptr`)
+  (func (export "call_inc") (param i32 i64) (result i64)
+        ;; copy the called contract address into register `160:0`
+        i32.const 0   ;; src_mem_idx
+        get_local 0   ;; src_mem_ptr
+        i32.const 20  ;; len
+        i32.const 160 ;; dst_reg_bits
+        i32.const 0   ;; dst_reg_idx
+        call $mem_to_reg_copy
+
+        ;; register `64:0` = call arg
+        get_local 1   ;; value
+        i32.const 64  ;; reg_bits
+        i32.const 0   ;; reg_idx
+        call $reg_write_be_i64
+
+        i32.const 0   ;; addr_reg_idx
+        i32.const 100 ;; func_name_ptr
+        i32.const 3   ;; func_name_len
+        i32.const 0   ;; args_reg_idx
+        i32.const 1   ;; args_count
+        i32.const 1   ;; results_reg_idx
+        call $contract_call
+        drop
+
+        ;; return the call result (register `64:1`)
+        i32.const 64  ;; reg_bits
+        i32.const 1   ;; reg_idx
+        call $reg_read_be_i64))
@@ -0,0 +1,14 @@
+(module
+  ;; import `svm` vmcalls
+  (func $emit_event_from_mem (import "svm" "emit_event_from_mem") (param i32 i32 i32 i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported function to be called
+  (func (export "do_emit_from_mem") (param i32 i32 i32 i32)
+        get_local 0 ;; topic_reg_idx
+        i32.const 0 ;; src_mem_idx
+        get_local 1 ;; src_mem_ptr
+        get_local 2 ;; len
+        get_local 3 ;; expiration
+        call $emit_event_from_mem))
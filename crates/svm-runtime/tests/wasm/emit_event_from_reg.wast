@@ -0,0 +1,14 @@
+(module
+  ;; import `svm` vmcalls
+  (func $emit_event_from_reg (import "svm" "emit_event_from_reg") (param i32 i32 i32 i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported function to be called
+  (func (export "do_emit_from_reg") (param i32 i32 i32 i32)
+        get_local 0  ;; topic_reg_idx
+        i32.const 64 ;; src_reg_bits
+        get_local 1  ;; src_reg_idx
+        get_local 2  ;; len
+        get_local 3  ;; expiration
+        call $emit_event_from_reg))
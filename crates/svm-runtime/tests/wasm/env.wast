@@ -0,0 +1,46 @@
+(module
+  ;; import `svm` vmcalls
+  (func $get_sender_to_reg (import "svm" "get_sender_to_reg") (param i32))
+  (func $get_sender_to_mem (import "svm" "get_sender_to_mem") (param i32 i32))
+  (func $get_self_address_to_reg (import "svm" "get_self_address_to_reg") (param i32))
+  (func $get_self_address_to_mem (import "svm" "get_self_address_to_mem") (param i32 i32))
+  (func $get_layer_id (import "svm" "get_layer_id") (result i64))
+  (func $get_layer_timestamp (import "svm" "get_layer_timestamp") (result i64))
+  (func $get_beacon_to_reg (import "svm" "get_beacon_to_reg") (param i32))
+  (func $get_beacon_to_mem (import "svm" "get_beacon_to_mem") (param i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported functions to be called
+  (func (export "do_get_sender_to_reg") (param i32)
+        get_local 0 ;; reg_idx
+        call $get_sender_to_reg)
+
+  (func (export "do_get_sender_to_mem") (param i32)
+        i32.const 0 ;; mem_idx
+        get_local 0 ;; mem_ptr
+        call $get_sender_to_mem)
+
+  (func (export "do_get_self_address_to_reg") (param i32)
+        get_local 0 ;; reg_idx
+        call $get_self_address_to_reg)
+
+  (func (export "do_get_self_address_to_mem") (param i32)
+        i32.const 0 ;; mem_idx
+        get_local 0 ;; mem_ptr
+        call $get_self_address_to_mem)
+
+  (func (export "do_get_layer_id") (result i64)
+        call $get_layer_id)
+
+  (func (export "do_get_layer_timestamp") (result i64)
+        call $get_layer_timestamp)
+
+  (func (export "do_get_beacon_to_reg") (param i32)
+        get_local 0 ;; reg_idx
+        call $get_beacon_to_reg)
+
+  (func (export "do_get_beacon_to_mem") (param i32)
+        i32.const 0 ;; mem_idx
+        get_local 0 ;; mem_ptr
+        call $get_beacon_to_mem))
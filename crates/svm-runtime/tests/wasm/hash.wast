@@ -0,0 +1,44 @@
+(module
+  ;; import `svm` vmcalls
+  (func $hash_len (import "svm" "hash_len") (param i32 i32 i32) (result i32))
+  (func $hash_get_to_reg (import "svm" "hash_get_to_reg") (param i32 i32 i32 i32 i32 i32 i32) (result i32))
+  (func $hash_insert_from_reg (import "svm" "hash_insert_from_reg") (param i32 i32 i32 i32 i32 i32 i32) (result i32))
+  (func $hash_remove (import "svm" "hash_remove") (param i32 i32 i32 i32 i32) (result i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; the hash is stored at page `2`, keys are `4 bytes` and values are `8 bytes`
+
+  (func (export "len") (result i32)
+        i32.const 2 ;; page_idx
+        i32.const 4 ;; key_size
+        i32.const 8 ;; value_size
+        call $hash_len)
+
+  (func (export "insert") (param i32 i32) (result i32)
+        i32.const 2  ;; page_idx
+        i32.const 4  ;; key_size
+        i32.const 8  ;; value_size
+        i32.const 32 ;; key_reg_bits
+        get_local 0  ;; key_reg_idx
+        i32.const 64 ;; value_reg_bits
+        get_local 1  ;; value_reg_idx
+        call $hash_insert_from_reg)
+
+  (func (export "get") (param i32 i32) (result i32)
+        i32.const 2  ;; page_idx
+        i32.const 4  ;; key_size
+        i32.const 8  ;; value_size
+        i32.const 32 ;; key_reg_bits
+        get_local 0  ;; key_reg_idx
+        i32.const 64 ;; dst_reg_bits
+        get_local 1  ;; dst_reg_idx
+        call $hash_get_to_reg)
+
+  (func (export "remove") (param i32) (result i32)
+        i32.const 2  ;; page_idx
+        i32.const 4  ;; key_size
+        i32.const 8  ;; value_size
+        i32.const 32 ;; key_reg_bits
+        get_local 0  ;; key_reg_idx
+        call $hash_remove))
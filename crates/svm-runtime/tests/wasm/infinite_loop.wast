@@ -0,0 +1,7 @@
+(module
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported function to be called
+  (func (export "run")
+        (loop
+          br 0)))
@@ -0,0 +1,10 @@
+(module
+  ;; import `svm` vmcalls
+  (func $is_admin (import "svm" "is_admin") (param i32) (result i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; exported function to be called
+  (func (export "do_is_admin") (param i32) (result i32)
+        get_local 0 ;; addr_reg_idx
+        call $is_admin))
@@ -0,0 +1,31 @@
+(module
+  ;; import `svm` vmcalls
+  (func $get_sender_to_mem (import "svm" "get_sender_to_mem") (param i32 i32))
+  (func $get_self_address_to_mem (import "svm" "get_self_address_to_mem") (param i32 i32))
+  (func $get_layer_id (import "svm" "get_layer_id") (result i64))
+  (func $storage_write_from_mem (import "svm" "storage_write_from_mem") (param i32 i32 i32 i32 i32 i32))
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; persists the sender address followed by the contract address to storage (page=`0`, slice=`0`, offset=`0`)
+  (func (export "keep_env")
+        ;; copying the sender address into memory `0` (address `0`)
+        i32.const 0   ;; mem_idx
+        i32.const 0   ;; mem_ptr
+        call $get_sender_to_mem
+
+        ;; copying the contract address into memory `0` (address `20`)
+        i32.const 0   ;; mem_idx
+        i32.const 20  ;; mem_ptr
+        call $get_self_address_to_mem
+
+        ;; persist to storage
+        i32.const 0   ;; src_mem_idx
+        i32.const 0   ;; src_mem_ptr
+        i32.const 40  ;; len
+        i32.const 0   ;; dst_page
+        i32.const 0   ;; dst_slice
+        i32.const 0   ;; dst_offset
+        call $storage_write_from_mem)
+
+  (func (export "layer_id") (result i64)
+        call $get_layer_id))
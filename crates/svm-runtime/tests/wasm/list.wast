@@ -0,0 +1,60 @@
+(module
+  ;; import `svm` vmcalls
+  (func $list_len (import "svm" "list_len") (param i32 i32) (result i32))
+  (func $list_get_to_reg (import "svm" "list_get_to_reg") (param i32 i32 i32 i32 i32))
+  (func $list_push_front_from_reg (import "svm" "list_push_front_from_reg") (param i32 i32 i32 i32))
+  (func $list_push_back_from_reg (import "svm" "list_push_back_from_reg") (param i32 i32 i32 i32))
+  (func $list_pop_front_to_reg (import "svm" "list_pop_front_to_reg") (param i32 i32 i32 i32) (result i32))
+  (func $list_pop_back_to_reg (import "svm" "list_pop_back_to_reg") (param i32 i32 i32 i32) (result i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; the list is stored at page `1`, each item is `8 bytes` (unless given explicitly)
+
+  (func (export "len") (result i32)
+        i32.const 1 ;; page_idx
+        i32.const 8 ;; item_size
+        call $list_len)
+
+  (func (export "push_front") (param i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        i32.const 64 ;; src_reg_bits
+        get_local 0  ;; src_reg_idx
+        call $list_push_front_from_reg)
+
+  (func (export "push_back") (param i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        i32.const 64 ;; src_reg_bits
+        get_local 0  ;; src_reg_idx
+        call $list_push_back_from_reg)
+
+  (func (export "push_back_sized") (param i32 i32)
+        i32.const 1  ;; page_idx
+        get_local 0  ;; item_size
+        i32.const 64 ;; src_reg_bits
+        get_local 1  ;; src_reg_idx
+        call $list_push_back_from_reg)
+
+  (func (export "get") (param i32 i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        get_local 0  ;; idx
+        i32.const 64 ;; dst_reg_bits
+        get_local 1  ;; dst_reg_idx
+        call $list_get_to_reg)
+
+  (func (export "pop_front") (param i32) (result i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        i32.const 64 ;; dst_reg_bits
+        get_local 0  ;; dst_reg_idx
+        call $list_pop_front_to_reg)
+
+  (func (export "pop_back") (param i32) (result i32)
+        i32.const 1  ;; page_idx
+        i32.const 8  ;; item_size
+        i32.const 64 ;; dst_reg_bits
+        get_local 0  ;; dst_reg_idx
+        call $list_pop_back_to_reg))
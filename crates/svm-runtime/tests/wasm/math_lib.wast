@@ -0,0 +1,6 @@
+(module
+  ;; a library (imports nothing)
+  (func (export "add") (param i64 i64) (result i64)
+        get_local 0
+        get_local 1
+        i64.add))
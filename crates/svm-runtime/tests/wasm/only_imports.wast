@@ -7,7 +7,7 @@
   (func $storage_write_from_mem (import "svm" "storage_write_from_mem") (param i32 i32 i32 i32 i32 i32))
   (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32))
 
-  (memory 100)
+  (memory 100 100)
 
   ;; exported function to be called
   (func (export "do_nothing")))
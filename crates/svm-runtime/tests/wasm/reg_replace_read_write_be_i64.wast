@@ -4,7 +4,7 @@
   (func $svm_reg_read_be_i64 (import "svm" "reg_read_be_i64") (param i32 i32) (result i64))
   (func $svm_reg_write_be_i64 (import "svm" "reg_write_be_i64") (param i64 i32 i32))
 
-  (memory 1)  ;; memory `0` (default) is initialized with one page
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
 
   (func (export "inc") (param i32)
         ;; loading the register value
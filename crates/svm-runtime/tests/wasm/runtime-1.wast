@@ -2,7 +2,7 @@
   ;; import `svm` vmcalls
   (func $reg_write_be_i64 (import "svm" "reg_write_be_i64") (param i64 i32 i32))
   (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32 i32))
-  (memory 1)  ;; memory `0` (default) is initialized with one page
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
 
   (func (export "reg_set_and_persist") (param i64 i32 i32 i32 i32 i32)
         ;; register set
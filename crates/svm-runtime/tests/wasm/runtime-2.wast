@@ -0,0 +1,25 @@
+(module
+  ;; import `svm` vmcalls
+  (func $storage_read_to_reg (import "svm" "storage_read_to_reg") (param i32 i32 i32 i32 i32 i32))
+  (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32 i32))
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; copies the 8 bytes of (page=`0`, slice=`0`, offset=`0`) into (page=`0`, slice=`1`, offset=`8`)
+  (func (export "copy_slice")
+        ;; load from storage into register `64:0`
+        i32.const 0   ;; src_page
+        i32.const 0   ;; src_slice
+        i32.const 0   ;; offset
+        i32.const 8   ;; len
+        i32.const 64  ;; dst_reg_bits
+        i32.const 0   ;; dst_reg_idx
+        call $storage_read_to_reg
+
+        ;; persist register `64:0` to storage
+        i32.const 64  ;; src_reg_bits
+        i32.const 0   ;; src_reg_idx
+        i32.const 8   ;; len
+        i32.const 0   ;; dst_page
+        i32.const 1   ;; dst_slice
+        i32.const 8   ;; dst_offset
+        call $storage_write_from_reg))
@@ -0,0 +1,18 @@
+(module
+  ;; import `svm` vmcalls
+  (func $storage_write_from_mem (import "svm" "storage_write_from_mem") (param i32 i32 i32 i32 i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; persists the slice (copied by the runtime to memory `0` at `offset`) and returns its length
+  (func (export "persist_slice") (param i32 i64) (result i64)
+        i32.const 0   ;; src_mem_idx
+        get_local 0   ;; src_mem_ptr
+        get_local 1   ;; len
+        i32.wrap/i64
+        i32.const 0   ;; dst_page
+        i32.const 0   ;; dst_slice
+        i32.const 0   ;; dst_offset
+        call $storage_write_from_mem
+
+        get_local 1))
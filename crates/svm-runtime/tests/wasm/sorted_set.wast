@@ -0,0 +1,45 @@
+(module
+  ;; import `svm` vmcalls
+  (func $sorted_set_len (import "svm" "sorted_set_len") (param i32 i32) (result i32))
+  (func $sorted_set_get_to_reg (import "svm" "sorted_set_get_to_reg") (param i32 i32 i32 i32 i32))
+  (func $sorted_set_contains (import "svm" "sorted_set_contains") (param i32 i32 i32 i32) (result i32))
+  (func $sorted_set_insert_from_reg (import "svm" "sorted_set_insert_from_reg") (param i32 i32 i32 i32) (result i32))
+  (func $sorted_set_remove (import "svm" "sorted_set_remove") (param i32 i32 i32 i32) (result i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; the sorted set is stored at page `1`, each item is `4 bytes`
+
+  (func (export "len") (result i32)
+        i32.const 1 ;; page_idx
+        i32.const 4 ;; item_size
+        call $sorted_set_len)
+
+  (func (export "get") (param i32 i32)
+        i32.const 1  ;; page_idx
+        i32.const 4  ;; item_size
+        get_local 0  ;; idx
+        i32.const 32 ;; dst_reg_bits
+        get_local 1  ;; dst_reg_idx
+        call $sorted_set_get_to_reg)
+
+  (func (export "contains") (param i32) (result i32)
+        i32.const 1  ;; page_idx
+        i32.const 4  ;; item_size
+        i32.const 32 ;; reg_bits
+        get_local 0  ;; reg_idx
+        call $sorted_set_contains)
+
+  (func (export "insert") (param i32) (result i32)
+        i32.const 1  ;; page_idx
+        i32.const 4  ;; item_size
+        i32.const 32 ;; reg_bits
+        get_local 0  ;; reg_idx
+        call $sorted_set_insert_from_reg)
+
+  (func (export "remove") (param i32) (result i32)
+        i32.const 1  ;; page_idx
+        i32.const 4  ;; item_size
+        i32.const 32 ;; reg_bits
+        get_local 0  ;; reg_idx
+        call $sorted_set_remove))
@@ -2,7 +2,7 @@
   ;; import `svm` vmcalls
   (func $storage_write_from_mem (import "svm" "storage_write_from_mem") (param i32 i32 i32 i32 i32 i32))
 
-  (memory 1)  ;; memory `0` (default) is initialized with one page
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
 
   ;; exported function to be called
   (func (export "do_write_from_mem") (param i32 i32 i32 i32 i32)
@@ -0,0 +1,34 @@
+(module
+  ;; import `svm` vmcalls
+  (func $string_len (import "svm" "string_len") (param i32) (result i32))
+  (func $string_read_to_mem (import "svm" "string_read_to_mem") (param i32 i32 i32) (result i32))
+  (func $string_write_from_mem (import "svm" "string_write_from_mem") (param i32 i32 i32 i32))
+  (func $string_append_from_mem (import "svm" "string_append_from_mem") (param i32 i32 i32 i32))
+
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; the string is stored at page `1`
+
+  (func (export "len") (result i32)
+        i32.const 1 ;; page_idx
+        call $string_len)
+
+  (func (export "read") (param i32) (result i32)
+        i32.const 1 ;; page_idx
+        i32.const 0 ;; dst_mem_idx
+        get_local 0 ;; dst_mem_ptr
+        call $string_read_to_mem)
+
+  (func (export "write") (param i32 i32)
+        i32.const 1 ;; page_idx
+        i32.const 0 ;; src_mem_idx
+        get_local 0 ;; src_mem_ptr
+        get_local 1 ;; len
+        call $string_write_from_mem)
+
+  (func (export "append") (param i32 i32)
+        i32.const 1 ;; page_idx
+        i32.const 0 ;; src_mem_idx
+        get_local 0 ;; src_mem_ptr
+        get_local 1 ;; len
+        call $string_append_from_mem))
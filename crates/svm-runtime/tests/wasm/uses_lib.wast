@@ -0,0 +1,8 @@
+(module
+  ;; import the functions of the contract first dependency
+  (func $add (import "dep0" "add") (param i64 i64) (result i64))
+
+  (func (export "add_ten") (param i64) (result i64)
+        get_local 0
+        i64.const 10
+        call $add))
@@ -0,0 +1,21 @@
+(module
+  ;; import `svm` vmcalls
+  (func $get_value (import "svm" "get_value") (param i32 i32))
+  (func $storage_write_from_reg (import "svm" "storage_write_from_reg") (param i32 i32 i32 i32 i32 i32))
+  (memory 1 1)  ;; memory `0` (default) has a single page (it can't grow)
+
+  ;; persists the value sent with the transaction to storage (page=`0`, slice=`0`, offset=`0`)
+  (func (export "keep_value")
+        ;; copying the value into register `256:0`
+        i32.const 256  ;; reg_bits
+        i32.const 0    ;; reg_idx
+        call $get_value
+
+        ;; persist to storage
+        i32.const 256  ;; src_reg_bits
+        i32.const 0    ;; src_reg_idx
+        i32.const 16   ;; len
+        i32.const 0    ;; dst_page
+        i32.const 0    ;; dst_slice
+        i32.const 0    ;; dst_offset
+        call $storage_write_from_reg))
@@ -0,0 +1,247 @@
+use super::{read_len, validated_capacity, write_len, CollectionError, HEADER_SIZE};
+
+/// A fixed-capacity array of `item_size` bytes items, laid out over a page
+pub struct Array<'p> {
+    page: &'p mut [u8],
+    item_size: usize,
+    capacity: u32,
+}
+
+impl<'p> Array<'p> {
+    /// Views `page` as an `Array` of `item_size` bytes items.
+    /// An empty (zeros) page is an empty array.
+    /// Returns an error when the array length (stored in the page header) exceeds its capacity.
+    pub fn new(page: &'p mut [u8], item_size: usize) -> Result<Self, CollectionError> {
+        let capacity = validated_capacity(page, item_size)?;
+
+        Ok(Self {
+            page,
+            item_size,
+            capacity,
+        })
+    }
+
+    /// The maximum number of items the array can hold
+    #[inline(always)]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// The size of each item (in bytes)
+    #[inline(always)]
+    pub fn item_size(&self) -> usize {
+        self.item_size
+    }
+
+    /// The number of items in the array
+    #[inline(always)]
+    pub fn len(&self) -> u32 {
+        read_len(self.page)
+    }
+
+    /// Returns whether the array has no items
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the item at index `idx`
+    pub fn get(&self, idx: u32) -> Result<&[u8], CollectionError> {
+        self.ensure_index(idx)?;
+
+        let start = self.item_offset(idx);
+        Ok(&self.page[start..start + self.item_size])
+    }
+
+    /// Overrides the item at index `idx`
+    pub fn set(&mut self, idx: u32, item: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_item_size(item)?;
+        self.ensure_index(idx)?;
+
+        let start = self.item_offset(idx);
+        self.page[start..start + self.item_size].copy_from_slice(item);
+
+        Ok(())
+    }
+
+    /// Appends `item` to the end of the array
+    pub fn push(&mut self, item: &[u8]) -> Result<(), CollectionError> {
+        let len = self.len();
+        self.insert(len, item)
+    }
+
+    /// Removes the last item and returns it (`None` when the array is empty)
+    pub fn pop(&mut self) -> Option<Vec<u8>> {
+        match self.len() {
+            0 => None,
+            len => self.remove(len - 1).ok(),
+        }
+    }
+
+    /// Inserts `item` at index `idx`, shifting all the items after it to the right
+    pub fn insert(&mut self, idx: u32, item: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_item_size(item)?;
+
+        let len = self.len();
+
+        if idx > len {
+            return Err(CollectionError::IndexOutOfBounds(idx));
+        }
+
+        if len == self.capacity {
+            return Err(CollectionError::Full);
+        }
+
+        let start = self.item_offset(idx);
+        let end = self.item_offset(len);
+
+        self.page.copy_within(start..end, start + self.item_size);
+        self.page[start..start + self.item_size].copy_from_slice(item);
+
+        write_len(self.page, len + 1);
+
+        Ok(())
+    }
+
+    /// Removes the item at index `idx` and returns it, shifting all the items after it to the left
+    pub fn remove(&mut self, idx: u32) -> Result<Vec<u8>, CollectionError> {
+        self.ensure_index(idx)?;
+
+        let len = self.len();
+        let start = self.item_offset(idx);
+        let end = self.item_offset(len);
+
+        let item = self.page[start..start + self.item_size].to_vec();
+
+        self.page.copy_within(start + self.item_size..end, start);
+
+        // zeroing the vacated last item keeps the page content canonical
+        for byte in self.page[end - self.item_size..end].iter_mut() {
+            *byte = 0;
+        }
+
+        write_len(self.page, len - 1);
+
+        Ok(item)
+    }
+
+    #[inline(always)]
+    fn item_offset(&self, idx: u32) -> usize {
+        HEADER_SIZE + (idx as usize) * self.item_size
+    }
+
+    fn ensure_index(&self, idx: u32) -> Result<(), CollectionError> {
+        if idx < self.len() {
+            Ok(())
+        } else {
+            Err(CollectionError::IndexOutOfBounds(idx))
+        }
+    }
+
+    fn ensure_item_size(&self, item: &[u8]) -> Result<(), CollectionError> {
+        if item.len() == self.item_size {
+            Ok(())
+        } else {
+            Err(CollectionError::InvalidItemSize(item.len()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::zero_page;
+
+    #[test]
+    fn array_zero_page_is_empty() {
+        let mut page = zero_page();
+        let array = Array::new(&mut page, 8).unwrap();
+
+        assert!(array.is_empty());
+        assert_eq!((4096 - 4) / 8, array.capacity());
+        assert_eq!(Err(CollectionError::IndexOutOfBounds(0)), array.get(0));
+    }
+
+    #[test]
+    fn array_invalid_item_size() {
+        let mut page = zero_page();
+
+        assert!(Array::new(&mut page, 0).is_err());
+        assert!(Array::new(&mut page, 4096).is_err());
+
+        let mut array = Array::new(&mut page, 2).unwrap();
+        assert_eq!(
+            Err(CollectionError::InvalidItemSize(3)),
+            array.push(&[1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn array_push_get_set_pop() {
+        let mut page = zero_page();
+        let mut array = Array::new(&mut page, 2).unwrap();
+
+        array.push(&[10, 20]).unwrap();
+        array.push(&[30, 40]).unwrap();
+        assert_eq!(2, array.len());
+
+        array.set(0, &[50, 60]).unwrap();
+        assert_eq!(&[50, 60], array.get(0).unwrap());
+        assert_eq!(&[30, 40], array.get(1).unwrap());
+
+        assert_eq!(Some(vec![30, 40]), array.pop());
+        assert_eq!(Some(vec![50, 60]), array.pop());
+        assert_eq!(None, array.pop());
+
+        // the page is back to being a zero page
+        assert_eq!(zero_page(), page);
+    }
+
+    #[test]
+    fn array_insert_and_remove() {
+        let mut page = zero_page();
+        let mut array = Array::new(&mut page, 1).unwrap();
+
+        array.push(&[10]).unwrap();
+        array.push(&[30]).unwrap();
+        array.insert(1, &[20]).unwrap();
+        array.insert(0, &[0]).unwrap();
+
+        let items = (0..array.len())
+            .map(|i| array.get(i).unwrap()[0])
+            .collect::<Vec<u8>>();
+        assert_eq!(vec![0, 10, 20, 30], items);
+
+        assert_eq!(Ok(vec![10]), array.remove(1));
+        assert_eq!(&[20], array.get(1).unwrap());
+        assert_eq!(3, array.len());
+
+        assert_eq!(
+            Err(CollectionError::IndexOutOfBounds(5)),
+            array.insert(5, &[1])
+        );
+        assert_eq!(Err(CollectionError::IndexOutOfBounds(3)), array.remove(3));
+    }
+
+    #[test]
+    fn array_invalid_length() {
+        let mut page = vec![0; 4 + 2 * 3];
+        page[0..4].copy_from_slice(&3u32.to_be_bytes());
+
+        assert_eq!(
+            Err(CollectionError::InvalidLength(3)),
+            Array::new(&mut page, 3).map(|array| array.len())
+        );
+    }
+
+    #[test]
+    fn array_full() {
+        let mut page = vec![0; 4 + 2 * 3];
+        let mut array = Array::new(&mut page, 3).unwrap();
+
+        array.push(&[1, 1, 1]).unwrap();
+        array.push(&[2, 2, 2]).unwrap();
+
+        assert_eq!(Err(CollectionError::Full), array.push(&[3, 3, 3]));
+    }
+}
@@ -0,0 +1,47 @@
+/// Storage collection error
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum CollectionError {
+    InvalidItemSize(usize),
+    IndexOutOfBounds(u32),
+    InvalidLength(u32),
+    InvalidHead(u32),
+    Full,
+}
+
+impl std::error::Error for CollectionError {
+    fn description(&self) -> &'static str {
+        match self {
+            CollectionError::InvalidItemSize(_) => "Invalid item size",
+            CollectionError::IndexOutOfBounds(_) => "Index out of bounds",
+            CollectionError::InvalidLength(_) => "Invalid collection length",
+            CollectionError::InvalidHead(_) => "Invalid list head",
+            CollectionError::Full => "Collection is full",
+        }
+    }
+}
+
+impl std::fmt::Display for CollectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            CollectionError::InvalidItemSize(size) => format!("Invalid item size: `{}`", size),
+            CollectionError::IndexOutOfBounds(idx) => format!("Index `{}` is out of bounds", idx),
+            CollectionError::InvalidLength(len) => {
+                format!(
+                    "Invalid collection length: `{}` (exceeds its capacity)",
+                    len
+                )
+            }
+            CollectionError::InvalidHead(head) => format!("Invalid list head: `{}`", head),
+            CollectionError::Full => "Collection is full".to_string(),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for CollectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
@@ -0,0 +1,301 @@
+use super::{read_len, validated_capacity, write_len, CollectionError, HEADER_SIZE};
+
+const EMPTY: u8 = 0;
+const OCCUPIED: u8 = 1;
+const DELETED: u8 = 2;
+
+/// A fixed-capacity hash table of `key_size` bytes keys and `value_size` bytes values, laid out over a page.
+///
+/// Each bucket is laid out as `tag (1 byte) || key || value`, where the tag is one of:
+/// `0` (empty), `1` (occupied) or `2` (deleted). Collisions are resolved by linear probing,
+/// starting at bucket `FNV-1a(key) % capacity`.
+pub struct Hash<'p> {
+    page: &'p mut [u8],
+    key_size: usize,
+    value_size: usize,
+    capacity: u32,
+}
+
+impl<'p> Hash<'p> {
+    /// Views `page` as a `Hash` of `key_size` bytes keys and `value_size` bytes values.
+    /// An empty (zeros) page is an empty hash.
+    /// Returns an error when the hash length (stored in the page header) exceeds its capacity.
+    pub fn new(
+        page: &'p mut [u8],
+        key_size: usize,
+        value_size: usize,
+    ) -> Result<Self, CollectionError> {
+        if key_size == 0 {
+            return Err(CollectionError::InvalidItemSize(key_size));
+        }
+
+        let capacity = validated_capacity(page, 1 + key_size + value_size)?;
+
+        Ok(Self {
+            page,
+            key_size,
+            value_size,
+            capacity,
+        })
+    }
+
+    /// The maximum number of entries the hash can hold
+    #[inline(always)]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// The number of entries in the hash
+    #[inline(always)]
+    pub fn len(&self) -> u32 {
+        read_len(self.page)
+    }
+
+    /// Returns whether the hash has no entries
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the value stored under `key` (if any)
+    pub fn get(&self, key: &[u8]) -> Result<Option<&[u8]>, CollectionError> {
+        self.ensure_size(key, self.key_size)?;
+
+        match self.find(key) {
+            None => Ok(None),
+            Some(bucket) => {
+                let start = self.bucket_offset(bucket) + 1 + self.key_size;
+                Ok(Some(&self.page[start..start + self.value_size]))
+            }
+        }
+    }
+
+    /// Returns whether `key` has an entry
+    pub fn contains(&self, key: &[u8]) -> Result<bool, CollectionError> {
+        self.ensure_size(key, self.key_size)?;
+
+        Ok(self.find(key).is_some())
+    }
+
+    /// Stores `value` under `key` (overriding the current value if any).
+    /// Returns `true` when a new entry has been added.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> Result<bool, CollectionError> {
+        self.ensure_size(key, self.key_size)?;
+        self.ensure_size(value, self.value_size)?;
+
+        let (bucket, is_new) = match self.find(key) {
+            Some(bucket) => (bucket, false),
+            None => {
+                let len = self.len();
+
+                if len == self.capacity {
+                    return Err(CollectionError::Full);
+                }
+
+                // the buckets tags may disagree with the length (when the page is corrupted)
+                let bucket = self.find_vacant(key).ok_or(CollectionError::Full)?;
+
+                write_len(self.page, len + 1);
+
+                (bucket, true)
+            }
+        };
+
+        let start = self.bucket_offset(bucket);
+        let key_start = start + 1;
+        let value_start = key_start + self.key_size;
+
+        self.page[start] = OCCUPIED;
+        self.page[key_start..value_start].copy_from_slice(key);
+        self.page[value_start..value_start + self.value_size].copy_from_slice(value);
+
+        Ok(is_new)
+    }
+
+    /// Removes the entry of `key`. Returns `true` when there was such entry.
+    pub fn remove(&mut self, key: &[u8]) -> Result<bool, CollectionError> {
+        self.ensure_size(key, self.key_size)?;
+
+        match self.find(key) {
+            None => Ok(false),
+            Some(bucket) => {
+                let start = self.bucket_offset(bucket);
+                let end = start + 1 + self.key_size + self.value_size;
+
+                // the bucket is marked as deleted, so probing for other keys will continue past it
+                self.page[start] = DELETED;
+
+                for byte in self.page[start + 1..end].iter_mut() {
+                    *byte = 0;
+                }
+
+                // saturating, since the buckets tags may disagree with the length (when the page is corrupted)
+                let len = self.len();
+                write_len(self.page, len.saturating_sub(1));
+
+                Ok(true)
+            }
+        }
+    }
+
+    /// Returns the bucket holding `key` (if any)
+    fn find(&self, key: &[u8]) -> Option<u32> {
+        for bucket in self.probe(key) {
+            let start = self.bucket_offset(bucket);
+
+            match self.page[start] {
+                EMPTY => return None,
+                OCCUPIED if &self.page[start + 1..start + 1 + self.key_size] == key => {
+                    return Some(bucket)
+                }
+                _ => continue,
+            }
+        }
+
+        None
+    }
+
+    /// Returns the first non-occupied bucket along the probing sequence of `key` (if any)
+    fn find_vacant(&self, key: &[u8]) -> Option<u32> {
+        self.probe(key)
+            .find(|bucket| self.page[self.bucket_offset(*bucket)] != OCCUPIED)
+    }
+
+    fn probe(&self, key: &[u8]) -> impl Iterator<Item = u32> {
+        let capacity = self.capacity;
+        let first = fnv1a(key) % capacity;
+
+        (0..capacity).map(move |i| (first + i) % capacity)
+    }
+
+    #[inline(always)]
+    fn bucket_offset(&self, bucket: u32) -> usize {
+        HEADER_SIZE + (bucket as usize) * (1 + self.key_size + self.value_size)
+    }
+
+    fn ensure_size(&self, bytes: &[u8], size: usize) -> Result<(), CollectionError> {
+        if bytes.len() == size {
+            Ok(())
+        } else {
+            Err(CollectionError::InvalidItemSize(bytes.len()))
+        }
+    }
+}
+
+/// The 32-bit `FNV-1a` hash. We need a hash function that is stable across platforms and versions
+fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash: u32 = 0x81_1c_9d_c5;
+
+    for byte in bytes {
+        hash ^= *byte as u32;
+        hash = hash.wrapping_mul(0x01_00_01_93);
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::zero_page;
+
+    #[test]
+    fn hash_zero_page_is_empty() {
+        let mut page = zero_page();
+        let hash = Hash::new(&mut page, 4, 8).unwrap();
+
+        assert!(hash.is_empty());
+        assert_eq!((4096 - 4) / 13, hash.capacity());
+        assert_eq!(Ok(None), hash.get(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn hash_insert_get_override() {
+        let mut page = zero_page();
+        let mut hash = Hash::new(&mut page, 2, 3).unwrap();
+
+        assert_eq!(Ok(true), hash.insert(&[1, 1], &[10, 20, 30]));
+        assert_eq!(Ok(true), hash.insert(&[2, 2], &[40, 50, 60]));
+        assert_eq!(2, hash.len());
+
+        assert_eq!(Ok(Some(&[10, 20, 30][..])), hash.get(&[1, 1]));
+        assert_eq!(Ok(Some(&[40, 50, 60][..])), hash.get(&[2, 2]));
+
+        assert_eq!(Ok(false), hash.insert(&[1, 1], &[70, 80, 90]));
+        assert_eq!(Ok(Some(&[70, 80, 90][..])), hash.get(&[1, 1]));
+        assert_eq!(2, hash.len());
+    }
+
+    #[test]
+    fn hash_remove() {
+        let mut page = zero_page();
+        let mut hash = Hash::new(&mut page, 1, 1).unwrap();
+
+        hash.insert(&[1], &[10]).unwrap();
+        hash.insert(&[2], &[20]).unwrap();
+
+        assert_eq!(Ok(true), hash.remove(&[1]));
+        assert_eq!(Ok(false), hash.remove(&[1]));
+        assert_eq!(Ok(None), hash.get(&[1]));
+        assert_eq!(Ok(Some(&[20][..])), hash.get(&[2]));
+        assert_eq!(1, hash.len());
+    }
+
+    #[test]
+    fn hash_collisions() {
+        // a page with only 3 buckets, so keys are bound to collide
+        let mut page = vec![0; 4 + 3 * 3];
+        let mut hash = Hash::new(&mut page, 1, 1).unwrap();
+
+        hash.insert(&[1], &[10]).unwrap();
+        hash.insert(&[2], &[20]).unwrap();
+        hash.insert(&[3], &[30]).unwrap();
+        assert_eq!(Err(CollectionError::Full), hash.insert(&[4], &[40]));
+
+        // removing an entry keeps the others reachable, and frees a bucket
+        hash.remove(&[2]).unwrap();
+        assert_eq!(Ok(Some(&[10][..])), hash.get(&[1]));
+        assert_eq!(Ok(Some(&[30][..])), hash.get(&[3]));
+
+        assert_eq!(Ok(true), hash.insert(&[4], &[40]));
+        assert_eq!(Ok(Some(&[40][..])), hash.get(&[4]));
+        assert_eq!(3, hash.len());
+    }
+
+    #[test]
+    fn hash_corrupted_page() {
+        let mut page = vec![0; 4 + 2 * 3];
+        page[0..4].copy_from_slice(&3u32.to_be_bytes());
+
+        assert_eq!(
+            Err(CollectionError::InvalidLength(3)),
+            Hash::new(&mut page, 1, 1).map(|hash| hash.len())
+        );
+
+        // all the buckets are tagged as occupied, while the length says the hash is empty
+        let mut page = vec![0, 0, 0, 0, 1, 1, 10, 1, 2, 20];
+        let mut hash = Hash::new(&mut page, 1, 1).unwrap();
+
+        assert_eq!(Err(CollectionError::Full), hash.insert(&[3], &[30]));
+        assert_eq!(0, hash.len());
+
+        assert_eq!(Ok(true), hash.remove(&[1]));
+        assert_eq!(0, hash.len());
+    }
+
+    #[test]
+    fn hash_invalid_sizes() {
+        let mut page = zero_page();
+        assert!(Hash::new(&mut page, 0, 1).is_err());
+
+        let mut hash = Hash::new(&mut page, 2, 2).unwrap();
+        assert_eq!(
+            Err(CollectionError::InvalidItemSize(1)),
+            hash.insert(&[1], &[1, 1])
+        );
+        assert_eq!(
+            Err(CollectionError::InvalidItemSize(3)),
+            hash.insert(&[1, 1], &[1, 1, 1])
+        );
+    }
+}
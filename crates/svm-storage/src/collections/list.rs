@@ -0,0 +1,325 @@
+use super::{read_len, write_len, CollectionError, HEADER_SIZE};
+
+/// The number of bytes holding the slot of the list first item (following the collection header)
+const HEAD_SIZE: usize = 4;
+
+/// A fixed-capacity list of `item_size` bytes items, laid out over a page as a ring buffer:
+///
+/// ```text
+///  +-------------------+----------------+---------------------------------+
+///  | length (4 bytes)  | head (4 bytes) |  slots (`item_size` bytes each) |
+///  +-------------------+----------------+---------------------------------+
+/// ```
+///
+/// The item at index `idx` is kept at slot `(head + idx) % capacity`, so pushing and popping items
+/// at both ends of the list doesn't shift the other items (unlike `Array::insert / Array::remove`).
+pub struct List<'p> {
+    page: &'p mut [u8],
+    item_size: usize,
+    capacity: u32,
+}
+
+impl<'p> List<'p> {
+    /// Views `page` as a `List` of `item_size` bytes items.
+    /// An empty (zeros) page is an empty list.
+    /// Returns an error when the list length or head (stored in the page) exceed its capacity.
+    pub fn new(page: &'p mut [u8], item_size: usize) -> Result<Self, CollectionError> {
+        let slots_size = page.len().saturating_sub(HEADER_SIZE + HEAD_SIZE);
+
+        if item_size == 0 || item_size > slots_size {
+            return Err(CollectionError::InvalidItemSize(item_size));
+        }
+
+        let capacity = (slots_size / item_size) as u32;
+
+        let list = Self {
+            page,
+            item_size,
+            capacity,
+        };
+
+        let len = list.len();
+
+        if len > capacity {
+            return Err(CollectionError::InvalidLength(len));
+        }
+
+        let head = list.head();
+
+        if head >= capacity || (len == 0 && head != 0) {
+            return Err(CollectionError::InvalidHead(head));
+        }
+
+        Ok(list)
+    }
+
+    /// The maximum number of items the list can hold
+    #[inline(always)]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// The size of each item (in bytes)
+    #[inline(always)]
+    pub fn item_size(&self) -> usize {
+        self.item_size
+    }
+
+    /// The number of items in the list
+    #[inline(always)]
+    pub fn len(&self) -> u32 {
+        read_len(self.page)
+    }
+
+    /// Returns whether the list has no items
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the item at index `idx` (the first item is at index `0`)
+    pub fn get(&self, idx: u32) -> Result<&[u8], CollectionError> {
+        self.ensure_index(idx)?;
+
+        let start = self.item_offset(idx);
+        Ok(&self.page[start..start + self.item_size])
+    }
+
+    /// Overrides the item at index `idx`
+    pub fn set(&mut self, idx: u32, item: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_item_size(item)?;
+        self.ensure_index(idx)?;
+
+        let start = self.item_offset(idx);
+        self.page[start..start + self.item_size].copy_from_slice(item);
+
+        Ok(())
+    }
+
+    /// Prepends `item` to the start of the list
+    pub fn push_front(&mut self, item: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_can_push(item)?;
+
+        let head = (self.head() + self.capacity - 1) % self.capacity;
+        self.write_head(head);
+
+        let len = self.len();
+        write_len(self.page, len + 1);
+
+        self.set(0, item)
+    }
+
+    /// Appends `item` to the end of the list
+    pub fn push_back(&mut self, item: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_can_push(item)?;
+
+        let len = self.len();
+        write_len(self.page, len + 1);
+
+        self.set(len, item)
+    }
+
+    /// Removes the first item and returns it (`None` when the list is empty)
+    pub fn pop_front(&mut self) -> Option<Vec<u8>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let item = self.take(0);
+
+        if !self.is_empty() {
+            let head = (self.head() + 1) % self.capacity;
+            self.write_head(head);
+        }
+
+        Some(item)
+    }
+
+    /// Removes the last item and returns it (`None` when the list is empty)
+    pub fn pop_back(&mut self) -> Option<Vec<u8>> {
+        match self.len() {
+            0 => None,
+            len => Some(self.take(len - 1)),
+        }
+    }
+
+    /// Zeroes the item at index `idx` (either the first or the last item) and decrements the length.
+    /// An emptied list has its head reset, so its page content is canonical (a zero page).
+    fn take(&mut self, idx: u32) -> Vec<u8> {
+        let start = self.item_offset(idx);
+        let item = self.page[start..start + self.item_size].to_vec();
+
+        for byte in self.page[start..start + self.item_size].iter_mut() {
+            *byte = 0;
+        }
+
+        let len = self.len() - 1;
+        write_len(self.page, len);
+
+        if len == 0 {
+            self.write_head(0);
+        }
+
+        item
+    }
+
+    fn head(&self) -> u32 {
+        let mut buf = [0; HEAD_SIZE];
+        buf.copy_from_slice(&self.page[HEADER_SIZE..HEADER_SIZE + HEAD_SIZE]);
+
+        u32::from_be_bytes(buf)
+    }
+
+    fn write_head(&mut self, head: u32) {
+        self.page[HEADER_SIZE..HEADER_SIZE + HEAD_SIZE].copy_from_slice(&head.to_be_bytes());
+    }
+
+    #[inline(always)]
+    fn item_offset(&self, idx: u32) -> usize {
+        let slot = (self.head() as u64 + idx as u64) % self.capacity as u64;
+
+        HEADER_SIZE + HEAD_SIZE + (slot as usize) * self.item_size
+    }
+
+    fn ensure_index(&self, idx: u32) -> Result<(), CollectionError> {
+        if idx < self.len() {
+            Ok(())
+        } else {
+            Err(CollectionError::IndexOutOfBounds(idx))
+        }
+    }
+
+    fn ensure_item_size(&self, item: &[u8]) -> Result<(), CollectionError> {
+        if item.len() == self.item_size {
+            Ok(())
+        } else {
+            Err(CollectionError::InvalidItemSize(item.len()))
+        }
+    }
+
+    fn ensure_can_push(&self, item: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_item_size(item)?;
+
+        if self.len() == self.capacity {
+            Err(CollectionError::Full)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::zero_page;
+
+    fn items(list: &List) -> Vec<u8> {
+        (0..list.len()).map(|i| list.get(i).unwrap()[0]).collect()
+    }
+
+    #[test]
+    fn list_zero_page_is_empty() {
+        let mut page = zero_page();
+        let list = List::new(&mut page, 8).unwrap();
+
+        assert!(list.is_empty());
+        assert_eq!((4096 - 4 - 4) / 8, list.capacity());
+        assert_eq!(Err(CollectionError::IndexOutOfBounds(0)), list.get(0));
+    }
+
+    #[test]
+    fn list_invalid_item_size() {
+        let mut page = zero_page();
+
+        assert!(List::new(&mut page, 0).is_err());
+        assert!(List::new(&mut page, 4096 - 4).is_err());
+
+        let mut list = List::new(&mut page, 2).unwrap();
+        assert_eq!(
+            Err(CollectionError::InvalidItemSize(3)),
+            list.push_back(&[1, 2, 3])
+        );
+        assert_eq!(
+            Err(CollectionError::InvalidItemSize(1)),
+            list.push_front(&[1])
+        );
+    }
+
+    #[test]
+    fn list_push_and_pop_at_both_ends() {
+        let mut page = zero_page();
+        let mut list = List::new(&mut page, 1).unwrap();
+
+        list.push_back(&[20]).unwrap();
+        list.push_front(&[10]).unwrap();
+        list.push_back(&[30]).unwrap();
+        list.push_front(&[0]).unwrap();
+        assert_eq!(vec![0, 10, 20, 30], items(&list));
+
+        list.set(1, &[15]).unwrap();
+        assert_eq!(vec![0, 15, 20, 30], items(&list));
+
+        assert_eq!(Some(vec![0]), list.pop_front());
+        assert_eq!(Some(vec![30]), list.pop_back());
+        assert_eq!(vec![15, 20], items(&list));
+
+        assert_eq!(Some(vec![20]), list.pop_back());
+        assert_eq!(Some(vec![15]), list.pop_front());
+        assert_eq!(None, list.pop_front());
+        assert_eq!(None, list.pop_back());
+
+        // the page is back to being a zero page
+        assert_eq!(zero_page(), page);
+    }
+
+    #[test]
+    fn list_wraps_around_the_page() {
+        let mut page = vec![0; 4 + 4 + 3];
+        let mut list = List::new(&mut page, 1).unwrap();
+
+        list.push_back(&[1]).unwrap();
+        list.push_back(&[2]).unwrap();
+        list.push_back(&[3]).unwrap();
+        assert_eq!(Err(CollectionError::Full), list.push_front(&[0]));
+
+        assert_eq!(Some(vec![1]), list.pop_front());
+        list.push_back(&[4]).unwrap();
+        assert_eq!(vec![2, 3, 4], items(&list));
+
+        assert_eq!(Some(vec![4]), list.pop_back());
+        assert_eq!(Some(vec![3]), list.pop_back());
+        list.push_front(&[1]).unwrap();
+        list.push_front(&[0]).unwrap();
+        assert_eq!(vec![0, 1, 2], items(&list));
+        assert_eq!(Err(CollectionError::Full), list.push_back(&[3]));
+    }
+
+    #[test]
+    fn list_invalid_length_or_head() {
+        let mut page = vec![0; 4 + 4 + 2 * 3];
+        page[0..4].copy_from_slice(&3u32.to_be_bytes());
+
+        assert_eq!(
+            Err(CollectionError::InvalidLength(3)),
+            List::new(&mut page, 3).map(|list| list.len())
+        );
+
+        let mut page = vec![0; 4 + 4 + 2 * 3];
+        page[0..4].copy_from_slice(&1u32.to_be_bytes());
+        page[4..8].copy_from_slice(&2u32.to_be_bytes());
+
+        assert_eq!(
+            Err(CollectionError::InvalidHead(2)),
+            List::new(&mut page, 3).map(|list| list.len())
+        );
+
+        // an empty list must have a zero head
+        let mut page = vec![0; 4 + 4 + 2 * 3];
+        page[4..8].copy_from_slice(&1u32.to_be_bytes());
+
+        assert_eq!(
+            Err(CollectionError::InvalidHead(1)),
+            List::new(&mut page, 3).map(|list| list.len())
+        );
+    }
+}
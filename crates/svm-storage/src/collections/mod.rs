@@ -0,0 +1,75 @@
+//! Each collection occupies exactly one page, so its layout is fully determined by the page content:
+//!
+//! ```text
+//!  +-------------------+-----------------------------------------+
+//!  | length (4 bytes)  |  items / buckets (fixed-size entries)   |
+//!  +-------------------+-----------------------------------------+
+//! ```
+//!
+//! * `Array` - items stored contiguously, in order (see `Array::insert / Array::remove` for shifting items).
+//! * `List`  - items stored in a ring buffer (the header is followed by the first item slot),
+//!   so pushing and popping at both ends of the list doesn't shift the other items.
+//! * `Hash`  - an open-addressing hash table (linear probing) of fixed-size keys and values.
+//! * `Set`   - a `Hash` with no values.
+//! * `SortedSet` - an `Array` whose items are kept sorted (looked up by a binary search).
+//! * `Str`   - a string of bytes (the header holds the number of bytes).
+//!
+//! The collections operate over a page buffer. Reading and writing the page is done by the caller
+//! (see `PageSliceCache::read_page / PageSliceCache::write_page`). A page used by a collection
+//! shouldn't be accessed via page-slices.
+
+mod array;
+mod error;
+mod hash;
+mod list;
+mod set;
+mod sorted_set;
+mod string;
+
+pub use array::Array;
+pub use error::CollectionError;
+pub use hash::Hash;
+pub use list::List;
+pub use set::Set;
+pub use sorted_set::SortedSet;
+pub use string::Str;
+
+/// The number of bytes of a collection header (holding the collection length)
+pub const HEADER_SIZE: usize = 4;
+
+/// Returns the collection length stored under the `page` header (Big-Endian)
+fn read_len(page: &[u8]) -> u32 {
+    let mut buf = [0; HEADER_SIZE];
+    buf.copy_from_slice(&page[0..HEADER_SIZE]);
+
+    u32::from_be_bytes(buf)
+}
+
+/// Stores `len` under the `page` header (Big-Endian)
+fn write_len(page: &mut [u8], len: u32) {
+    page[0..HEADER_SIZE].copy_from_slice(&len.to_be_bytes());
+}
+
+/// Returns the number of `entry_size` entries fitting into `page` (following its header)
+fn entries_capacity(page: &[u8], entry_size: usize) -> Result<u32, CollectionError> {
+    let entries_size = page.len().saturating_sub(HEADER_SIZE);
+
+    if entry_size == 0 || entry_size > entries_size {
+        return Err(CollectionError::InvalidItemSize(entry_size));
+    }
+
+    Ok((entries_size / entry_size) as u32)
+}
+
+/// Returns the number of `entry_size` entries fitting into `page`,
+/// after validating the length stored under the `page` header doesn't exceed it.
+fn validated_capacity(page: &[u8], entry_size: usize) -> Result<u32, CollectionError> {
+    let capacity = entries_capacity(page, entry_size)?;
+    let len = read_len(page);
+
+    if len > capacity {
+        return Err(CollectionError::InvalidLength(len));
+    }
+
+    Ok(capacity)
+}
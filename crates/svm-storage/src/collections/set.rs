@@ -0,0 +1,74 @@
+use super::{CollectionError, Hash};
+
+/// A fixed-capacity set of `item_size` bytes items, laid out over a page.
+/// A `Set` is a `Hash` whose entries have no values.
+pub struct Set<'p> {
+    hash: Hash<'p>,
+}
+
+impl<'p> Set<'p> {
+    /// Views `page` as a `Set` of `item_size` bytes items.
+    /// An empty (zeros) page is an empty set.
+    pub fn new(page: &'p mut [u8], item_size: usize) -> Result<Self, CollectionError> {
+        let hash = Hash::new(page, item_size, 0)?;
+
+        Ok(Self { hash })
+    }
+
+    /// The maximum number of items the set can hold
+    #[inline(always)]
+    pub fn capacity(&self) -> u32 {
+        self.hash.capacity()
+    }
+
+    /// The number of items in the set
+    #[inline(always)]
+    pub fn len(&self) -> u32 {
+        self.hash.len()
+    }
+
+    /// Returns whether the set has no items
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.hash.is_empty()
+    }
+
+    /// Returns whether `item` is in the set
+    pub fn contains(&self, item: &[u8]) -> Result<bool, CollectionError> {
+        self.hash.contains(item)
+    }
+
+    /// Adds `item` to the set. Returns `true` when `item` wasn't in the set.
+    pub fn insert(&mut self, item: &[u8]) -> Result<bool, CollectionError> {
+        self.hash.insert(item, &[])
+    }
+
+    /// Removes `item` from the set. Returns `true` when `item` was in the set.
+    pub fn remove(&mut self, item: &[u8]) -> Result<bool, CollectionError> {
+        self.hash.remove(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::zero_page;
+
+    #[test]
+    fn set_insert_contains_remove() {
+        let mut page = zero_page();
+        let mut set = Set::new(&mut page, 3).unwrap();
+
+        assert_eq!((4096 - 4) / 4, set.capacity());
+        assert_eq!(Ok(false), set.contains(&[1, 2, 3]));
+
+        assert_eq!(Ok(true), set.insert(&[1, 2, 3]));
+        assert_eq!(Ok(false), set.insert(&[1, 2, 3]));
+        assert_eq!(Ok(true), set.contains(&[1, 2, 3]));
+        assert_eq!(1, set.len());
+
+        assert_eq!(Ok(true), set.remove(&[1, 2, 3]));
+        assert_eq!(Ok(false), set.contains(&[1, 2, 3]));
+        assert!(set.is_empty());
+    }
+}
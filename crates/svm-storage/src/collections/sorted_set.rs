@@ -0,0 +1,150 @@
+use super::{Array, CollectionError};
+
+/// A fixed-capacity sorted set of `item_size` bytes items, laid out over a page.
+/// A `SortedSet` is an `Array` whose items are kept sorted (by their bytes, i.e `Big-Endian` numeric order
+/// for unsigned integers), so items are looked up by a binary search.
+pub struct SortedSet<'p> {
+    array: Array<'p>,
+}
+
+impl<'p> SortedSet<'p> {
+    /// Views `page` as a `SortedSet` of `item_size` bytes items.
+    /// An empty (zeros) page is an empty sorted set.
+    /// Returns an error when the sorted set length (stored in the page header) exceeds its capacity.
+    pub fn new(page: &'p mut [u8], item_size: usize) -> Result<Self, CollectionError> {
+        let array = Array::new(page, item_size)?;
+
+        Ok(Self { array })
+    }
+
+    /// The maximum number of items the sorted set can hold
+    #[inline(always)]
+    pub fn capacity(&self) -> u32 {
+        self.array.capacity()
+    }
+
+    /// The number of items in the sorted set
+    #[inline(always)]
+    pub fn len(&self) -> u32 {
+        self.array.len()
+    }
+
+    /// Returns whether the sorted set has no items
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Returns the item ranked `idx` (the smallest item is ranked `0`)
+    pub fn get(&self, idx: u32) -> Result<&[u8], CollectionError> {
+        self.array.get(idx)
+    }
+
+    /// Returns whether `item` is in the sorted set
+    pub fn contains(&self, item: &[u8]) -> Result<bool, CollectionError> {
+        self.search(item).map(|res| res.is_ok())
+    }
+
+    /// Adds `item` to the sorted set (keeping it sorted). Returns `true` when `item` wasn't in the sorted set.
+    pub fn insert(&mut self, item: &[u8]) -> Result<bool, CollectionError> {
+        match self.search(item)? {
+            Ok(_) => Ok(false),
+            Err(idx) => self.array.insert(idx, item).map(|_| true),
+        }
+    }
+
+    /// Removes `item` from the sorted set. Returns `true` when `item` was in the sorted set.
+    pub fn remove(&mut self, item: &[u8]) -> Result<bool, CollectionError> {
+        match self.search(item)? {
+            Ok(idx) => self.array.remove(idx).map(|_| true),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Binary searches `item`. Returns `Ok(idx)` when `item` is ranked `idx`,
+    /// and `Err(idx)` when `item` isn't in the sorted set (`idx` is where it should be inserted).
+    fn search(&self, item: &[u8]) -> Result<Result<u32, u32>, CollectionError> {
+        if item.len() != self.array.item_size() {
+            return Err(CollectionError::InvalidItemSize(item.len()));
+        }
+
+        let mut low = 0;
+        let mut high = self.len();
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            match self.array.get(mid)?.cmp(item) {
+                std::cmp::Ordering::Equal => return Ok(Ok(mid)),
+                std::cmp::Ordering::Less => low = mid + 1,
+                std::cmp::Ordering::Greater => high = mid,
+            }
+        }
+
+        Ok(Err(low))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::zero_page;
+
+    #[test]
+    fn sorted_set_keeps_items_sorted() {
+        let mut page = zero_page();
+        let mut set = SortedSet::new(&mut page, 2).unwrap();
+
+        assert_eq!((4096 - 4) / 2, set.capacity());
+
+        assert_eq!(Ok(true), set.insert(&[0, 30]));
+        assert_eq!(Ok(true), set.insert(&[0, 10]));
+        assert_eq!(Ok(true), set.insert(&[1, 0]));
+        assert_eq!(Ok(true), set.insert(&[0, 20]));
+        assert_eq!(Ok(false), set.insert(&[0, 10]));
+        assert_eq!(4, set.len());
+
+        let items = (0..set.len())
+            .map(|i| set.get(i).unwrap().to_vec())
+            .collect::<Vec<Vec<u8>>>();
+        assert_eq!(
+            vec![vec![0, 10], vec![0, 20], vec![0, 30], vec![1, 0]],
+            items
+        );
+    }
+
+    #[test]
+    fn sorted_set_contains_and_remove() {
+        let mut page = zero_page();
+        let mut set = SortedSet::new(&mut page, 1).unwrap();
+
+        set.insert(&[20]).unwrap();
+        set.insert(&[10]).unwrap();
+
+        assert_eq!(Ok(true), set.contains(&[10]));
+        assert_eq!(Ok(false), set.contains(&[30]));
+
+        assert_eq!(Ok(true), set.remove(&[10]));
+        assert_eq!(Ok(false), set.remove(&[10]));
+        assert_eq!(Ok(&[20][..]), set.get(0));
+        assert_eq!(1, set.len());
+
+        assert_eq!(Ok(true), set.remove(&[20]));
+        assert!(set.is_empty());
+
+        // the page is back to being a zero page
+        assert_eq!(zero_page(), page);
+    }
+
+    #[test]
+    fn sorted_set_invalid_item_size() {
+        let mut page = zero_page();
+        let mut set = SortedSet::new(&mut page, 2).unwrap();
+
+        assert_eq!(Err(CollectionError::InvalidItemSize(1)), set.insert(&[1]));
+        assert_eq!(
+            Err(CollectionError::InvalidItemSize(3)),
+            set.contains(&[1, 2, 3])
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use super::{read_len, validated_capacity, write_len, CollectionError, HEADER_SIZE};
+
+/// A bounded string of bytes (e.g an `UTF-8` string), laid out over a page.
+/// The length stored under the page header is the number of bytes of the string.
+pub struct Str<'p> {
+    page: &'p mut [u8],
+    capacity: u32,
+}
+
+impl<'p> Str<'p> {
+    /// Views `page` as a `Str`.
+    /// An empty (zeros) page is an empty string.
+    /// Returns an error when the string length (stored in the page header) exceeds its capacity.
+    pub fn new(page: &'p mut [u8]) -> Result<Self, CollectionError> {
+        let capacity = validated_capacity(page, 1)?;
+
+        Ok(Self { page, capacity })
+    }
+
+    /// The maximum number of bytes the string can hold
+    #[inline(always)]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// The number of bytes of the string
+    #[inline(always)]
+    pub fn len(&self) -> u32 {
+        read_len(self.page)
+    }
+
+    /// Returns whether the string is empty
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the string bytes
+    pub fn get(&self) -> &[u8] {
+        let end = HEADER_SIZE + self.len() as usize;
+
+        &self.page[HEADER_SIZE..end]
+    }
+
+    /// Overrides the string with `bytes`
+    pub fn set(&mut self, bytes: &[u8]) -> Result<(), CollectionError> {
+        self.ensure_fits(bytes.len())?;
+
+        let len = self.len() as usize;
+        let end = HEADER_SIZE + bytes.len();
+
+        self.page[HEADER_SIZE..end].copy_from_slice(bytes);
+
+        // zeroing the truncated bytes keeps the page content canonical
+        for byte in self.page[end..HEADER_SIZE + len.max(bytes.len())].iter_mut() {
+            *byte = 0;
+        }
+
+        write_len(self.page, bytes.len() as u32);
+
+        Ok(())
+    }
+
+    /// Appends `bytes` to the end of the string
+    pub fn append(&mut self, bytes: &[u8]) -> Result<(), CollectionError> {
+        let len = self.len() as usize;
+        self.ensure_fits(len + bytes.len())?;
+
+        let start = HEADER_SIZE + len;
+        self.page[start..start + bytes.len()].copy_from_slice(bytes);
+
+        write_len(self.page, (len + bytes.len()) as u32);
+
+        Ok(())
+    }
+
+    fn ensure_fits(&self, len: usize) -> Result<(), CollectionError> {
+        if len <= self.capacity as usize {
+            Ok(())
+        } else {
+            Err(CollectionError::Full)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page::zero_page;
+
+    #[test]
+    fn str_set_append_get() {
+        let mut page = zero_page();
+        let mut s = Str::new(&mut page).unwrap();
+
+        assert!(s.is_empty());
+        assert_eq!(4096 - 4, s.capacity());
+
+        s.set(b"Hello").unwrap();
+        s.append(b", World").unwrap();
+        assert_eq!(b"Hello, World", s.get());
+        assert_eq!(12, s.len());
+
+        // setting a shorter string
+        s.set(b"Hi").unwrap();
+        assert_eq!(b"Hi", s.get());
+
+        s.set(b"").unwrap();
+        assert!(s.is_empty());
+
+        // the page is back to being a zero page
+        assert_eq!(zero_page(), page);
+    }
+
+    #[test]
+    fn str_full() {
+        let mut page = vec![0; 4 + 5];
+        let mut s = Str::new(&mut page).unwrap();
+
+        s.set(b"abc").unwrap();
+        assert_eq!(Err(CollectionError::Full), s.append(b"def"));
+        assert_eq!(Err(CollectionError::Full), s.set(b"abcdef"));
+        assert_eq!(b"abc", s.get());
+
+        s.append(b"de").unwrap();
+        assert_eq!(b"abcde", s.get());
+    }
+
+    #[test]
+    fn str_invalid_length() {
+        let mut page = vec![0; 4 + 5];
+        page[0..4].copy_from_slice(&6u32.to_be_bytes());
+
+        assert_eq!(
+            Err(CollectionError::InvalidLength(6)),
+            Str::new(&mut page).map(|s| s.len())
+        );
+    }
+}
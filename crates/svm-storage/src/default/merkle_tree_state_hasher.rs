@@ -0,0 +1,287 @@
+use crate::page::{PageHash, PageIndex};
+use crate::state::{PageProof, StateHash};
+use crate::traits::{ProvableStateHasher, StateHasher};
+use svm_common::{DefaultKeyHasher, KeyHasher, State};
+
+use std::marker::PhantomData;
+
+/// Prefixes the hashed page-hash of a leaf (so a leaf can't be passed as an inner node and vice versa)
+const LEAF_TAG: u8 = 0;
+
+/// Prefixes the hashed children of an inner node
+const NODE_TAG: u8 = 1;
+
+/// Prefixes the hashed pages count and tree root of a state
+const STATE_TAG: u8 = 2;
+
+pub struct MerkleTreeStateHasherImpl<KH> {
+    marker: PhantomData<KH>,
+}
+
+impl<KH> MerkleTreeStateHasherImpl<KH>
+where
+    KH: KeyHasher<Hash = [u8; 32]>,
+{
+    fn hash_leaf(page_hash: &PageHash) -> [u8; 32] {
+        let mut joined = Vec::with_capacity(33);
+        joined.push(LEAF_TAG);
+        joined.extend_from_slice(&page_hash.0);
+
+        KH::hash(&joined)
+    }
+
+    fn hash_node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        let mut joined = Vec::with_capacity(65);
+        joined.push(NODE_TAG);
+        joined.extend_from_slice(left);
+        joined.extend_from_slice(right);
+
+        KH::hash(&joined)
+    }
+
+    fn hash_state(pages_count: u32, root: &[u8; 32]) -> [u8; 32] {
+        let mut joined = Vec::with_capacity(37);
+        joined.push(STATE_TAG);
+        joined.extend_from_slice(&pages_count.to_be_bytes());
+        joined.extend_from_slice(root);
+
+        KH::hash(&joined)
+    }
+
+    fn leaves(pages_hash: &[PageHash]) -> Vec<[u8; 32]> {
+        pages_hash.iter().map(Self::hash_leaf).collect()
+    }
+
+    /// Computes the root of the tree over `leaves` (expected to be non-empty)
+    pub(crate) fn root(leaves: Vec<[u8; 32]>) -> [u8; 32] {
+        let mut level = leaves;
+
+        while level.len() > 1 {
+            level = Self::next_level(&level);
+        }
+
+        level[0]
+    }
+
+    /// Computes the next tree level. A node without a sibling is promoted as is.
+    fn next_level(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => Self::hash_node(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+}
+
+impl<KH> StateHasher for MerkleTreeStateHasherImpl<KH>
+where
+    KH: KeyHasher<Hash = [u8; 32]>,
+{
+    /// Given a slice of `PageHash`. `StateHash` commits to the pages count and to the root
+    /// of a binary Merkle tree whose leaves are the pages hash (ordered by page index):
+    ///
+    /// leaf  = HASH(0 || page_hash)
+    /// node  = HASH(1 || left_node || right_node)
+    /// state = HASH(2 || pages_count (4 bytes, Big-Endian) || root)
+    ///
+    /// A node without a right sibling is promoted to the next level as is.
+    /// The state of zero pages is `HASH([])`.
+    fn hash(pages_hash: &[PageHash]) -> StateHash {
+        if pages_hash.is_empty() {
+            return StateHash(KH::hash(&[]));
+        }
+
+        let root = Self::root(Self::leaves(pages_hash));
+
+        StateHash(Self::hash_state(pages_hash.len() as u32, &root))
+    }
+}
+
+impl<KH> ProvableStateHasher for MerkleTreeStateHasherImpl<KH>
+where
+    KH: KeyHasher<Hash = [u8; 32]>,
+{
+    fn prove(pages_hash: &[PageHash], page_idx: PageIndex) -> PageProof {
+        let mut idx = page_idx.0 as usize;
+        assert!(idx < pages_hash.len(), "page index out of range");
+
+        let mut level = Self::leaves(pages_hash);
+        let mut siblings = Vec::new();
+
+        while level.len() > 1 {
+            if let Some(sibling) = level.get(idx ^ 1) {
+                siblings.push(*sibling);
+            }
+
+            level = Self::next_level(&level);
+            idx /= 2;
+        }
+
+        PageProof {
+            page_idx,
+            pages_count: pages_hash.len() as u32,
+            page_hash: pages_hash[page_idx.0 as usize],
+            siblings,
+        }
+    }
+
+    fn verify(state: &State, proof: &PageProof) -> bool {
+        let mut idx = proof.page_idx.0 as usize;
+        let mut len = proof.pages_count as usize;
+
+        if idx >= len {
+            return false;
+        }
+
+        let mut node = Self::hash_leaf(&proof.page_hash);
+        let mut siblings = proof.siblings.iter();
+
+        while len > 1 {
+            if (idx ^ 1) < len {
+                let sibling = match siblings.next() {
+                    Some(sibling) => sibling,
+                    None => return false,
+                };
+
+                node = if idx & 1 == 0 {
+                    Self::hash_node(&node, sibling)
+                } else {
+                    Self::hash_node(sibling, &node)
+                };
+            }
+
+            idx /= 2;
+            len = len / 2 + len % 2;
+        }
+
+        let expected = Self::hash_state(proof.pages_count, &node);
+
+        siblings.next().is_none() && state.as_slice() == &expected[..]
+    }
+}
+
+/// A binary Merkle tree implementation for `StateHasher` trait (supporting pages proofs).
+pub type DefaultMerkleTreeStateHasher = MerkleTreeStateHasherImpl<DefaultKeyHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default::DefaultPageHasher;
+    use crate::traits::PageHasher;
+    use svm_common::Address;
+
+    fn pages_hash(count: u32) -> Vec<PageHash> {
+        let addr = Address::from(0xAABBCC);
+
+        (0..count)
+            .map(|i| DefaultPageHasher::hash(addr.clone(), PageIndex(i), &[i as u8; 3]))
+            .collect()
+    }
+
+    fn state(pages_hash: &[PageHash]) -> State {
+        let hash = DefaultMerkleTreeStateHasher::hash(pages_hash);
+        State::from(hash.as_ref())
+    }
+
+    #[test]
+    fn merkle_tree_state_hasher_sanity() {
+        let phs = pages_hash(3);
+        let leaves = DefaultMerkleTreeStateHasher::leaves(&phs);
+
+        let left = DefaultMerkleTreeStateHasher::hash_node(&leaves[0], &leaves[1]);
+        let root = DefaultMerkleTreeStateHasher::hash_node(&left, &leaves[2]);
+        let expected = DefaultMerkleTreeStateHasher::hash_state(3, &root);
+
+        assert_eq!(
+            StateHash(expected),
+            DefaultMerkleTreeStateHasher::hash(&phs)
+        );
+
+        let expected = DefaultMerkleTreeStateHasher::hash_state(1, &leaves[0]);
+
+        assert_eq!(
+            StateHash(expected),
+            DefaultMerkleTreeStateHasher::hash(&phs[..1])
+        );
+    }
+
+    #[test]
+    fn merkle_tree_leaves_and_nodes_are_domain_separated() {
+        let phs = pages_hash(2);
+        let leaves = DefaultMerkleTreeStateHasher::leaves(&phs);
+
+        // a single page whose page-hash is the inner node over two pages
+        let node = PageHash(DefaultMerkleTreeStateHasher::hash_node(
+            &leaves[0], &leaves[1],
+        ));
+
+        assert_ne!(
+            DefaultMerkleTreeStateHasher::hash(&phs),
+            DefaultMerkleTreeStateHasher::hash(&[node])
+        );
+    }
+
+    #[test]
+    fn merkle_tree_proofs_of_all_pages() {
+        for count in 1..=9 {
+            let phs = pages_hash(count);
+            let state = state(&phs);
+
+            for page_idx in 0..count {
+                let proof = DefaultMerkleTreeStateHasher::prove(&phs, PageIndex(page_idx));
+
+                assert_eq!(phs[page_idx as usize], proof.page_hash);
+                assert!(DefaultMerkleTreeStateHasher::verify(&state, &proof));
+            }
+        }
+    }
+
+    #[test]
+    fn merkle_tree_invalid_proofs() {
+        let phs = pages_hash(5);
+        let state = state(&phs);
+
+        let proof = DefaultMerkleTreeStateHasher::prove(&phs, PageIndex(2));
+
+        // another page-hash
+        let mut bad = proof.clone();
+        bad.page_hash = phs[3];
+        assert!(!DefaultMerkleTreeStateHasher::verify(&state, &bad));
+
+        // another page index
+        let mut bad = proof.clone();
+        bad.page_idx = PageIndex(3);
+        assert!(!DefaultMerkleTreeStateHasher::verify(&state, &bad));
+
+        // page index out of range
+        let mut bad = proof.clone();
+        bad.page_idx = PageIndex(5);
+        assert!(!DefaultMerkleTreeStateHasher::verify(&state, &bad));
+
+        // missing / extra siblings
+        let mut bad = proof.clone();
+        bad.siblings.pop();
+        assert!(!DefaultMerkleTreeStateHasher::verify(&state, &bad));
+
+        let mut bad = proof.clone();
+        bad.siblings.push([0; 32]);
+        assert!(!DefaultMerkleTreeStateHasher::verify(&state, &bad));
+
+        // another pages count (having the same tree shape along the proven path)
+        let phs = pages_hash(4);
+        let state = self::state(&phs);
+        let proof = DefaultMerkleTreeStateHasher::prove(&phs, PageIndex(0));
+        assert!(DefaultMerkleTreeStateHasher::verify(&state, &proof));
+
+        let mut bad = proof.clone();
+        bad.pages_count = 3;
+        assert!(!DefaultMerkleTreeStateHasher::verify(&state, &bad));
+
+        // another state
+        let other = State::from(0x10_20_30_40);
+        assert!(!DefaultMerkleTreeStateHasher::verify(&other, &proof));
+    }
+}
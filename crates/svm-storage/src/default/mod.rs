@@ -13,6 +13,10 @@ mod pages_storage;
 /// Default `crates::traits::StateHasher` implementation.
 mod state_hasher;
 
+/// Binary Merkle tree `crates::traits::StateHasher` implementation (supporting pages proofs).
+mod merkle_tree_state_hasher;
+
+pub use merkle_tree_state_hasher::DefaultMerkleTreeStateHasher;
 pub use page_cache::DefaultPageCache;
 pub use page_hasher::DefaultPageHasher;
 pub use page_index_hasher::DefaultPageIndexHasher;
@@ -1,6 +1,7 @@
 use crate::page::{PageHash, PageIndex};
 use crate::traits::{PageCache, PagesStateStorage, PagesStorage};
 use svm_common::State;
+use svm_kv::KVError;
 
 use log::{debug, trace};
 
@@ -30,7 +31,12 @@ pub struct DefaultPageCache<PS: PagesStateStorage> {
     pages_storage: PS,
 }
 
-impl<PS: PagesStateStorage> PageCache for DefaultPageCache<PS> {}
+impl<PS: PagesStateStorage> PageCache for DefaultPageCache<PS> {
+    #[inline(always)]
+    fn max_pages(&self) -> u32 {
+        self.cached_pages.len() as u32
+    }
+}
 
 impl<PS: PagesStateStorage> PagesStateStorage for DefaultPageCache<PS> {
     #[inline(always)]
@@ -43,6 +49,21 @@ impl<PS: PagesStateStorage> PagesStateStorage for DefaultPageCache<PS> {
     fn get_page_hash(&self, page_idx: PageIndex) -> PageHash {
         self.pages_storage.get_page_hash(page_idx)
     }
+
+    #[inline(always)]
+    fn pages_count(&self) -> u32 {
+        self.pages_storage.pages_count()
+    }
+
+    /// The storage can't grow beyond `max_pages`
+    fn grow(&mut self, pages_count: u32) {
+        assert!(
+            pages_count <= self.max_pages(),
+            "can't grow beyond `max_pages`"
+        );
+
+        self.pages_storage.grow(pages_count);
+    }
 }
 
 /// A `DefaultPageCache` is caching layer on top of a storage pages.
@@ -55,7 +76,8 @@ impl<PS: PagesStateStorage> DefaultPageCache<PS> {
     ///   doing a `pages_storage.commit()` should persist data to the underlying database.
     ///
     /// * `max_pages` - the maximum pages the `DefaultPageCache` instance could use when doing read / write.
-    ///   A page index is within the range `0..(max_pages - 1)` (inclusive)
+    ///   A page index is within the range `0..(max_pages - 1)` (inclusive).
+    ///   The underlying `pages_storage` may have less pages, and grows (up to `max_pages`) when written to.
     pub fn new(pages_storage: PS, max_pages: usize) -> Self {
         Self {
             dirty_pages: vec![false; max_pages],
@@ -72,11 +94,14 @@ impl<PS: PagesStateStorage> DefaultPageCache<PS> {
 
 impl<PS: PagesStateStorage> PagesStorage for DefaultPageCache<PS> {
     fn read_page(&mut self, page_idx: PageIndex) -> Option<Vec<u8>> {
-        // we can have an `assert` here since we are given the maximum storage-pages upon initialization
-        assert!(self.cached_pages.len() > page_idx.0 as usize);
-
         debug!("reading page #{}", page_idx.0);
 
+        if page_idx.0 >= self.max_pages() {
+            // a page beyond `max_pages` can't be written, so it has no content
+            debug!("page #{} is beyond `max_pages`", page_idx.0);
+            return None;
+        }
+
         let cache_status = &self.cached_pages[page_idx.0 as usize];
 
         match cache_status {
@@ -133,6 +158,12 @@ impl<PS: PagesStateStorage> PagesStorage for DefaultPageCache<PS> {
         debug!("writing page #{} (not persisting)", page_idx.0);
         trace!("page #{} content: {:?}", page_idx.0, page);
 
+        // we can have an `assert` here since we are given the maximum storage-pages upon initialization
+        assert!(page_idx.0 < self.max_pages(), "page index out of range");
+
+        // the pages count is tracked by `pages_storage`
+        self.pages_storage.grow(page_idx.0 + 1);
+
         std::mem::replace(
             &mut self.cached_pages[page_idx.0 as usize],
             CachedPage::Cached(page.to_vec()),
@@ -167,7 +198,7 @@ impl<PS: PagesStateStorage> PagesStorage for DefaultPageCache<PS> {
     /// * we call `pages_storage.commit` to flush the persist the changes
     ///
     /// since a smart contract is a short-lived program, we don't clear after `commit`
-    fn commit(&mut self) {
+    fn commit(&mut self) -> Result<(), KVError> {
         debug!("page-cache is about to commit dirty pages to underlying pages-storage");
 
         for ((page_idx, dirty), cached_page) in
@@ -192,7 +223,7 @@ impl<PS: PagesStateStorage> PagesStorage for DefaultPageCache<PS> {
             }
         }
 
-        self.pages_storage.commit();
+        self.pages_storage.commit()
     }
 }
 
@@ -255,7 +286,7 @@ mod tests {
         assert_eq!(vec![10, 20, 30], cache.read_page(PageIndex(0)).unwrap());
 
         let ph = default_page_idx_hash!(0x11_22_33_44, 0);
-        assert_eq!(None, kv.borrow().get(&ph));
+        assert_eq!(Ok(None), kv.borrow().get(&ph));
     }
 
     #[test]
@@ -282,11 +313,11 @@ mod tests {
 
         // `cache.write_page` doesn't persist the page yet
         let ph = default_page_idx_hash!(0x11_22_33_44, 0);
-        assert_eq!(None, kv.borrow().get(&ph));
+        assert_eq!(Ok(None), kv.borrow().get(&ph));
 
-        cache.commit();
+        cache.commit().unwrap();
 
         // `cache.commit` persists the page
-        assert_eq!(Some(vec![10, 20, 30]), kv.borrow().get(&ph));
+        assert_eq!(Ok(Some(vec![10, 20, 30])), kv.borrow().get(&ph));
     }
 }
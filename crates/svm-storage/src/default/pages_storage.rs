@@ -1,6 +1,7 @@
 use crate::page::PageIndex;
 use crate::traits::{PageIndexHasher, PagesStorage};
 use svm_kv::traits::KVStore;
+use svm_kv::KVError;
 
 use svm_common::Address;
 
@@ -34,6 +35,7 @@ pub struct DefaultPagesStorage<PIH: PageIndexHasher, KV: KVStore> {
     addr: Address,
     kv: Rc<RefCell<KV>>,
     uncommitted: HashMap<Vec<u8>, Vec<u8>>,
+    read_error: Option<KVError>,
     marker: PhantomData<PIH>,
 }
 
@@ -49,6 +51,7 @@ where
             addr,
             kv,
             uncommitted: HashMap::new(),
+            read_error: None,
             marker: PhantomData,
         }
     }
@@ -69,7 +72,13 @@ where
     fn read_page(&mut self, page_idx: PageIndex) -> Option<Vec<u8>> {
         let ph = self.compute_page_hash(page_idx);
 
-        self.kv.borrow().get(&ph)
+        match self.kv.borrow().get(&ph) {
+            Ok(page) => page,
+            Err(e) => {
+                self.read_error.get_or_insert(e);
+                None
+            }
+        }
     }
 
     /// Pushes a new pending change (persistence *only* upon `commit`)
@@ -84,16 +93,24 @@ where
         self.uncommitted.clear();
     }
 
-    /// Commits pending changes to the underlying key-value store
-    fn commit(&mut self) {
+    /// Commits pending changes to the underlying key-value store.
+    /// Fails without writing anything if a previous `read_page` has failed.
+    fn commit(&mut self) -> Result<(), KVError> {
+        if let Some(e) = self.read_error.take() {
+            self.clear();
+            return Err(e);
+        }
+
         let changes: Vec<(&[u8], &[u8])> = self
             .uncommitted
             .iter()
             .map(|(key, page)| (key.as_ref(), page.as_ref()))
             .collect();
 
-        self.kv.borrow_mut().store(changes.as_slice());
+        let res = self.kv.borrow_mut().store(changes.as_slice());
 
         self.clear();
+
+        res
     }
 }
@@ -0,0 +1,75 @@
+use crate::page::{PageIndex, SliceIndex};
+
+/// A contract storage write error
+#[derive(PartialEq, Clone)]
+pub enum StorageError {
+    /// The page index is beyond the maximum number of pages
+    PageIndexOutOfRange {
+        /// The written page index
+        page_idx: PageIndex,
+
+        /// The maximum number of pages
+        max_pages: u32,
+    },
+
+    /// The page-slice index is beyond the maximum number of page-slices
+    SliceIndexOutOfRange {
+        /// The written page-slice index
+        slice_idx: SliceIndex,
+
+        /// The maximum number of page-slices
+        max_slices: u32,
+    },
+
+    /// The page-slice exceeds the page boundary
+    SliceOutOfPage {
+        /// The page-slice offset (within the page)
+        offset: u32,
+
+        /// The page-slice length (in bytes)
+        len: u32,
+    },
+}
+
+impl std::error::Error for StorageError {
+    fn description(&self) -> &'static str {
+        match self {
+            StorageError::PageIndexOutOfRange { .. } => "Page index out of range",
+            StorageError::SliceIndexOutOfRange { .. } => "Page-slice index out of range",
+            StorageError::SliceOutOfPage { .. } => "Page-slice out of page",
+        }
+    }
+}
+
+impl std::fmt::Display for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            StorageError::PageIndexOutOfRange {
+                page_idx,
+                max_pages,
+            } => format!(
+                "Page index {} out of range (max pages: {})",
+                page_idx.0, max_pages
+            ),
+            StorageError::SliceIndexOutOfRange {
+                slice_idx,
+                max_slices,
+            } => format!(
+                "Page-slice index {} out of range (max page-slices: {})",
+                slice_idx.0, max_slices
+            ),
+            StorageError::SliceOutOfPage { offset, len } => format!(
+                "Page-slice (offset: {}, len: {}) exceeds the page size",
+                offset, len
+            ),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for StorageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
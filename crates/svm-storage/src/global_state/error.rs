@@ -0,0 +1,62 @@
+use svm_common::{Address, State};
+use svm_kv::KVError;
+
+/// A `GlobalState` error
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum GlobalStateError {
+    RootNotFound(State),
+    NodeNotFound(State),
+    CorruptedNode(State),
+    AccountExists(Address),
+    AccountNotFound(Address),
+    StoreFailed(KVError),
+}
+
+impl std::error::Error for GlobalStateError {
+    fn description(&self) -> &'static str {
+        match self {
+            GlobalStateError::RootNotFound(_) => "Global state root not found",
+            GlobalStateError::NodeNotFound(_) => "Global state node not found",
+            GlobalStateError::CorruptedNode(_) => "Global state node is corrupted",
+            GlobalStateError::AccountExists(_) => "Account already exists",
+            GlobalStateError::AccountNotFound(_) => "Account not found",
+            GlobalStateError::StoreFailed(_) => "Global state store failed",
+        }
+    }
+}
+
+impl std::fmt::Display for GlobalStateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            GlobalStateError::RootNotFound(root) => {
+                format!("Global state root not found: `{:?}`", root)
+            }
+            GlobalStateError::NodeNotFound(hash) => {
+                format!("Global state node not found: `{:?}`", hash)
+            }
+            GlobalStateError::CorruptedNode(hash) => {
+                format!("Global state node is corrupted: `{:?}`", hash)
+            }
+            GlobalStateError::AccountExists(addr) => {
+                format!("Account already exists: `{:?}`", addr)
+            }
+            GlobalStateError::AccountNotFound(addr) => format!("Account not found: `{:?}`", addr),
+            GlobalStateError::StoreFailed(e) => format!("Global state store failed: {}", e),
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for GlobalStateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
+
+impl From<KVError> for GlobalStateError {
+    fn from(e: KVError) -> Self {
+        GlobalStateError::StoreFailed(e)
+    }
+}
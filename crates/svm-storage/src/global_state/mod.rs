@@ -0,0 +1,520 @@
+//! The global state of all the contracts: `Address -> (State, CodeHash)`.
+//!
+//! The accounts are kept in a binary Merkle trie keyed by the account address bits
+//! (bit `0` is the most significant bit of the first address byte, `0` - left, `1` - right).
+//! Each account leaf sits at the shallowest depth where it's the only account of its subtrie,
+//! so the trie shape (and its root) depends only on the accounts and not on the order they were added.
+//!
+//! * leaf   = HASH(0 || address || state || code_hash)
+//! * branch = HASH(1 || left child hash || right child hash)
+//! * empty subtrie = `[0; 32]`
+//!
+//! Each node is persisted under its hash, and a `commit` persists only the nodes created since the
+//! previous commit. So every committed root (layer) remains readable, and an account can be proven
+//! against a root (see `GlobalState::prove / AccountProof::verify`).
+
+mod error;
+mod node;
+mod proof;
+
+pub use error::GlobalStateError;
+pub use proof::AccountProof;
+
+use node::{address_bit, Node, ADDRESS_BITS, EMPTY_HASH};
+
+use svm_common::{Address, CodeHash, State};
+use svm_kv::traits::KVStore;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use log::debug;
+
+/// The global state entry of a contract account
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountState {
+    /// The contract storage state
+    pub state: State,
+
+    /// The contract code-hash
+    pub code_hash: CodeHash,
+}
+
+/// `GlobalState` maintains the accounts trie of all the contracts and computes a single global root per layer.
+/// The key-value store should be dedicated to the global state (it must not be pruned by `prune::prune_pages`).
+pub struct GlobalState<KV: KVStore> {
+    kv: Rc<RefCell<KV>>,
+
+    /// The root of the last commit
+    root: State,
+
+    /// The root including the pending changes
+    pending_root: [u8; 32],
+
+    /// The nodes created since the last commit (by their hashes)
+    pending: HashMap<[u8; 32], Vec<u8>>,
+}
+
+impl<KV> GlobalState<KV>
+where
+    KV: KVStore,
+{
+    /// Opens the global state at `root` (`State::empty()` for no accounts).
+    /// Returns an error when `root` doesn't exist under `kv`.
+    pub fn new(kv: Rc<RefCell<KV>>, root: State) -> Result<Self, GlobalStateError> {
+        debug!("opening global state {:?}", root);
+
+        if root != State::empty() && kv.borrow().get(root.as_slice())?.is_none() {
+            return Err(GlobalStateError::RootNotFound(root));
+        }
+
+        let mut pending_root = [0; 32];
+        pending_root.copy_from_slice(root.as_slice());
+
+        Ok(Self {
+            kv,
+            root,
+            pending_root,
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Returns the global root of the last commit (not reflecting pending changes)
+    #[must_use]
+    pub fn get_root(&self) -> State {
+        self.root.clone()
+    }
+
+    /// Returns the global root including the pending changes
+    #[must_use]
+    pub fn compute_root(&self) -> State {
+        State::from(&self.pending_root[..])
+    }
+
+    /// Returns the account of contract `addr` (including pending changes)
+    pub fn get_account(&self, addr: &Address) -> Result<Option<AccountState>, GlobalStateError> {
+        let proof = self.prove(addr)?;
+
+        Ok(proof.account(addr).cloned())
+    }
+
+    /// Returns the Merkle proof of the account of contract `addr` (or of its absence)
+    /// against the global root including the pending changes (see `compute_root`)
+    pub fn prove(&self, addr: &Address) -> Result<AccountProof, GlobalStateError> {
+        let mut siblings = Vec::new();
+        let mut hash = self.pending_root;
+
+        loop {
+            if hash == EMPTY_HASH {
+                return Ok(AccountProof {
+                    leaf: None,
+                    siblings,
+                });
+            }
+
+            match self.load_node(&hash)? {
+                Node::Leaf(leaf_addr, account) => {
+                    return Ok(AccountProof {
+                        leaf: Some((leaf_addr, account)),
+                        siblings,
+                    });
+                }
+                Node::Branch(left, right) => {
+                    let depth = siblings.len();
+                    ensure_depth(depth, &hash)?;
+
+                    if address_bit(addr, depth) {
+                        siblings.push(left);
+                        hash = right;
+                    } else {
+                        siblings.push(right);
+                        hash = left;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adds a newly deployed contract account (with an empty state).
+    /// Returns an error if the account already exists.
+    pub fn add_account(
+        &mut self,
+        addr: Address,
+        code_hash: CodeHash,
+    ) -> Result<(), GlobalStateError> {
+        if self.get_account(&addr)?.is_some() {
+            return Err(GlobalStateError::AccountExists(addr));
+        }
+
+        let account = AccountState {
+            state: State::empty(),
+            code_hash,
+        };
+
+        self.put_account(addr, account)
+    }
+
+    /// Sets the new state of contract `addr` (for example, a receipt `new_state`).
+    /// Returns an error if the account doesn't exist.
+    pub fn set_state(&mut self, addr: &Address, state: State) -> Result<(), GlobalStateError> {
+        let mut account = self.existing_account(addr)?;
+        account.state = state;
+
+        self.put_account(addr.clone(), account)
+    }
+
+    /// Sets the new code-hash of contract `addr` (after the contract has been upgraded).
+    /// Returns an error if the account doesn't exist.
+    pub fn set_code_hash(
+        &mut self,
+        addr: &Address,
+        code_hash: CodeHash,
+    ) -> Result<(), GlobalStateError> {
+        let mut account = self.existing_account(addr)?;
+        account.code_hash = code_hash;
+
+        self.put_account(addr.clone(), account)
+    }
+
+    /// Persists the pending changes (a new layer), and returns the new global root.
+    /// Only the nodes created since the last commit (and reachable from the new root) are persisted.
+    /// On failure the root is unchanged (the pending changes are kept).
+    pub fn commit(&mut self) -> Result<State, GlobalStateError> {
+        let mut changes: Vec<(&[u8], &[u8])> = Vec::new();
+        let mut stack = vec![self.pending_root];
+
+        // the nodes which aren't pending are already persisted (along with their subtries)
+        while let Some(hash) = stack.pop() {
+            if let Some((hash, bytes)) = self.pending.get_key_value(&hash) {
+                if let Node::Branch(left, right) = Node::decode(hash, bytes)? {
+                    stack.push(left);
+                    stack.push(right);
+                }
+
+                changes.push((&hash[..], &bytes[..]));
+            }
+        }
+
+        if !changes.is_empty() {
+            self.kv.borrow_mut().store(&changes)?;
+        }
+
+        // the pending nodes overridden within the layer are dropped
+        self.pending.clear();
+        self.root = self.compute_root();
+
+        debug!("committed global state {:?}", self.root);
+
+        Ok(self.get_root())
+    }
+
+    fn existing_account(&self, addr: &Address) -> Result<AccountState, GlobalStateError> {
+        self.get_account(addr)?
+            .ok_or_else(|| GlobalStateError::AccountNotFound(addr.clone()))
+    }
+
+    fn put_account(
+        &mut self,
+        addr: Address,
+        account: AccountState,
+    ) -> Result<(), GlobalStateError> {
+        let root = self.pending_root;
+        self.pending_root = self.insert(root, 0, addr, account)?;
+
+        Ok(())
+    }
+
+    /// Inserts (or overrides) the account leaf of `addr` under the subtrie `hash` at depth `depth`.
+    /// Returns the new subtrie hash.
+    fn insert(
+        &mut self,
+        hash: [u8; 32],
+        depth: usize,
+        addr: Address,
+        account: AccountState,
+    ) -> Result<[u8; 32], GlobalStateError> {
+        if hash == EMPTY_HASH {
+            return Ok(self.put_node(Node::Leaf(addr, account)));
+        }
+
+        match self.load_node(&hash)? {
+            Node::Leaf(leaf_addr, _) if leaf_addr == addr => {
+                Ok(self.put_node(Node::Leaf(addr, account)))
+            }
+            Node::Leaf(leaf_addr, _) => {
+                let leaf_hash = self.put_node(Node::Leaf(addr.clone(), account));
+
+                self.split(depth, (hash, &leaf_addr), (leaf_hash, &addr))
+            }
+            Node::Branch(left, right) => {
+                ensure_depth(depth, &hash)?;
+
+                let branch = if address_bit(&addr, depth) {
+                    Node::Branch(left, self.insert(right, depth + 1, addr, account)?)
+                } else {
+                    Node::Branch(self.insert(left, depth + 1, addr, account)?, right)
+                };
+
+                Ok(self.put_node(branch))
+            }
+        }
+    }
+
+    /// Returns the hash of the subtrie (at depth `depth`) holding only leaves `a` and `b`
+    fn split(
+        &mut self,
+        depth: usize,
+        a: ([u8; 32], &Address),
+        b: ([u8; 32], &Address),
+    ) -> Result<[u8; 32], GlobalStateError> {
+        // two distinct addresses differ before the last bit, unless the trie is corrupted
+        ensure_depth(depth, &a.0)?;
+
+        let branch = match (address_bit(a.1, depth), address_bit(b.1, depth)) {
+            (false, true) => Node::Branch(a.0, b.0),
+            (true, false) => Node::Branch(b.0, a.0),
+            (false, false) => Node::Branch(self.split(depth + 1, a, b)?, EMPTY_HASH),
+            (true, true) => Node::Branch(EMPTY_HASH, self.split(depth + 1, a, b)?),
+        };
+
+        Ok(self.put_node(branch))
+    }
+
+    fn put_node(&mut self, node: Node) -> [u8; 32] {
+        let bytes = node.encode();
+        let hash = Node::hash(&bytes);
+
+        self.pending.insert(hash, bytes);
+
+        hash
+    }
+
+    fn load_node(&self, hash: &[u8; 32]) -> Result<Node, GlobalStateError> {
+        if let Some(bytes) = self.pending.get(hash) {
+            return Node::decode(hash, bytes);
+        }
+
+        match self.kv.borrow().get(hash)? {
+            Some(bytes) => Node::decode(hash, &bytes),
+            None => Err(GlobalStateError::NodeNotFound(State::from(&hash[..]))),
+        }
+    }
+}
+
+/// A branch can't be deeper than the address bits (otherwise, the trie is corrupted)
+fn ensure_depth(depth: usize, hash: &[u8; 32]) -> Result<(), GlobalStateError> {
+    if depth < ADDRESS_BITS {
+        Ok(())
+    } else {
+        Err(GlobalStateError::CorruptedNode(State::from(&hash[..])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use svm_kv::memory::MemKVStore;
+
+    macro_rules! global_state_open {
+        ($kv: expr, $root: expr) => {{
+            GlobalState::new(Rc::clone(&$kv), $root.clone()).unwrap()
+        }};
+    }
+
+    macro_rules! kv_len {
+        ($kv: expr) => {{
+            $kv.borrow().range(&[], None).unwrap().len()
+        }};
+    }
+
+    #[test]
+    fn global_state_empty_root() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut gs = global_state_open!(kv, State::empty());
+
+        assert_eq!(State::empty(), gs.compute_root());
+        assert_eq!(Ok(State::empty()), gs.commit());
+        assert_eq!(Ok(None), gs.get_account(&Address::from(0x10_20_30_40)));
+        assert_eq!(0, kv_len!(kv));
+    }
+
+    #[test]
+    fn global_state_missing_root() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let root = State::from(0x10_20_30_40);
+
+        assert_eq!(
+            Err(GlobalStateError::RootNotFound(root.clone())),
+            GlobalState::new(kv, root).map(|gs| gs.get_root())
+        );
+    }
+
+    #[test]
+    fn global_state_commit_and_reopen() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut gs = global_state_open!(kv, State::empty());
+
+        let addr1 = Address::from(0x10_20_30_40);
+        let addr2 = Address::from(0x50_60_70_80);
+
+        // layer #1: deploying two contracts
+        gs.add_account(addr1.clone(), CodeHash([1; 32])).unwrap();
+        gs.add_account(addr2.clone(), CodeHash([2; 32])).unwrap();
+        let root1 = gs.commit().unwrap();
+        assert_ne!(State::empty(), root1);
+
+        // layer #2: `addr1` executed a transaction
+        gs.set_state(&addr1, State::from(0x11_22_33_44)).unwrap();
+        assert_eq!(root1, gs.get_root());
+
+        let root2 = gs.commit().unwrap();
+        assert_ne!(root1, root2);
+
+        // committing without changes doesn't create a new root
+        assert_eq!(Ok(root2.clone()), gs.commit());
+
+        // reopening at both layers
+        let gs = global_state_open!(kv, root2);
+        assert_eq!(root2, gs.compute_root());
+        assert_eq!(
+            Ok(Some(AccountState {
+                state: State::from(0x11_22_33_44),
+                code_hash: CodeHash([1; 32])
+            })),
+            gs.get_account(&addr1)
+        );
+
+        let gs = global_state_open!(kv, root1);
+        assert_eq!(
+            State::empty(),
+            gs.get_account(&addr1).unwrap().unwrap().state
+        );
+        assert_eq!(
+            CodeHash([2; 32]),
+            gs.get_account(&addr2).unwrap().unwrap().code_hash
+        );
+    }
+
+    #[test]
+    fn global_state_commit_persists_only_changed_nodes() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut gs = global_state_open!(kv, State::empty());
+
+        for i in 0..16 {
+            let addr = Address::from(0x10_00_00_00 + i * 0x01_01_01);
+            gs.add_account(addr, CodeHash([i as u8; 32])).unwrap();
+        }
+
+        gs.commit().unwrap();
+        let before = kv_len!(kv);
+
+        // updating an account state twice within a layer
+        let addr = Address::from(0x10_00_00_00);
+        gs.set_state(&addr, State::from(0x11_22_33_44)).unwrap();
+        gs.set_state(&addr, State::from(0x55_66_77_88)).unwrap();
+        gs.commit().unwrap();
+
+        // only the new leaf and the branches along its path have been persisted
+        let path_len = gs.prove(&addr).unwrap().siblings.len();
+        assert_eq!(before + path_len + 1, kv_len!(kv));
+    }
+
+    #[test]
+    fn global_state_upgraded_account_keeps_state() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut gs = global_state_open!(kv, State::empty());
+
+        let addr = Address::from(0x10_20_30_40);
+        gs.add_account(addr.clone(), CodeHash([1; 32])).unwrap();
+        gs.set_state(&addr, State::from(0x11_22_33_44)).unwrap();
+        let root1 = gs.commit().unwrap();
+
+        gs.set_code_hash(&addr, CodeHash([2; 32])).unwrap();
+        let root2 = gs.commit().unwrap();
+        assert_ne!(root1, root2);
+
+        let account = gs.get_account(&addr).unwrap().unwrap();
+        assert_eq!(State::from(0x11_22_33_44), account.state);
+        assert_eq!(CodeHash([2; 32]), account.code_hash);
+    }
+
+    #[test]
+    fn global_state_root_is_order_independent() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+
+        let addrs = [
+            Address::from(0x10_20_30_40),
+            Address::from(0x50_60_70_80),
+            Address::from(0x10_20_30_41),
+        ];
+
+        let mut gs1 = global_state_open!(kv, State::empty());
+        for addr in addrs.iter() {
+            gs1.add_account(addr.clone(), CodeHash([1; 32])).unwrap();
+        }
+
+        let mut gs2 = global_state_open!(kv, State::empty());
+        for addr in addrs.iter().rev() {
+            gs2.add_account(addr.clone(), CodeHash([1; 32])).unwrap();
+        }
+
+        assert_eq!(gs1.compute_root(), gs2.compute_root());
+    }
+
+    #[test]
+    fn global_state_account_proofs() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut gs = global_state_open!(kv, State::empty());
+
+        let addr1 = Address::from(0x10_20_30_40);
+        let addr2 = Address::from(0x10_20_30_41);
+        let missing = Address::from(0x50_60_70_80);
+
+        gs.add_account(addr1.clone(), CodeHash([1; 32])).unwrap();
+        gs.add_account(addr2.clone(), CodeHash([2; 32])).unwrap();
+        let root = gs.commit().unwrap();
+
+        // membership
+        let proof = gs.prove(&addr2).unwrap();
+        assert!(proof.verify(&root, &addr2));
+        assert_eq!(CodeHash([2; 32]), proof.account(&addr2).unwrap().code_hash);
+
+        // a proof doesn't verify against another root
+        assert!(!proof.verify(&State::from(0x11_22_33_44), &addr2));
+
+        // a forged account doesn't verify
+        let mut forged = proof.clone();
+        forged.leaf.as_mut().unwrap().1.state = State::from(0x11_22_33_44);
+        assert!(!forged.verify(&root, &addr2));
+
+        // absence
+        let proof = gs.prove(&missing).unwrap();
+        assert!(proof.verify(&root, &missing));
+        assert_eq!(None, proof.account(&missing));
+    }
+
+    #[test]
+    fn global_state_unknown_accounts_errors() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut gs = global_state_open!(kv, State::empty());
+
+        let addr = Address::from(0x10_20_30_40);
+
+        assert_eq!(
+            Err(GlobalStateError::AccountNotFound(addr.clone())),
+            gs.set_state(&addr, State::from(0x11_22_33_44))
+        );
+        assert_eq!(
+            Err(GlobalStateError::AccountNotFound(addr.clone())),
+            gs.set_code_hash(&addr, CodeHash([1; 32]))
+        );
+
+        gs.add_account(addr.clone(), CodeHash([1; 32])).unwrap();
+        assert_eq!(
+            Err(GlobalStateError::AccountExists(addr.clone())),
+            gs.add_account(addr, CodeHash([2; 32]))
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use super::{AccountState, GlobalStateError};
+
+use svm_common::{Address, CodeHash, DefaultKeyHasher, KeyHasher, State};
+
+/// The hash of an empty (sub)trie. The root of zero accounts is `State::empty()`.
+pub(crate) const EMPTY_HASH: [u8; 32] = [0; 32];
+
+/// The number of bits of an account address (the maximum depth of the trie)
+pub(crate) const ADDRESS_BITS: usize = 20 * 8;
+
+const LEAF_TAG: u8 = 0;
+const BRANCH_TAG: u8 = 1;
+
+/// `tag (1 byte) || address (20 bytes) || state (32 bytes) || code-hash (32 bytes)`
+const LEAF_LEN: usize = 1 + 20 + 32 + 32;
+
+/// `tag (1 byte) || left child hash (32 bytes) || right child hash (32 bytes)`
+const BRANCH_LEN: usize = 1 + 32 + 32;
+
+/// A node of the accounts trie.
+/// A node is persisted under its hash: `HASH(encoded node)`. The leading tag separates the leaves hashes
+/// from the branches hashes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    /// An account
+    Leaf(Address, AccountState),
+
+    /// The hashes of the `0` bit (left) child and of the `1` bit (right) child
+    Branch([u8; 32], [u8; 32]),
+}
+
+impl Node {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        match self {
+            Node::Leaf(addr, account) => {
+                let mut buf = Vec::with_capacity(LEAF_LEN);
+
+                buf.push(LEAF_TAG);
+                buf.extend_from_slice(addr.as_slice());
+                buf.extend_from_slice(account.state.as_slice());
+                buf.extend_from_slice(&account.code_hash.0);
+
+                buf
+            }
+            Node::Branch(left, right) => {
+                let mut buf = Vec::with_capacity(BRANCH_LEN);
+
+                buf.push(BRANCH_TAG);
+                buf.extend_from_slice(left);
+                buf.extend_from_slice(right);
+
+                buf
+            }
+        }
+    }
+
+    /// Decodes the node persisted under `hash`
+    pub(crate) fn decode(hash: &[u8; 32], bytes: &[u8]) -> Result<Self, GlobalStateError> {
+        match (bytes.first(), bytes.len()) {
+            (Some(&LEAF_TAG), LEAF_LEN) => {
+                let addr = Address::from(&bytes[1..21]);
+                let state = State::from(&bytes[21..53]);
+
+                let mut code_hash = [0; 32];
+                code_hash.copy_from_slice(&bytes[53..85]);
+
+                let account = AccountState {
+                    state,
+                    code_hash: CodeHash(code_hash),
+                };
+
+                Ok(Node::Leaf(addr, account))
+            }
+            (Some(&BRANCH_TAG), BRANCH_LEN) => {
+                let mut left = [0; 32];
+                let mut right = [0; 32];
+
+                left.copy_from_slice(&bytes[1..33]);
+                right.copy_from_slice(&bytes[33..65]);
+
+                Ok(Node::Branch(left, right))
+            }
+            _ => Err(GlobalStateError::CorruptedNode(State::from(&hash[..]))),
+        }
+    }
+
+    pub(crate) fn hash(encoded: &[u8]) -> [u8; 32] {
+        DefaultKeyHasher::hash(encoded)
+    }
+}
+
+/// Returns bit `depth` of `addr` (bit `0` is the most significant bit of the first byte).
+/// The account leaf is reached by following the address bits (`0` - left, `1` - right).
+pub(crate) fn address_bit(addr: &Address, depth: usize) -> bool {
+    let byte = addr.as_slice()[depth / 8];
+
+    byte & (0x80 >> (depth % 8)) != 0
+}
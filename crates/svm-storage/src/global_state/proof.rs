@@ -0,0 +1,62 @@
+use super::node::{address_bit, Node, ADDRESS_BITS, EMPTY_HASH};
+use super::AccountState;
+
+use svm_common::{Address, State};
+
+/// A Merkle proof of an account (or of its absence) against a global root (see `GlobalState::prove`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountProof {
+    /// The leaf reached by following the proven address bits from the root.
+    /// It's the proven account, another account (sharing the path) or `None` (an empty subtrie).
+    /// The last two prove the absence of the proven account.
+    pub leaf: Option<(Address, AccountState)>,
+
+    /// The hashes of the siblings along the path from the root to the leaf (the root's child sibling first)
+    pub siblings: Vec<[u8; 32]>,
+}
+
+impl AccountProof {
+    /// Returns the proven account of `addr` (`None` for a proof of absence)
+    pub fn account(&self, addr: &Address) -> Option<&AccountState> {
+        match self.leaf {
+            Some((ref leaf_addr, ref account)) if leaf_addr == addr => Some(account),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the proof of `addr` (an account or an absence proof) is valid against global root `root`
+    pub fn verify(&self, root: &State, addr: &Address) -> bool {
+        let depth = self.siblings.len();
+
+        if depth > ADDRESS_BITS {
+            return false;
+        }
+
+        let mut hash = match self.leaf {
+            None => EMPTY_HASH,
+            Some((ref leaf_addr, ref account)) => {
+                // the leaf must be on the path of `addr`
+                let on_path = (0..depth).all(|d| address_bit(leaf_addr, d) == address_bit(addr, d));
+
+                if !on_path {
+                    return false;
+                }
+
+                let leaf = Node::Leaf(leaf_addr.clone(), account.clone());
+                Node::hash(&leaf.encode())
+            }
+        };
+
+        for (d, sibling) in self.siblings.iter().enumerate().rev() {
+            let branch = if address_bit(addr, d) {
+                Node::Branch(*sibling, hash)
+            } else {
+                Node::Branch(hash, *sibling)
+            };
+
+            hash = Node::hash(&branch.encode());
+        }
+
+        hash[..] == *root.as_slice()
+    }
+}
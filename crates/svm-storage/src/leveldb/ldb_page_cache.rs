@@ -2,4 +2,54 @@ use crate::default::DefaultPageCache;
 use crate::leveldb::LDBPages;
 
 /// `DefaultPageCache` implementation backed by `LDBPages` pages-storage.
-pub type LDBMerklePageCache<'pc> = DefaultPageCache<'pc, LDBPages>;
+pub type LDBMerklePageCache = DefaultPageCache<LDBPages>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::page::PageIndex;
+    use crate::traits::{PageCache, PagesStateStorage, PagesStorage};
+
+    use svm_common::{Address, State};
+    use svm_kv::leveldb::LDBStore;
+    use svm_kv::traits::KVStore;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn ldb_page_cache_write_page_and_then_commit() {
+        let path = std::env::temp_dir().join("ldb-page-cache-commit-tests");
+        let _ = std::fs::remove_dir_all(&path);
+
+        let kv = Rc::new(RefCell::new(LDBStore::new(&path).unwrap()));
+        let addr = Address::from(0x11_22_33_44);
+        let pages = LDBPages::new(addr.clone(), Rc::clone(&kv), State::empty(), 2);
+        let mut cache = LDBMerklePageCache::new(pages, 10);
+
+        assert_eq!(10, cache.max_pages());
+        assert_eq!(None, cache.read_page(PageIndex(0)));
+
+        cache.write_page(PageIndex(0), &[10, 20, 30]);
+        assert_eq!(Some(vec![10, 20, 30]), cache.read_page(PageIndex(0)));
+
+        // `cache.write_page` doesn't persist the page yet
+        let ph = default_page_hash!(0x11_22_33_44, 0, &[10, 20, 30]);
+        assert_eq!(Ok(None), kv.borrow().get(&ph.0));
+
+        // `cache.commit` persists the page
+        cache.commit().unwrap();
+        assert_eq!(Ok(Some(vec![10, 20, 30])), kv.borrow().get(&ph.0));
+
+        let state = cache.get_state();
+        assert_ne!(State::empty(), state);
+
+        // a new cache over the committed state
+        let pages = LDBPages::new(addr, Rc::clone(&kv), state, 2);
+        let mut cache = LDBMerklePageCache::new(pages, 10);
+        assert_eq!(Some(vec![10, 20, 30]), cache.read_page(PageIndex(0)));
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
@@ -5,3 +5,115 @@ use svm_kv::leveldb::LDBStore;
 
 /// A `MerklePagesStorage` implementation backed by `LDBStore` kv-store.
 pub type LDBPages = MerklePagesStorage<LDBStore, DefaultPageHasher, DefaultStateHasher>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::page::{zero_page, PageIndex};
+    use crate::traits::{PagesStateStorage, PagesStorage};
+
+    use svm_common::{Address, State};
+    use svm_kv::traits::KVStore;
+
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+    use std::rc::Rc;
+
+    fn ldb_open(path: &PathBuf) -> Rc<RefCell<LDBStore>> {
+        Rc::new(RefCell::new(LDBStore::new(path).unwrap()))
+    }
+
+    fn ldb_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        let _ = std::fs::remove_dir_all(&path);
+
+        path
+    }
+
+    #[test]
+    fn ldb_pages_first_run_with_no_modifications() {
+        let path = ldb_path("ldb-pages-no-modifications-tests");
+        let kv = ldb_open(&path);
+        let addr = Address::from(0x11_22_33_44);
+
+        let mut storage = LDBPages::new(addr, Rc::clone(&kv), State::empty(), 3);
+
+        assert_eq!(State::empty(), storage.get_state());
+        assert_eq!(3, storage.pages_count());
+        assert_eq!(None, storage.read_page(PageIndex(0)));
+
+        storage.commit().unwrap();
+        assert_ne!(State::empty(), storage.get_state());
+
+        // the zero pages aren't persisted, only the state entry is
+        let ph0 = default_page_hash!(0x11_22_33_44, 0, &zero_page());
+        assert_eq!(Ok(None), kv.borrow().get(&ph0.0));
+        assert!(kv
+            .borrow()
+            .get(storage.get_state().as_slice())
+            .unwrap()
+            .is_some());
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn ldb_pages_write_commit_and_reopen() {
+        let path = ldb_path("ldb-pages-reopen-tests");
+        let addr = Address::from(0x11_22_33_44);
+
+        // 1st run
+        let kv = ldb_open(&path);
+        let mut storage = LDBPages::new(addr.clone(), Rc::clone(&kv), State::empty(), 2);
+
+        storage.write_page(PageIndex(0), &[10, 20, 30]);
+        storage.write_page(PageIndex(1), &[40, 50, 60]);
+        storage.commit().unwrap();
+
+        let state = storage.get_state();
+        let ph0 = default_page_hash!(0x11_22_33_44, 0, &[10, 20, 30]);
+        assert_eq!(ph0, storage.get_page_hash(PageIndex(0)));
+        assert_eq!(Ok(Some(vec![10, 20, 30])), kv.borrow().get(&ph0.0));
+
+        // the database is closed once all its handles are dropped
+        drop(storage);
+        drop(kv);
+
+        // 2nd run (reopening the database). the pages count is recorded by the state entry
+        let kv = ldb_open(&path);
+        let mut storage = LDBPages::new(addr, Rc::clone(&kv), state.clone(), 5);
+
+        assert_eq!(state, storage.get_state());
+        assert_eq!(2, storage.pages_count());
+        assert_eq!(Some(vec![10, 20, 30]), storage.read_page(PageIndex(0)));
+        assert_eq!(Some(vec![40, 50, 60]), storage.read_page(PageIndex(1)));
+        assert_eq!(None, storage.read_page(PageIndex(2)));
+
+        // writing beyond the pages count grows the storage
+        storage.write_page(PageIndex(3), &[70, 80, 90]);
+        storage.commit().unwrap();
+
+        assert_eq!(4, storage.pages_count());
+        assert_ne!(state, storage.get_state());
+        assert_eq!(Some(vec![70, 80, 90]), storage.read_page(PageIndex(3)));
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn ldb_pages_missing_state_fails_commit() {
+        let path = ldb_path("ldb-pages-missing-state-tests");
+        let kv = ldb_open(&path);
+        let addr = Address::from(0x11_22_33_44);
+        let state = State::from(0x10_20_30_40);
+
+        let mut storage = LDBPages::new(addr, Rc::clone(&kv), state.clone(), 3);
+        storage.write_page(PageIndex(0), &[10, 20, 30]);
+
+        assert!(storage.commit().is_err());
+        assert_eq!(state, storage.get_state());
+
+        let _ = std::fs::remove_dir_all(&path);
+    }
+}
@@ -7,6 +7,7 @@
 /// Default implementations for crate traits (see `traits.rs`).
 pub mod default;
 
+mod error;
 mod merkle_pages_storage;
 
 mod page_slice_cache;
@@ -17,8 +18,18 @@ pub mod page;
 /// Contains definitions `State`-related.
 pub mod state;
 
+/// The global state (accounts trie) of all the contracts
+pub mod global_state;
+
+/// Garbage collection of page versions unreachable from the live states
+pub mod prune;
+
+pub use crate::error::StorageError;
 pub use crate::page_slice_cache::PageSliceCache;
 
+/// Storage collections (`Array`, `Hash`, `Set`) laid out over contract storage pages
+pub mod collections;
+
 /// Storage related traits
 #[macro_use]
 pub mod traits;
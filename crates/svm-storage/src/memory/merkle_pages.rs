@@ -1,7 +1,12 @@
-use crate::default::{DefaultPageHasher, DefaultStateHasher};
+use crate::default::{DefaultMerkleTreeStateHasher, DefaultPageHasher, DefaultStateHasher};
 use crate::merkle_pages_storage::MerklePagesStorage;
 
 use svm_kv::memory::MemKVStore;
 
 /// A `MerklePagesStorage` implementation backed by `MemKVStore` kv-store.
 pub type MemMerklePages = MerklePagesStorage<MemKVStore, DefaultPageHasher, DefaultStateHasher>;
+
+/// A `MerklePagesStorage` implementation backed by `MemKVStore` kv-store.
+/// Its state is a binary Merkle tree root (supporting pages proofs).
+pub type MemMerkleTreePages =
+    MerklePagesStorage<MemKVStore, DefaultPageHasher, DefaultMerkleTreeStateHasher>;
@@ -2,4 +2,4 @@ mod merkle_page_cache;
 mod merkle_pages;
 
 pub use merkle_page_cache::MemMerklePageCache;
-pub use merkle_pages::MemMerklePages;
+pub use merkle_pages::{MemMerklePages, MemMerkleTreePages};
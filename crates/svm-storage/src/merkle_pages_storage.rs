@@ -1,8 +1,12 @@
 use crate::page::{PageHash, PageIndex};
-use crate::traits::{PageHasher, PagesStateStorage, PagesStorage, StateHasher};
+use crate::state::PageProof;
+use crate::traits::{
+    PageHasher, PagesStateStorage, PagesStorage, ProvableStateHasher, StateHasher,
+};
 
 use svm_common::{Address, State};
 use svm_kv::traits::KVStore;
+use svm_kv::KVError;
 
 use std::cell::RefCell;
 use std::marker::PhantomData;
@@ -12,7 +16,6 @@ use log::{debug, error, trace};
 
 #[derive(Debug, Clone)]
 enum MerklePage {
-    Uninitialized,
     NotModified(PageHash),
     Modified(PageHash, Vec<u8>),
 }
@@ -31,7 +34,7 @@ where
     addr: Address,
     pages: Vec<MerklePage>,
     kv: Rc<RefCell<KV>>,
-    pages_count: u32,
+    read_error: Option<KVError>,
     marker: PhantomData<(PH, SH)>,
 }
 
@@ -45,18 +48,20 @@ where
     /// * `addr`        - The running contract account address.
     /// * `kv`          - The underlying kv-store used for retrieving a page raw-data when queried by its page-hash serving as a key.
     /// * `state`       - The current contract-storage state prior execution of the current contract transaction.
-    /// * `pages_count` - The number of pages consumed by the contract storage (it's a fixed value per-contract).
+    /// * `pages_count` - The initial number of pages of the contract storage. Used only when `state` is empty
+    ///                   (i.e the contract runs for the first time). Otherwise, the pages count is recorded by the state entry.
+    ///                   The storage grows on demand (see `grow` and `write_page`).
     pub fn new(addr: Address, kv: Rc<RefCell<KV>>, state: State, pages_count: u32) -> Self {
         let mut storage = Self {
             state,
             kv,
-            pages_count,
             addr,
-            pages: vec![MerklePage::Uninitialized; pages_count as usize],
+            pages: Vec::new(),
+            read_error: None,
             marker: PhantomData,
         };
 
-        storage.init_pages_state();
+        storage.init_pages_state(pages_count);
 
         storage
     }
@@ -65,31 +70,55 @@ where
     /// state ---> [page1_hash || page2_hash || .... || pageN_hash]
     ///
     /// Then, populates `self.pages`. Each page is initialized with `MerklePage::NotModified(page_hash)`
-    fn init_pages_state(&mut self) {
+    ///
+    /// When the state entry is missing (or corrupted) the storage starts with `pages_count` zero pages,
+    /// and the error is reported by `commit` (as for a failed page read).
+    fn init_pages_state(&mut self, pages_count: u32) {
         debug!("initializating pages-storage with state {:?}", self.state);
 
         if self.state == State::empty() {
             // `self.state` is `000...0`. It means that state doesn't exist under the key-value store.
             // This happens when a Smart Contract runs for the first time.
-            // We initialize each page with its zero-page hash `HASH(addr || page_idx || 0...0)`
+            self.grow(pages_count);
+            return;
+        }
 
-            for page_idx in 0..(self.pages_count as usize) {
-                let ph = self.compute_zero_page_hash(PageIndex(page_idx as u32));
-                self.pages[page_idx] = MerklePage::NotModified(ph);
-            }
-        } else if let Some(v) = self.kv.borrow().get(self.state.as_slice()) {
+        let res = match self.kv.borrow().get(self.state.as_slice()) {
             // `v` should be a concatenation of pages-hash. Each page hash consumes exactly 32 bytes.
-            assert!(v.len() % 32 == 0);
+            Ok(Some(v)) if v.len() % 32 == 0 => Ok(v),
+            Ok(Some(v)) => Err(KVError::ReadFailed(format!(
+                "state {:?} entry is corrupted (`{}` bytes aren't a concatenation of pages-hash)",
+                self.state.as_slice(),
+                v.len()
+            ))),
+            Ok(None) => Err(KVError::ReadFailed(format!(
+                "state {:?} not found",
+                self.state.as_slice()
+            ))),
+            Err(e) => Err(e),
+        };
 
-            for (page_idx, raw_ph) in v.chunks_exact(32).enumerate() {
-                let ph = PageHash::from(raw_ph);
-                self.pages[page_idx] = MerklePage::NotModified(ph);
+        match res {
+            Ok(v) => {
+                for (page_idx, raw_ph) in v.chunks_exact(32).enumerate() {
+                    let ph = PageHash::from(raw_ph);
+                    self.pages.push(MerklePage::NotModified(ph));
 
-                trace!("page #{}, has page-hash {:?}", page_idx, ph);
+                    trace!("page #{}, has page-hash {:?}", page_idx, ph);
+                }
+            }
+            Err(e) => {
+                // the state can't be loaded, so no new state can be committed on top of it.
+                // the error is reported by `commit`
+                error!(
+                    "failed loading state: {:?} ({:?})",
+                    self.state.as_slice(),
+                    e
+                );
+
+                self.grow(pages_count);
+                self.read_error = Some(e);
             }
-        } else {
-            error!("Didn't find state: {:?}", self.state.as_slice());
-            panic!("Didn't find state: {:?}", self.state.as_slice());
         }
     }
 
@@ -111,7 +140,6 @@ where
         self.pages.iter().fold(0, |acc, page| match page {
             MerklePage::NotModified(..) => acc,
             MerklePage::Modified(..) => acc + 1,
-            MerklePage::Uninitialized => unreachable!(),
         })
     }
 
@@ -129,7 +157,6 @@ where
 
                     pages_hash.push(*ph);
                 }
-                MerklePage::Uninitialized => unreachable!(),
             }
         }
 
@@ -140,6 +167,29 @@ where
     }
 }
 
+impl<KV, PH, SH> MerklePagesStorage<KV, PH, SH>
+where
+    KV: KVStore,
+    PH: PageHasher,
+    SH: ProvableStateHasher,
+{
+    /// Returns an inclusion proof of page `page_idx` under the current state (see `get_state`).
+    /// Panics when there are uncommitted pages (the current state doesn't reflect them).
+    #[must_use]
+    pub fn prove_page(&self, page_idx: PageIndex) -> PageProof {
+        let pages_hash: Vec<PageHash> = self
+            .pages
+            .iter()
+            .map(|page| match page {
+                MerklePage::NotModified(ph) => *ph,
+                MerklePage::Modified(..) => panic!("Not allowed to prove a dirty pages-storage"),
+            })
+            .collect();
+
+        SH::prove(&pages_hash, page_idx)
+    }
+}
+
 impl<KV, PH, SH> PagesStateStorage for MerklePagesStorage<KV, PH, SH>
 where
     KV: KVStore,
@@ -152,12 +202,35 @@ where
         self.state.clone()
     }
 
+    /// A page beyond the storage pages count has the zero-page hash
     #[must_use]
     fn get_page_hash(&self, page_idx: PageIndex) -> PageHash {
-        match self.pages[page_idx.0 as usize] {
-            MerklePage::NotModified(ph) => ph,
-            MerklePage::Modified(ph, _) => ph,
-            MerklePage::Uninitialized => unreachable!(),
+        match self.pages.get(page_idx.0 as usize) {
+            Some(MerklePage::NotModified(ph)) => *ph,
+            Some(MerklePage::Modified(ph, _)) => *ph,
+            None => self.compute_zero_page_hash(page_idx),
+        }
+    }
+
+    #[inline(always)]
+    fn pages_count(&self) -> u32 {
+        self.pages.len() as u32
+    }
+
+    /// Appends pages initialized with their zero-page hash `HASH(addr || page_idx || 0...0)`
+    fn grow(&mut self, pages_count: u32) {
+        let current = self.pages_count();
+
+        if pages_count > current {
+            debug!(
+                "growing pages-storage from {} to {} pages",
+                current, pages_count
+            );
+        }
+
+        for page_idx in current..pages_count {
+            let ph = self.compute_zero_page_hash(PageIndex(page_idx));
+            self.pages.push(MerklePage::NotModified(ph));
         }
     }
 }
@@ -170,14 +243,26 @@ where
 {
     #[must_use]
     fn read_page(&mut self, page_idx: PageIndex) -> Option<Vec<u8>> {
-        match self.pages[page_idx.0 as usize] {
-            MerklePage::NotModified(ph) => self.kv.borrow().get(&ph.0),
-            MerklePage::Modified(..) => panic!("Not allowed to read a dirty page"),
-            MerklePage::Uninitialized => unreachable!(),
+        match self.pages.get(page_idx.0 as usize) {
+            // the page is beyond the storage pages count, so it has no content
+            None => None,
+            Some(MerklePage::NotModified(ph)) => match self.kv.borrow().get(&ph.0) {
+                Ok(page) => page,
+                Err(e) => {
+                    error!("failed reading page #{} ({:?})", page_idx.0, e);
+
+                    self.read_error.get_or_insert(e);
+                    None
+                }
+            },
+            Some(MerklePage::Modified(..)) => panic!("Not allowed to read a dirty page"),
         }
     }
 
+    /// Writing a page beyond the storage pages count grows the storage
     fn write_page(&mut self, page_idx: PageIndex, page_data: &[u8]) {
+        self.grow(page_idx.0 + 1);
+
         let ph = self.compute_page_hash(page_idx, page_data);
 
         self.pages[page_idx.0 as usize] = MerklePage::Modified(ph, page_data.to_vec());
@@ -190,12 +275,17 @@ where
             match page {
                 MerklePage::Modified(ph, ..) => *page = MerklePage::NotModified(*ph),
                 MerklePage::NotModified(..) => (),
-                MerklePage::Uninitialized => unreachable!(),
             }
         }
     }
 
-    fn commit(&mut self) {
+    fn commit(&mut self) -> Result<(), KVError> {
+        if let Some(e) = self.read_error.take() {
+            // a page (or the state) couldn't be read, so the computed new state can't be trusted
+            self.clear();
+            return Err(e);
+        }
+
         // We have each page-hash (dirty and non-dirty) under `self.pages`
         // Now, we'll compute the new state (merkle proof) of the Smart Contract.
         //
@@ -226,10 +316,15 @@ where
         // pageN_hash ---> pageN_content
         // ```
 
-        self.kv.borrow_mut().store(entries.as_slice());
-        self.state = new_state;
+        let res = self.kv.borrow_mut().store(entries.as_slice());
+
+        if res.is_ok() {
+            self.state = new_state;
+        }
 
         self.clear();
+
+        res
     }
 }
 
@@ -292,14 +387,14 @@ mod tests {
 
     macro_rules! assert_key_value {
         ($kv: expr, $key: expr, $expected: expr) => {{
-            let actual = $kv.borrow().get(&$key).unwrap();
+            let actual = $kv.borrow().get(&$key).unwrap().unwrap();
             assert_eq!($expected, &actual[..]);
         }};
     }
 
     macro_rules! assert_no_key {
         ($kv: expr, $key: expr) => {{
-            assert!($kv.borrow().get(&$key).is_none());
+            assert!($kv.borrow().get(&$key).unwrap().is_none());
         }};
     }
 
@@ -352,7 +447,7 @@ mod tests {
 
     macro_rules! compute_page_hash {
         ($addr: ident, $page_idx: expr, $data: expr) => {{
-            use $crate::default::DefaultPageHasher;;
+            use $crate::default::DefaultPageHasher;
             DefaultPageHasher::hash($addr.clone(), PageIndex($page_idx), $data)
         }};
     }
@@ -386,7 +481,7 @@ mod tests {
     fn first_run_with_no_modifications_with_commit() {
         mem_merkle_pages_setup!(0x11_22_33_44, addr, storage, kv, 3);
         assert_dirty_pages_count!(storage, 0);
-        storage.commit();
+        storage.commit().unwrap();
 
         let ph0 = compute_page_hash!(addr, 0, &zero_page());
         let ph1 = compute_page_hash!(addr, 1, &zero_page());
@@ -413,7 +508,7 @@ mod tests {
 
         storage.write_page(PageIndex(0), &[10, 20, 30]);
         assert_dirty_pages_count!(storage, 1);
-        storage.commit();
+        storage.commit().unwrap();
 
         let ph0 = compute_page_hash!(addr, 0, &[10, 20, 30]);
         let ph1 = compute_page_hash!(addr, 1, &zero_page());
@@ -437,7 +532,7 @@ mod tests {
         storage.write_page(PageIndex(0), &[10, 20, 30]);
         storage.write_page(PageIndex(1), &[40, 50, 60]);
         assert_dirty_pages_count!(storage, 2);
-        storage.commit();
+        storage.commit().unwrap();
 
         let ph0 = compute_page_hash!(addr, 0, &[10, 20, 30]);
         let ph1 = compute_page_hash!(addr, 1, &[40, 50, 60]);
@@ -458,14 +553,14 @@ mod tests {
     fn second_run_after_first_run_with_no_modifications() {
         // 1st run
         mem_merkle_pages_setup!(0x11_22_33_44, addr, storage, kv, 3);
-        storage.commit();
+        storage.commit().unwrap();
         let old_state = storage.get_state();
 
         // 2nd run
         mem_merkle_pages_open!(0x11_22_33_44, addr, storage, kv, old_state, 3);
         storage.write_page(PageIndex(0), &[10, 20, 30]);
         storage.write_page(PageIndex(1), &[40, 50, 60]);
-        storage.commit();
+        storage.commit().unwrap();
 
         // modifying pages `0` and `1`
         let ph0 = compute_page_hash!(addr, 0, &[10, 20, 30]);
@@ -490,14 +585,14 @@ mod tests {
         // 1st run
         mem_merkle_pages_setup!(0x11_22_33_44, addr, storage, kv, 3);
         storage.write_page(PageIndex(0), &[11, 22, 33]);
-        storage.commit();
+        storage.commit().unwrap();
         let old_state = storage.get_state();
 
         // 2nd run
         mem_merkle_pages_open!(0x11_22_33_44, addr, storage, kv, old_state, 3);
         storage.write_page(PageIndex(0), &[10, 20, 30]);
         storage.write_page(PageIndex(1), &[40, 50, 60]);
-        storage.commit();
+        storage.commit().unwrap();
 
         // modifying pages `0` and `1`
         let ph0_old = compute_page_hash!(addr, 0, &[11, 22, 33]);
@@ -529,14 +624,14 @@ mod tests {
         // 1st run
         mem_merkle_pages_setup!(0x11_22_33_44, addr, storage, kv, 3);
         storage.write_page(PageIndex(0), &[11, 22, 33]);
-        storage.commit();
+        storage.commit().unwrap();
         let state_1 = storage.get_state();
 
         // 2nd run
         mem_merkle_pages_open!(0x11_22_33_44, addr, storage, kv, state_1, 3);
         storage.write_page(PageIndex(0), &[10, 20, 30]);
         storage.write_page(PageIndex(1), &[40, 50, 60]);
-        storage.commit();
+        storage.commit().unwrap();
         let state_2 = storage.get_state();
 
         // 3rd run (rollbacks to `state_1` initial state)
@@ -571,4 +666,172 @@ mod tests {
         assert_key_value!(kv, state_2.bytes(), jph);
         assert_state!(state_2, storage);
     }
+
+    #[test]
+    fn merkle_tree_state_page_proofs() {
+        use crate::default::DefaultMerkleTreeStateHasher;
+        use crate::memory::MemMerkleTreePages;
+
+        let addr = Address::from(0x11_22_33_44 as u32);
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut storage = MemMerkleTreePages::new(addr.clone(), Rc::clone(&kv), State::empty(), 3);
+
+        storage.write_page(PageIndex(1), &[10, 20, 30]);
+        storage.commit().unwrap();
+        let state = storage.get_state();
+
+        // a light client holding only `state`, the page data and its proof
+        let proof = storage.prove_page(PageIndex(1));
+        assert_eq!(compute_page_hash!(addr, 1, &[10, 20, 30]), proof.page_hash);
+        assert!(DefaultMerkleTreeStateHasher::verify(&state, &proof));
+
+        let proof = storage.prove_page(PageIndex(2));
+        assert_eq!(compute_page_hash!(addr, 2, &zero_page()), proof.page_hash);
+        assert!(DefaultMerkleTreeStateHasher::verify(&state, &proof));
+
+        // reopening the storage
+        let storage = MemMerkleTreePages::new(addr, Rc::clone(&kv), state.clone(), 3);
+        let proof = storage.prove_page(PageIndex(1));
+        assert!(DefaultMerkleTreeStateHasher::verify(&state, &proof));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not allowed to prove a dirty pages-storage")]
+    fn merkle_tree_state_proving_dirty_storage_panics() {
+        use crate::memory::MemMerkleTreePages;
+
+        let addr = Address::from(0x11_22_33_44 as u32);
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let mut storage = MemMerkleTreePages::new(addr, kv, State::empty(), 3);
+
+        storage.write_page(PageIndex(0), &[10, 20, 30]);
+        let _ = storage.prove_page(PageIndex(0));
+    }
+
+    #[test]
+    fn writing_beyond_pages_count_grows_the_storage() {
+        // 1st run (starting with no pages)
+        mem_merkle_pages_setup!(0x11_22_33_44, addr, storage, kv, 0);
+        assert_eq!(0, storage.pages_count());
+        assert_page!(storage, 1, None);
+
+        storage.write_page(PageIndex(1), &[10, 20, 30]);
+        assert_eq!(2, storage.pages_count());
+        storage.commit().unwrap();
+
+        let ph0 = compute_page_hash!(addr, 0, &zero_page());
+        let ph1 = compute_page_hash!(addr, 1, &[10, 20, 30]);
+        let jph = join_pages_hash!(&[ph0, ph1]);
+        let state = compute_state!(jph);
+
+        assert_state!(state, storage);
+        assert_key_value!(kv, state.bytes(), jph);
+
+        // 2nd run. the pages count is recorded by the state entry (the `pages_count` parameter is ignored)
+        mem_merkle_pages_open!(0x11_22_33_44, addr, storage, kv, state, 5);
+        assert_eq!(2, storage.pages_count());
+        assert_page!(storage, 1, Some(vec![10, 20, 30]));
+        assert_page!(storage, 4, None);
+        assert_eq!(
+            compute_page_hash!(addr, 4, &zero_page()),
+            storage.get_page_hash(PageIndex(4))
+        );
+
+        storage.grow(3);
+        assert_eq!(3, storage.pages_count());
+    }
+
+    use svm_kv::traits::{KVChange, KVEntries};
+
+    /// A key-value store whose reads always fail (e.g a disk failure)
+    struct FailingReadsKV {
+        kv: MemKVStore,
+    }
+
+    impl KVStore for FailingReadsKV {
+        fn get(&self, _key: &[u8]) -> Result<Option<Vec<u8>>, KVError> {
+            Err(KVError::ReadFailed("disk failure".to_string()))
+        }
+
+        fn write_batch(&mut self, changes: &[KVChange]) -> Result<(), KVError> {
+            self.kv.write_batch(changes)
+        }
+
+        fn range(&self, _from: &[u8], _to: Option<&[u8]>) -> Result<KVEntries, KVError> {
+            Err(KVError::ReadFailed("disk failure".to_string()))
+        }
+
+        fn keys_from(&self, _from: &[u8], _limit: usize) -> Result<Vec<Vec<u8>>, KVError> {
+            Err(KVError::ReadFailed("disk failure".to_string()))
+        }
+    }
+
+    #[test]
+    fn missing_state_fails_commit() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let state = State::from(0x10_20_30_40);
+
+        mem_merkle_pages_open!(0x11_22_33_44, addr, storage, kv, state, 3);
+        assert_eq!(3, storage.pages_count());
+        assert_page!(storage, 0, None);
+
+        storage.write_page(PageIndex(0), &[10, 20, 30]);
+
+        let expected = KVError::ReadFailed(format!("state {:?} not found", state.as_slice()));
+        assert_eq!(Err(expected), storage.commit());
+
+        // nothing has been persisted and the state is unchanged
+        assert!(kv_keys_vec!(kv).is_empty());
+        assert_state!(state, storage);
+    }
+
+    #[test]
+    fn corrupted_state_entry_fails_commit() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+        let state = State::from(0x10_20_30_40);
+
+        // the state entry isn't a concatenation of 32 bytes pages-hash
+        kv.borrow_mut()
+            .store(&[(state.as_slice(), &[0xFF; 33][..])])
+            .unwrap();
+
+        mem_merkle_pages_open!(0x11_22_33_44, addr, storage, kv, state, 3);
+        assert_eq!(3, storage.pages_count());
+
+        match storage.commit() {
+            Err(KVError::ReadFailed(msg)) => assert!(msg.contains("corrupted")),
+            _ => unreachable!(),
+        }
+
+        assert_eq!(1, kv_keys_vec!(kv).len());
+        assert_state!(state, storage);
+    }
+
+    #[test]
+    fn failed_page_read_fails_commit() {
+        use crate::default::{DefaultPageHasher, DefaultStateHasher};
+
+        let addr = Address::from(0x11_22_33_44 as u32);
+        let kv = Rc::new(RefCell::new(FailingReadsKV {
+            kv: MemKVStore::new(),
+        }));
+
+        let mut storage = MerklePagesStorage::<_, DefaultPageHasher, DefaultStateHasher>::new(
+            addr,
+            Rc::clone(&kv),
+            State::empty(),
+            3,
+        );
+
+        // a failed read is treated as a missing page
+        assert_eq!(None, storage.read_page(PageIndex(0)));
+        storage.write_page(PageIndex(1), &[10, 20, 30]);
+
+        let expected = KVError::ReadFailed("disk failure".to_string());
+        assert_eq!(Err(expected), storage.commit());
+
+        // nothing has been persisted and the state is unchanged
+        assert!(kv.borrow().kv.keys().next().is_none());
+        assert_state!(State::empty(), storage);
+    }
 }
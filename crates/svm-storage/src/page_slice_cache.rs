@@ -1,8 +1,10 @@
 use crate::page;
 use crate::page::{PageIndex, PageSliceLayout};
 use crate::traits::PageCache;
+use crate::StorageError;
 use std::collections::HashMap;
 use svm_common::State;
+use svm_kv::KVError;
 
 use log::{debug, trace};
 
@@ -134,8 +136,16 @@ impl<PC: PageCache> PageSliceCache<PC> {
         }
     }
 
+    /// * We validate the page-slice layout. An out of range layout isn't written and its error is returned
+    ///
+    /// * We grow the underlying storage when the page is beyond its current pages count
+    ///
     /// * We insert the new page slice into `cached_slices` as `Cached(PageSlice)` and mark it as dirty
-    pub fn write_page_slice(&mut self, layout: &PageSliceLayout, data: &[u8]) {
+    pub fn write_page_slice(
+        &mut self,
+        layout: &PageSliceLayout,
+        data: &[u8],
+    ) -> Result<(), StorageError> {
         // We don't mind whether the underlying page is already in the cache or not.
         // We just save the new written page-slice and mark it as `dirty`.
         debug!("writing page-slice (not persising yet) {:?}", layout);
@@ -144,7 +154,21 @@ impl<PC: PageCache> PageSliceCache<PC> {
 
         let slice_index = layout.slice_idx.0 as usize;
 
-        assert!(slice_index < self.cached_slices.len());
+        if slice_index >= self.cached_slices.len() {
+            return Err(StorageError::SliceIndexOutOfRange {
+                slice_idx: layout.slice_idx,
+                max_slices: self.cached_slices.len() as u32,
+            });
+        }
+
+        if layout.offset as usize + layout.len as usize > page::PAGE_SIZE {
+            return Err(StorageError::SliceOutOfPage {
+                offset: layout.offset,
+                len: layout.len,
+            });
+        }
+
+        self.grow_to_page(layout.page_idx)?;
 
         let slice = PageSlice {
             layout: layout.clone(),
@@ -156,6 +180,75 @@ impl<PC: PageCache> PageSliceCache<PC> {
             &mut self.cached_slices[slice_index],
             CachedPageSlice::Cached(slice),
         );
+
+        Ok(())
+    }
+
+    /// Reads page `page_idx` as a whole (a zero page when the page has no content).
+    /// Intended for pages which aren't accessed via page-slices (see `collections`).
+    pub fn read_page(&mut self, page_idx: PageIndex) -> Vec<u8> {
+        debug!("reading page #{}", page_idx.0);
+
+        match self.page_cache.read_page(page_idx) {
+            Some(page) => page,
+            None => page::zero_page(),
+        }
+    }
+
+    /// Writes page `page_idx` as a whole (not persisting yet).
+    /// Intended for pages which aren't accessed via page-slices (see `collections`).
+    /// The underlying storage grows when the page is beyond its current pages count.
+    pub fn write_page(&mut self, page_idx: PageIndex, page: &[u8]) -> Result<(), StorageError> {
+        debug!("writing page #{} (not persisting yet)", page_idx.0);
+
+        self.grow_to_page(page_idx)?;
+        self.page_cache.write_page(page_idx, page);
+
+        Ok(())
+    }
+
+    /// Returns the current number of storage pages
+    #[must_use]
+    pub fn pages_count(&self) -> u32 {
+        self.page_cache.pages_count()
+    }
+
+    /// Returns the maximum number of storage pages
+    #[must_use]
+    pub fn max_pages(&self) -> u32 {
+        self.page_cache.max_pages()
+    }
+
+    /// Grows the storage to `pages_count` pages (does nothing when it already has that many pages).
+    /// Returns an error when `pages_count` is greater than `max_pages`.
+    pub fn grow(&mut self, pages_count: u32) -> Result<(), StorageError> {
+        let max_pages = self.max_pages();
+
+        if pages_count > max_pages {
+            return Err(StorageError::PageIndexOutOfRange {
+                page_idx: PageIndex(pages_count - 1),
+                max_pages,
+            });
+        }
+
+        self.page_cache.grow(pages_count);
+
+        Ok(())
+    }
+
+    /// Grows the storage to include page `page_idx`.
+    /// Returns an error when `page_idx` isn't less than `max_pages`.
+    fn grow_to_page(&mut self, page_idx: PageIndex) -> Result<(), StorageError> {
+        let max_pages = self.max_pages();
+
+        if page_idx.0 >= max_pages {
+            return Err(StorageError::PageIndexOutOfRange {
+                page_idx,
+                max_pages,
+            });
+        }
+
+        self.grow(page_idx.0 + 1)
     }
 
     /// * Clears the `cached_slices`
@@ -181,12 +274,12 @@ impl<PC: PageCache> PageSliceCache<PC> {
     /// * For each patched cached page we do `page_cache.write_page(..)`
     ///
     /// * We do `page_cache.commit()` and return the new underlying page-storage `State`.
+    ///   When the underlying database fails, returns its error (and no new state is adopted).
     ///
     /// * We don't do a `clear`. In real-life usage, the `svm` will call a `commit()`
     ///   after terimnating execution of the smart contract. The `clear` method is intended to be
     ///   used *only* for `tests`
-    #[must_use]
-    pub fn commit(&mut self) -> State {
+    pub fn commit(&mut self) -> Result<State, KVError> {
         debug!("commiting page-slice cache to underlying pages-storage");
 
         let mut page_slices = HashMap::<u32, Vec<PageSlice>>::new();
@@ -231,13 +324,13 @@ impl<PC: PageCache> PageSliceCache<PC> {
             self.page_cache.write_page(page_idx, &page);
         }
 
-        self.page_cache.commit();
+        self.page_cache.commit()?;
         debug!("finished commiting page-slice cache changes...");
 
         let state = self.page_cache.get_state();
         debug!("new contract state: {:?}", state);
 
-        state
+        Ok(state)
     }
 
     /// Applies `slice` on top of a `page`
@@ -354,13 +447,13 @@ mod tests {
 
         assert_eq!(None, cache.read_page_slice(&layout));
 
-        cache.write_page_slice(&layout, &vec![10, 20, 30]);
+        cache.write_page_slice(&layout, &vec![10, 20, 30]).unwrap();
 
         assert_eq!(Some(vec![10, 20, 30]), cache.read_page_slice(&layout));
 
         // page is not persisted though since we didn't `commit`
         let ph = default_page_hash!(0x11_22_33_44, 0, &[10, 20, 30]);
-        assert_eq!(None, kv.borrow().get(&ph.0));
+        assert_eq!(Ok(None), kv.borrow().get(&ph.0));
     }
 
     #[test]
@@ -375,8 +468,8 @@ mod tests {
             len: 3,
         };
 
-        cache.write_page_slice(&layout, &[10, 20, 30]);
-        let new_state = cache.commit();
+        cache.write_page_slice(&layout, &[10, 20, 30]).unwrap();
+        let new_state = cache.commit().unwrap();
 
         // asserting persisted data. when viewing in the context of `new_state`.
         reopen_page_slice_cache!(cache, kv, addr, new_state, 2, 100);
@@ -387,7 +480,7 @@ mod tests {
         fill_page(&mut expected_page, &[(100, 10), (101, 20), (102, 30)]);
 
         let ph = default_page_hash!(addr, 1, &expected_page);
-        let actual_page = kv.borrow().get(&ph.0).unwrap();
+        let actual_page = kv.borrow().get(&ph.0).unwrap().unwrap();
 
         assert_eq!(expected_page, actual_page);
     }
@@ -404,8 +497,8 @@ mod tests {
             len: 3,
         };
 
-        cache.write_page_slice(&layout, &vec![10, 20, 30]);
-        let _ = cache.commit();
+        cache.write_page_slice(&layout, &vec![10, 20, 30]).unwrap();
+        cache.commit().unwrap();
 
         let mut expected_page = page::zero_page();
         fill_page(&mut expected_page, &[(100, 10), (101, 20), (102, 30)]);
@@ -413,22 +506,22 @@ mod tests {
         fill_page(&mut expected_page, &[(100, 40), (101, 50), (102, 60)]);
         let ph2 = default_page_hash!(addr, 1, &expected_page);
 
-        let page = kv.borrow().get(&ph1.0).unwrap();
+        let page = kv.borrow().get(&ph1.0).unwrap().unwrap();
         assert_eq!(vec![10, 20, 30], &page[100..103]);
-        &cache.write_page_slice(&layout, &vec![40, 50, 60]);
+        cache.write_page_slice(&layout, &vec![40, 50, 60]).unwrap();
 
         // new page is on the page-cache, but not persisted yet
         assert_eq!(Some(vec![40, 50, 60]), cache.read_page_slice(&layout));
 
-        let page = kv.borrow().get(&ph1.0).unwrap();
+        let page = kv.borrow().get(&ph1.0).unwrap().unwrap();
         assert_eq!(vec![10, 20, 30], &page[100..103]);
 
-        assert_eq!(None, kv.borrow().get(&ph2.0));
+        assert_eq!(Ok(None), kv.borrow().get(&ph2.0));
 
         // now we also persist the new page version
-        let _ = cache.commit();
+        cache.commit().unwrap();
 
-        let page = kv.borrow().get(&ph2.0).unwrap();
+        let page = kv.borrow().get(&ph2.0).unwrap().unwrap();
         assert_eq!(vec![40, 50, 60], &page[100..103]);
     }
 
@@ -451,10 +544,10 @@ mod tests {
         let ph2 = default_page_hash!(addr, 1, &expected_page);
 
         // 1) first page write
-        cache.write_page_slice(&layout, &vec![10, 20, 30]);
+        cache.write_page_slice(&layout, &vec![10, 20, 30]).unwrap();
 
         // 2) commit
-        let _ = cache.commit();
+        cache.commit().unwrap();
 
         // 3) load persisted page (we do a `clear` first to make sure we load from the page cache)
         cache.clear();
@@ -462,19 +555,40 @@ mod tests {
         assert_eq!(vec![10, 20, 30], cache.read_page_slice(&layout).unwrap());
 
         // 4) page override
-        cache.write_page_slice(&layout, &vec![40, 50, 60]);
+        cache.write_page_slice(&layout, &vec![40, 50, 60]).unwrap();
         assert_eq!(vec![40, 50, 60], cache.read_page_slice(&layout).unwrap());
 
         // 5) commit again
-        let page = kv.borrow().get(&ph1.0).unwrap();
+        let page = kv.borrow().get(&ph1.0).unwrap().unwrap();
         assert_eq!(vec![10, 20, 30], &page[100..103]);
 
-        let _ = cache.commit();
+        cache.commit().unwrap();
 
-        let page = kv.borrow().get(&ph2.0).unwrap();
+        let page = kv.borrow().get(&ph2.0).unwrap().unwrap();
         assert_eq!(vec![40, 50, 60], &page[100..103]);
     }
 
+    #[test]
+    fn write_page_and_commit() {
+        let addr = 0x11_22_33_44;
+        page_slice_cache_gen!(cache, kv, addr, 0x00_00_00_00, 2, 100);
+
+        assert_eq!(page::zero_page(), cache.read_page(PageIndex(1)));
+
+        let mut page = page::zero_page();
+        fill_page(&mut page, &[(0, 10), (4095, 20)]);
+
+        cache.write_page(PageIndex(1), &page).unwrap();
+        assert_eq!(page, cache.read_page(PageIndex(1)));
+
+        let new_state = cache.commit().unwrap();
+
+        // asserting persisted data. when viewing in the context of `new_state`.
+        reopen_page_slice_cache!(cache, kv, addr, new_state, 2, 100);
+
+        assert_eq!(page, cache.read_page(PageIndex(1)));
+    }
+
     #[test]
     fn write_two_slices_under_same_page_and_commit() {
         let addr = 0x11_22_33_44;
@@ -502,16 +616,16 @@ mod tests {
 
         let ph = default_page_hash!(addr, 1, &expected_page);
 
-        cache.write_page_slice(&layout1, &vec![10, 20, 30]);
-        cache.write_page_slice(&layout2, &vec![40, 50]);
+        cache.write_page_slice(&layout1, &vec![10, 20, 30]).unwrap();
+        cache.write_page_slice(&layout2, &vec![40, 50]).unwrap();
 
         assert_eq!(vec![10, 20, 30], cache.read_page_slice(&layout1).unwrap());
         assert_eq!(vec![40, 50], cache.read_page_slice(&layout2).unwrap());
 
         // commiting two slices under the same page
-        assert_eq!(None, kv.borrow().get(&ph.0));
+        assert_eq!(Ok(None), kv.borrow().get(&ph.0));
 
-        let new_state = cache.commit();
+        let new_state = cache.commit().unwrap();
 
         // asserting persisted data. when viewing in the context of `new_state`.
         reopen_page_slice_cache!(cache, kv, addr, new_state, 2, 100);
@@ -520,8 +634,127 @@ mod tests {
         assert_eq!(vec![40, 50], cache.read_page_slice(&layout2).unwrap());
 
         // queryind the key-value store directly
-        let page = kv.borrow().get(&ph.0).unwrap();
+        let page = kv.borrow().get(&ph.0).unwrap().unwrap();
         assert_eq!(vec![10, 20, 30], &page[100..103]);
         assert_eq!(vec![40, 50], &page[200..202]);
     }
+
+    #[test]
+    fn write_slice_grows_the_pages_storage() {
+        use crate::default::DefaultPageCache;
+        use crate::memory::MemMerklePages;
+        use svm_kv::memory::MemKVStore;
+
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let addr = 0x11_22_33_44;
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+
+        // the storage has no pages, and the page-cache allows up to 3 pages
+        let pages = reopen_pages_storage!(kv, addr, State::empty(), 0);
+        let page_cache = DefaultPageCache::<MemMerklePages>::new(pages, 3);
+        let mut cache = PageSliceCache::new(page_cache, 100);
+
+        assert_eq!(0, cache.pages_count());
+        assert_eq!(3, cache.max_pages());
+
+        let layout = PageSliceLayout {
+            slice_idx: SliceIndex(0),
+            page_idx: PageIndex(1),
+            offset: 100,
+            len: 3,
+        };
+
+        cache.write_page_slice(&layout, &[10, 20, 30]).unwrap();
+        assert_eq!(2, cache.pages_count());
+
+        let new_state = cache.commit().unwrap();
+
+        reopen_page_slice_cache!(cache, kv, addr, new_state, 3, 100);
+        assert_eq!(2, cache.pages_count());
+        assert_eq!(vec![10, 20, 30], cache.read_page_slice(&layout).unwrap());
+    }
+
+    #[test]
+    fn write_out_of_range_returns_an_error() {
+        page_slice_cache_gen!(cache, kv, 0x11_22_33_44, 0x00_00_00_00, 2, 100);
+
+        let layout = PageSliceLayout {
+            slice_idx: SliceIndex(0),
+            page_idx: PageIndex(2),
+            offset: 100,
+            len: 3,
+        };
+
+        let expected = StorageError::PageIndexOutOfRange {
+            page_idx: PageIndex(2),
+            max_pages: 2,
+        };
+        assert_eq!(
+            Err(expected.clone()),
+            cache.write_page_slice(&layout, &[10, 20, 30])
+        );
+        assert_eq!(
+            Err(expected),
+            cache.write_page(PageIndex(2), &page::zero_page())
+        );
+
+        // the last page index (e.g a negative page index passed by a contract)
+        let layout = PageSliceLayout {
+            slice_idx: SliceIndex(0),
+            page_idx: PageIndex(std::u32::MAX),
+            offset: 100,
+            len: 3,
+        };
+
+        let expected = StorageError::PageIndexOutOfRange {
+            page_idx: PageIndex(std::u32::MAX),
+            max_pages: 2,
+        };
+        assert_eq!(
+            Err(expected.clone()),
+            cache.write_page_slice(&layout, &[10, 20, 30])
+        );
+        assert_eq!(
+            Err(expected),
+            cache.write_page(PageIndex(std::u32::MAX), &page::zero_page())
+        );
+
+        let layout = PageSliceLayout {
+            slice_idx: SliceIndex(100),
+            page_idx: PageIndex(1),
+            offset: 100,
+            len: 3,
+        };
+
+        let expected = StorageError::SliceIndexOutOfRange {
+            slice_idx: SliceIndex(100),
+            max_slices: 100,
+        };
+        assert_eq!(
+            Err(expected),
+            cache.write_page_slice(&layout, &[10, 20, 30])
+        );
+
+        let layout = PageSliceLayout {
+            slice_idx: SliceIndex(0),
+            page_idx: PageIndex(1),
+            offset: 4095,
+            len: 3,
+        };
+
+        let expected = StorageError::SliceOutOfPage {
+            offset: 4095,
+            len: 3,
+        };
+        assert_eq!(
+            Err(expected),
+            cache.write_page_slice(&layout, &[10, 20, 30])
+        );
+
+        // nothing has been written
+        assert_eq!(2, cache.pages_count());
+        assert_eq!(None, cache.read_page_slice(&layout));
+    }
 }
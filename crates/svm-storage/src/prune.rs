@@ -0,0 +1,284 @@
+use crate::page::PAGE_HASH_LEN;
+
+use svm_common::State;
+use svm_kv::traits::KVStore;
+use svm_kv::KVError;
+
+use std::collections::HashSet;
+
+use log::{debug, info, warn};
+
+/// The maximum number of keys read (and deleted) at once while sweeping
+pub const PRUNE_CHUNK_SIZE: usize = 10_000;
+
+/// Pruning error
+#[allow(missing_docs)]
+#[derive(PartialEq, Clone)]
+pub enum PruneError {
+    StoreFailed(KVError),
+    CorruptedState(State),
+}
+
+impl std::error::Error for PruneError {
+    fn description(&self) -> &'static str {
+        match self {
+            PruneError::StoreFailed(_) => "Store failed",
+            PruneError::CorruptedState(_) => "Corrupted state",
+        }
+    }
+}
+
+impl std::fmt::Display for PruneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let msg = match self {
+            PruneError::StoreFailed(e) => format!("Store failed: {:?}", e),
+            PruneError::CorruptedState(state) => {
+                format!("State `{:?}` entry isn't a sequence of pages-hash", state)
+            }
+        };
+
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::fmt::Debug for PruneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        <Self as std::fmt::Display>::fmt(self, f)
+    }
+}
+
+impl From<KVError> for PruneError {
+    fn from(e: KVError) -> Self {
+        PruneError::StoreFailed(e)
+    }
+}
+
+/// The outcome of a pruning run
+#[derive(Debug, Clone, PartialEq)]
+pub struct PruneStats {
+    /// The number of entries retained (live states and their pages)
+    pub kept: usize,
+
+    /// The number of entries deleted
+    pub pruned: usize,
+}
+
+/// Garbage-collects page versions no longer reachable from `live_states` (mark-and-sweep).
+///
+/// Each commit of `MerklePagesStorage` adds the entries:
+/// ```text
+/// state     ---> [page1_hash || page2_hash || .... || pageN_hash]
+/// page_hash ---> page data
+/// ```
+///
+/// * Mark  - each live state entry and the pages-hash it references are reachable.
+/// * Sweep - every other key of `kv` is deleted. The keys are scanned (without their values)
+///   in chunks of `PRUNE_CHUNK_SIZE`, and the garbage of each chunk is deleted in one batch.
+///
+/// `kv` must be dedicated to pages-storage entries, and `live_states` must hold the states
+/// to retain of *all* the contracts sharing `kv` (e.g. the states of the last N layers
+/// plus the current state of each contract). A live state missing from `kv` is skipped,
+/// and a live state whose entry isn't a sequence of pages-hash fails the run before any deletion.
+pub fn prune_pages<KV: KVStore>(
+    kv: &mut KV,
+    live_states: &[State],
+) -> Result<PruneStats, PruneError> {
+    prune_pages_in_chunks(kv, live_states, PRUNE_CHUNK_SIZE)
+}
+
+fn prune_pages_in_chunks<KV: KVStore>(
+    kv: &mut KV,
+    live_states: &[State],
+    chunk_size: usize,
+) -> Result<PruneStats, PruneError> {
+    assert!(chunk_size > 0);
+
+    let reachable = mark(kv, live_states)?;
+
+    let mut stats = PruneStats { kept: 0, pruned: 0 };
+    let mut from = Vec::new();
+
+    loop {
+        let keys = kv.keys_from(&from, chunk_size)?;
+
+        let garbage: Vec<&[u8]> = keys
+            .iter()
+            .map(|k| k.as_slice())
+            .filter(|k| !reachable.contains(*k))
+            .collect();
+
+        stats.kept += keys.len() - garbage.len();
+        stats.pruned += garbage.len();
+
+        if !garbage.is_empty() {
+            kv.delete(&garbage)?;
+        }
+
+        match keys.last() {
+            Some(last) if keys.len() == chunk_size => {
+                // the smallest key following `last`
+                from = last.clone();
+                from.push(0);
+            }
+            _ => break,
+        }
+    }
+
+    info!(
+        "pruned pages-storage (kept = {}, pruned = {})",
+        stats.kept, stats.pruned
+    );
+
+    Ok(stats)
+}
+
+fn mark<KV: KVStore>(kv: &KV, live_states: &[State]) -> Result<HashSet<Vec<u8>>, PruneError> {
+    let mut reachable = HashSet::new();
+
+    for state in live_states {
+        if *state == State::empty() {
+            continue;
+        }
+
+        match kv.get(state.as_slice())? {
+            Some(pages_hash) => {
+                debug!("marking state {:?}", state);
+
+                if pages_hash.len() % PAGE_HASH_LEN != 0 {
+                    return Err(PruneError::CorruptedState(state.clone()));
+                }
+
+                for ph in pages_hash.chunks_exact(PAGE_HASH_LEN) {
+                    reachable.insert(ph.to_vec());
+                }
+
+                reachable.insert(state.as_slice().to_vec());
+            }
+            None => warn!("live state {:?} not found", state),
+        }
+    }
+
+    Ok(reachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::memory::MemMerklePages;
+    use crate::page::PageIndex;
+    use crate::traits::{PagesStateStorage, PagesStorage};
+
+    use svm_common::Address;
+    use svm_kv::memory::MemKVStore;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn commit_pages(kv: &Rc<RefCell<MemKVStore>>, state: &State, pages: &[(u32, &[u8])]) -> State {
+        let addr = Address::from(0x11_22_33_44_u32);
+        let mut storage = MemMerklePages::new(addr, Rc::clone(kv), state.clone(), 3);
+
+        for (page_idx, data) in pages {
+            storage.write_page(PageIndex(*page_idx), data);
+        }
+
+        storage.commit().unwrap();
+        storage.get_state()
+    }
+
+    fn read_page(kv: &Rc<RefCell<MemKVStore>>, state: &State, page_idx: u32) -> Option<Vec<u8>> {
+        let addr = Address::from(0x11_22_33_44_u32);
+        let mut storage = MemMerklePages::new(addr, Rc::clone(kv), state.clone(), 3);
+
+        storage.read_page(PageIndex(page_idx))
+    }
+
+    #[test]
+    fn prune_keeps_only_reachable_pages() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+
+        let state_1 = commit_pages(&kv, &State::empty(), &[(0, &[10, 20, 30])]);
+        let state_2 = commit_pages(&kv, &state_1, &[(1, &[40, 50, 60])]);
+        let state_3 = commit_pages(&kv, &state_2, &[(0, &[70, 80, 90])]);
+
+        // 3 states + 3 pages
+        assert_eq!(6, kv.borrow().keys().count());
+
+        let stats = prune_pages(&mut *kv.borrow_mut(), std::slice::from_ref(&state_3)).unwrap();
+        assert_eq!(PruneStats { kept: 3, pruned: 3 }, stats);
+
+        assert_eq!(Some(vec![70, 80, 90]), read_page(&kv, &state_3, 0));
+        assert_eq!(Some(vec![40, 50, 60]), read_page(&kv, &state_3, 1));
+        assert_eq!(None, kv.borrow().get(state_1.as_slice()).unwrap());
+        assert_eq!(None, kv.borrow().get(state_2.as_slice()).unwrap());
+
+        // pruning again is a no-op
+        let stats = prune_pages(&mut *kv.borrow_mut(), std::slice::from_ref(&state_3)).unwrap();
+        assert_eq!(PruneStats { kept: 3, pruned: 0 }, stats);
+    }
+
+    #[test]
+    fn prune_keeps_pages_shared_by_live_states() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+
+        let state_1 = commit_pages(&kv, &State::empty(), &[(0, &[10, 20, 30])]);
+        let state_2 = commit_pages(&kv, &state_1, &[(1, &[40, 50, 60])]);
+        let state_3 = commit_pages(&kv, &state_2, &[(1, &[70, 80, 90])]);
+
+        let unknown = State::from(0x10_20_30_40);
+        let live = [state_1.clone(), state_3.clone(), unknown, State::empty()];
+
+        // only the page `1` of `state_2` is garbage (besides `state_2` itself)
+        let stats = prune_pages(&mut *kv.borrow_mut(), &live).unwrap();
+        assert_eq!(PruneStats { kept: 4, pruned: 2 }, stats);
+
+        assert_eq!(Some(vec![10, 20, 30]), read_page(&kv, &state_1, 0));
+        assert_eq!(Some(vec![10, 20, 30]), read_page(&kv, &state_3, 0));
+        assert_eq!(Some(vec![70, 80, 90]), read_page(&kv, &state_3, 1));
+    }
+
+    #[test]
+    fn prune_in_chunks_smaller_than_the_store() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+
+        let state_1 = commit_pages(&kv, &State::empty(), &[(0, &[10, 20, 30])]);
+        let state_2 = commit_pages(&kv, &state_1, &[(1, &[40, 50, 60])]);
+        let state_3 = commit_pages(&kv, &state_2, &[(0, &[70, 80, 90])]);
+
+        for chunk_size in 1..=6 {
+            let live = [state_3.clone()];
+            let stats = prune_pages_in_chunks(&mut *kv.borrow_mut(), &live, chunk_size).unwrap();
+
+            if chunk_size == 1 {
+                assert_eq!(PruneStats { kept: 3, pruned: 3 }, stats);
+            } else {
+                assert_eq!(PruneStats { kept: 3, pruned: 0 }, stats);
+            }
+        }
+
+        assert_eq!(3, kv.borrow().keys().count());
+        assert_eq!(Some(vec![70, 80, 90]), read_page(&kv, &state_3, 0));
+        assert_eq!(Some(vec![40, 50, 60]), read_page(&kv, &state_3, 1));
+    }
+
+    #[test]
+    fn prune_fails_on_a_corrupted_live_state() {
+        let kv = Rc::new(RefCell::new(MemKVStore::new()));
+
+        let state_1 = commit_pages(&kv, &State::empty(), &[(0, &[10, 20, 30])]);
+        let state_2 = commit_pages(&kv, &state_1, &[(1, &[40, 50, 60])]);
+
+        let corrupted = State::from(0x10_20_30_40);
+        kv.borrow_mut()
+            .store(&[(corrupted.as_slice(), &[1, 2, 3])])
+            .unwrap();
+
+        let live = [state_2.clone(), corrupted.clone()];
+        let res = prune_pages(&mut *kv.borrow_mut(), &live);
+        assert_eq!(Err(PruneError::CorruptedState(corrupted)), res);
+
+        // nothing has been deleted (2 states + 2 pages + the corrupted entry)
+        assert_eq!(5, kv.borrow().keys().count());
+    }
+}
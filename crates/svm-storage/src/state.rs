@@ -1,3 +1,5 @@
+use crate::page::{PageHash, PageIndex};
+
 /// A Contract's state Hash length is 32 bytes.
 pub const STATE_HASH_LEN: usize = 32;
 
@@ -11,3 +13,24 @@ impl AsRef<[u8]> for StateHash {
         self.0.as_ref()
     }
 }
+
+/// An inclusion proof of a single page-hash under a contract state (see `ProvableStateHasher`).
+///
+/// * `page_idx`    - The proven page index.
+/// * `pages_count` - The number of pages of the contract storage (determines the tree shape).
+/// * `page_hash`   - The proven page-hash.
+/// * `siblings`    - The sibling hashes along the path from the page-hash up to the root (bottom-up).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageProof {
+    /// The proven page index
+    pub page_idx: PageIndex,
+
+    /// The number of pages of the contract storage
+    pub pages_count: u32,
+
+    /// The proven page-hash
+    pub page_hash: PageHash,
+
+    /// The sibling hashes (bottom-up)
+    pub siblings: Vec<[u8; 32]>,
+}
@@ -1,6 +1,7 @@
 use crate::page::{PageHash, PageIndex};
-use crate::state::StateHash;
+use crate::state::{PageProof, StateHash};
 use svm_common::{Address, State};
+use svm_kv::KVError;
 
 /// `PagesStorage` is the most low-level trait for dealing with a contract's storage.
 /// For performance concerns, we work on pages units (a page is 4096 bytes)
@@ -8,6 +9,7 @@ use svm_common::{Address, State};
 /// That is flushed to the underlying database only when calling `commit`
 pub trait PagesStorage {
     /// Retrieves the content of page indexed `page_idx` (Optional)
+    /// A failed read of the backed database is treated as a missing page and is reported by the next `commit`
     #[must_use]
     fn read_page(&mut self, page_idx: PageIndex) -> Option<Vec<u8>>;
 
@@ -19,7 +21,7 @@ pub trait PagesStorage {
     fn clear(&mut self);
 
     /// Persist the pending in-memory dirty pages into the backed database
-    fn commit(&mut self);
+    fn commit(&mut self) -> Result<(), KVError>;
 }
 
 /// `PageIndexHasher` is a trait defining that a contract storage-page hash must be determined by
@@ -55,6 +57,18 @@ pub trait StateHasher {
     fn hash(pages_hash: &[PageHash]) -> StateHash;
 }
 
+/// A `StateHasher` whose state commits to each page-hash on its own,
+/// so that a single page-hash can be proven against a state (without all the other pages hash).
+pub trait ProvableStateHasher: StateHasher {
+    /// Returns an inclusion proof of the page-hash of page `page_idx` within `pages_hash`
+    #[must_use]
+    fn prove(pages_hash: &[PageHash], page_idx: PageIndex) -> PageProof;
+
+    /// Returns whether `proof` proves its page-hash is included under `state`
+    #[must_use]
+    fn verify(state: &State, proof: &PageProof) -> bool;
+}
+
 /// This trait should be implemented by state-oriented pages storage.
 /// Since a Smart Contract must have a state (like a source control revision) we need to have this
 /// capability implemented for real-usage Smart Contract storage.
@@ -66,8 +80,21 @@ pub trait PagesStateStorage: PagesStorage {
     /// Returns the page-hash of a given page indexed by `page_idx`
     #[must_use]
     fn get_page_hash(&self, page_idx: PageIndex) -> PageHash;
+
+    /// Returns the current number of storage pages (recorded by the state entry upon `commit`)
+    #[must_use]
+    fn pages_count(&self) -> u32;
+
+    /// Grows the storage to `pages_count` pages (does nothing when it already has that many pages).
+    /// The new pages are zero pages.
+    fn grow(&mut self, pages_count: u32);
 }
 
-/// `PageCache` is a marker trait intended for subclassing the `PagesStateStorage` trait.
+/// `PageCache` is a trait intended for subclassing the `PagesStateStorage` trait.
 /// It's intended to mark a `PagesStateStorage` as having a caching layer on top of the backed pages-storage.
-pub trait PageCache: PagesStateStorage {}
+pub trait PageCache: PagesStateStorage {
+    /// Returns the maximum number of pages the cache can hold.
+    /// A page index is within the range `0..(max_pages - 1)` (inclusive)
+    #[must_use]
+    fn max_pages(&self) -> u32;
+}